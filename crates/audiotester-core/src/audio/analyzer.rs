@@ -8,6 +8,9 @@
 
 use rustfft::{num_complex::Complex, FftPlanner};
 
+/// Default duration of non-incrementing counter samples before declaring silence
+pub const DEFAULT_SILENCE_THRESHOLD_MS: u32 = 100;
+
 /// Analysis results from comparing sent and received signals
 ///
 /// Note: For latency measurement, prefer using the burst-based system
@@ -39,8 +42,13 @@ pub struct FrameLossResult {
     pub confirmed_lost: usize,
     /// True when the counter signal is absent (all zeros exceeding threshold)
     pub counter_silent: bool,
+    /// True when the counter holds one constant non-zero value for longer than
+    /// the silence threshold (frozen/repeating audio buffer). Implies `counter_silent`.
+    pub counter_stuck: bool,
     /// Number of samples that were analyzed in this call
     pub samples_analyzed: usize,
+    /// Index in the analyzed samples of the first gap (None without loss)
+    pub first_gap_index: Option<usize>,
 }
 
 /// Signal analyzer for loss detection (and legacy MLS correlation)
@@ -69,7 +77,9 @@ pub struct Analyzer {
     has_reference: bool,
     /// Count of consecutive samples where counter is NOT incrementing by 1
     non_incrementing_count: usize,
-    /// Number of non-incrementing samples required to declare silence (sample_rate / 10 = 100ms)
+    /// Count of consecutive samples repeating the same non-zero counter value
+    stuck_count: usize,
+    /// Number of non-incrementing samples required to declare silence (default 100ms)
     silence_threshold: usize,
     /// Whether the previous call was in a silence state (used for recovery resync)
     was_silent: bool,
@@ -121,7 +131,8 @@ impl Analyzer {
             expected_frame: 0,
             has_reference,
             non_incrementing_count: 0,
-            silence_threshold: (sample_rate / 10) as usize,
+            stuck_count: 0,
+            silence_threshold: Self::threshold_samples(sample_rate, DEFAULT_SILENCE_THRESHOLD_MS),
             was_silent: false,
             last_counter: None,
         }
@@ -258,8 +269,9 @@ impl Analyzer {
         }
 
         let mut total_lost = 0usize;
+        let mut first_gap_index = None;
 
-        for &sample in counter_samples {
+        for (index, &sample) in counter_samples.iter().enumerate() {
             // Decode counter from normalized audio (0.0-1.0 → 0-65535)
             let normalized = sample.clamp(0.0, 1.0);
             let received_counter = (normalized * 65536.0) as u32 & 0xFFFF;
@@ -272,6 +284,14 @@ impl Analyzer {
                     (65536 + received_counter as u64 - last as u64) as u32
                 };
 
+                // Stuck detection: the same non-zero value repeating means a
+                // frozen buffer rather than a muted route (which decodes as 0)
+                if diff == 0 && received_counter != 0 {
+                    self.stuck_count += 1;
+                } else {
+                    self.stuck_count = 0;
+                }
+
                 if diff == 1 {
                     // Normal increment: counter is alive
                     self.non_incrementing_count = 0;
@@ -303,6 +323,7 @@ impl Analyzer {
                 };
                 if diff > 1 && diff < 32768 {
                     total_lost += (diff - 1) as usize;
+                    first_gap_index.get_or_insert(index);
                 }
             }
 
@@ -323,7 +344,9 @@ impl Analyzer {
         FrameLossResult {
             confirmed_lost: total_lost,
             counter_silent,
+            counter_stuck: counter_silent && self.stuck_count >= self.silence_threshold,
             samples_analyzed: counter_samples.len(),
+            first_gap_index,
         }
     }
 
@@ -332,6 +355,29 @@ impl Analyzer {
         self.sample_rate
     }
 
+    /// Convert a silence threshold in ms to samples (at least one sample)
+    fn threshold_samples(sample_rate: u32, ms: u32) -> usize {
+        ((sample_rate as u64 * ms as u64 / 1000) as usize).max(1)
+    }
+
+    /// Set how long the counter may stop incrementing before it is declared silent
+    ///
+    /// Lower values detect a muted loopback faster but may flag legitimate
+    /// brief silences. The same threshold gates the recovery resync in
+    /// [`Self::detect_frame_loss`], so it also controls how long a gap can be
+    /// before it is treated as silence instead of being counted as loss.
+    ///
+    /// # Arguments
+    /// * `ms` - Threshold in milliseconds
+    pub fn set_silence_threshold_ms(&mut self, ms: u32) {
+        self.silence_threshold = Self::threshold_samples(self.sample_rate, ms);
+    }
+
+    /// Get the silence threshold in milliseconds
+    pub fn silence_threshold_ms(&self) -> u32 {
+        (self.silence_threshold as u64 * 1000 / self.sample_rate.max(1) as u64) as u32
+    }
+
     /// Check if analyzer has a valid MLS reference for correlation
     pub fn has_reference(&self) -> bool {
         self.has_reference
@@ -342,6 +388,7 @@ impl Analyzer {
         self.last_latency = None;
         self.expected_frame = 0;
         self.non_incrementing_count = 0;
+        self.stuck_count = 0;
         self.was_silent = false;
         self.last_counter = None;
     }
@@ -423,6 +470,8 @@ mod tests {
             result.confirmed_lost
         );
         assert!(!result.counter_silent);
+        // The gap is at the first sample after the skip
+        assert_eq!(result.first_gap_index, Some(100));
     }
 
     #[test]
@@ -435,6 +484,7 @@ mod tests {
         let result = analyzer.detect_frame_loss(&samples);
         assert_eq!(result.confirmed_lost, 0, "Should detect no lost frames");
         assert!(!result.counter_silent);
+        assert_eq!(result.first_gap_index, None);
     }
 
     #[test]
@@ -458,6 +508,62 @@ mod tests {
         assert!(!result.counter_silent);
     }
 
+    #[test]
+    fn test_custom_silence_threshold() {
+        // 20ms at 48kHz = 960 samples
+        let mut analyzer = Analyzer::new(&[], 48000);
+        analyzer.set_silence_threshold_ms(20);
+        assert_eq!(analyzer.silence_threshold_ms(), 20);
+
+        let mut samples: Vec<f32> = (0..100).map(|i| i as f32 / 65536.0).collect();
+        samples.extend(std::iter::repeat_n(0.0f32, 1000));
+        let result = analyzer.detect_frame_loss(&samples);
+        assert!(result.counter_silent, "1000 stuck samples exceed 20ms");
+
+        // Default 100ms threshold (4800 samples) does not trigger on the same input
+        let mut analyzer = Analyzer::new(&[], 48000);
+        let result = analyzer.detect_frame_loss(&samples);
+        assert!(!result.counter_silent);
+
+        // Recovery resync still suppresses the false loss spike
+        let mut analyzer = Analyzer::new(&[], 48000);
+        analyzer.set_silence_threshold_ms(20);
+        analyzer.detect_frame_loss(&samples);
+        let resumed: Vec<f32> = (30000..30100).map(|i| i as f32 / 65536.0).collect();
+        let result = analyzer.detect_frame_loss(&resumed);
+        assert_eq!(result.confirmed_lost, 0);
+        assert!(!result.counter_silent);
+    }
+
+    #[test]
+    fn test_stuck_counter_detection() {
+        let mut analyzer = Analyzer::new(&[], 48000);
+
+        // Healthy counter, then the buffer freezes on one value for 200ms
+        let mut samples: Vec<f32> = (0..100).map(|i| i as f32 / 65536.0).collect();
+        samples.extend(std::iter::repeat_n(99.0 / 65536.0, 9600));
+
+        let result = analyzer.detect_frame_loss(&samples);
+        assert!(
+            result.counter_stuck,
+            "Constant counter should be flagged stuck"
+        );
+        assert!(result.counter_silent);
+        assert_eq!(result.confirmed_lost, 0);
+    }
+
+    #[test]
+    fn test_muted_counter_is_not_stuck() {
+        let mut analyzer = Analyzer::new(&[], 48000);
+
+        let mut samples: Vec<f32> = (0..100).map(|i| i as f32 / 65536.0).collect();
+        samples.extend(std::iter::repeat_n(0.0f32, 9600));
+
+        let result = analyzer.detect_frame_loss(&samples);
+        assert!(result.counter_silent);
+        assert!(!result.counter_stuck, "Muted route decodes as 0, not stuck");
+    }
+
     #[test]
     fn test_reset() {
         let gen = MlsGenerator::new(10);
@@ -1,15 +1,58 @@
 //! Burst signal generation for frame-based latency measurement
 //!
-//! Generates a 10ms burst of white noise every 100ms, enabling precise
-//! latency measurement through frame counter comparison.
+//! Generates a burst at the end of every cycle (10ms every 100ms by
+//! default), enabling precise latency measurement through frame counter
+//! comparison. The burst is white noise unless another [`BurstWaveform`]
+//! is selected.
 //! This approach measures latency via sample counting rather than wall-clock
 //! timestamps, eliminating ring buffer accumulation delays.
 
-/// Duration of silence before burst (90ms of 100ms cycle)
-const SILENCE_RATIO: f32 = 0.9;
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
 
-/// Burst amplitude (-6dB for headroom)
-const BURST_AMPLITUDE: f32 = 0.5;
+use crate::{BURST_CYCLE_MS, BURST_DURATION_MS};
+
+/// Shortest supported burst cycle in milliseconds (100Hz update rate)
+pub const MIN_CYCLE_MS: u32 = 10;
+
+/// Longest supported burst cycle in milliseconds
+pub const MAX_CYCLE_MS: u32 = 1000;
+
+/// Default burst amplitude (-6dB for headroom)
+pub const BURST_AMPLITUDE: f32 = 0.5;
+
+/// Highest configurable burst amplitude (-1dB, keeps noise peaks off full scale)
+pub const MAX_BURST_AMPLITUDE: f32 = 0.9;
+
+/// Chirp start frequency (Hz)
+pub const CHIRP_START_HZ: f32 = 300.0;
+
+/// Chirp end frequency (Hz), inside the passband of voice-grade paths
+pub const CHIRP_END_HZ: f32 = 3000.0;
+
+/// Waveform played during the burst
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BurstWaveform {
+    /// White noise (broadband, the original burst)
+    #[default]
+    Noise,
+    /// Linear sweep from [`CHIRP_START_HZ`] to [`CHIRP_END_HZ`]; survives
+    /// band-limited paths and starts at full amplitude for a sharp edge
+    Chirp,
+    /// A single full-amplitude sample at the burst start
+    Impulse,
+}
+
+impl std::fmt::Display for BurstWaveform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            BurstWaveform::Noise => "noise",
+            BurstWaveform::Chirp => "chirp",
+            BurstWaveform::Impulse => "impulse",
+        })
+    }
+}
 
 /// Event emitted when a burst starts in the output callback
 #[derive(Debug, Clone)]
@@ -27,7 +70,9 @@ pub struct DetectionEvent {
 
 /// Burst signal generator for latency measurement
 ///
-/// Generates a 10ms burst of white noise every 100ms cycle.
+/// Generates a burst at the end of each cycle (10ms every 100ms unless
+/// configured with [`BurstGenerator::with_params`]), white noise by default
+/// (see [`BurstGenerator::set_waveform`]).
 /// The burst timing is captured via [`BurstEvent`] for timestamp-based
 /// latency calculation.
 ///
@@ -42,9 +87,13 @@ pub struct DetectionEvent {
 pub struct BurstGenerator {
     /// Sample rate in Hz
     sample_rate: u32,
-    /// Total cycle length in samples (100ms)
+    /// Cycle period in milliseconds
+    cycle_ms: u32,
+    /// Burst duration in milliseconds
+    burst_ms: u32,
+    /// Total cycle length in samples
     cycle_length: usize,
-    /// Position where burst starts (90ms into cycle)
+    /// Position where burst starts (cycle length minus burst length)
     burst_start_position: usize,
     /// Current position in cycle (0..cycle_length)
     cycle_position: usize,
@@ -52,6 +101,8 @@ pub struct BurstGenerator {
     noise_seed: u32,
     /// Amplitude scaling factor
     amplitude: f32,
+    /// Waveform played during the burst
+    waveform: BurstWaveform,
 }
 
 impl BurstGenerator {
@@ -68,23 +119,69 @@ impl BurstGenerator {
     /// assert_eq!(gen.cycle_length(), 9600); // 100ms at 96kHz
     /// ```
     pub fn new(sample_rate: u32) -> Self {
-        let cycle_length = (sample_rate as f64 * 0.1) as usize; // 100ms
-        let burst_start_position = (cycle_length as f32 * SILENCE_RATIO) as usize;
+        Self::with_params(sample_rate, BURST_CYCLE_MS, BURST_DURATION_MS)
+            .expect("default burst timing is valid")
+    }
 
-        Self {
+    /// Create a burst generator with a custom cycle period and burst length
+    ///
+    /// The cycle must be longer than the round-trip latency being measured,
+    /// otherwise detections match the wrong burst.
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `cycle_ms` - Cycle period in milliseconds (update rate = 1000 / cycle_ms)
+    /// * `burst_ms` - Burst duration in milliseconds, less than `cycle_ms`
+    ///
+    /// # Example
+    /// ```
+    /// use audiotester_core::audio::burst::BurstGenerator;
+    ///
+    /// let gen = BurstGenerator::with_params(96000, 20, 5).unwrap();
+    /// assert_eq!(gen.cycle_length(), 1920); // 20ms at 96kHz
+    /// assert!(BurstGenerator::with_params(96000, 20, 20).is_err());
+    /// ```
+    pub fn with_params(sample_rate: u32, cycle_ms: u32, burst_ms: u32) -> Result<Self> {
+        Self::validate_timing(cycle_ms, burst_ms)?;
+
+        let cycle_length = (sample_rate as u64 * cycle_ms as u64 / 1000) as usize;
+        let burst_length = (sample_rate as u64 * burst_ms as u64 / 1000) as usize;
+
+        Ok(Self {
             sample_rate,
+            cycle_ms,
+            burst_ms,
             cycle_length,
-            burst_start_position,
+            burst_start_position: cycle_length - burst_length,
             cycle_position: 0,
             noise_seed: 0xDEADBEEF,
             amplitude: BURST_AMPLITUDE,
-        }
+            waveform: BurstWaveform::Noise,
+        })
+    }
+
+    /// Check a cycle period / burst duration pair
+    pub fn validate_timing(cycle_ms: u32, burst_ms: u32) -> Result<()> {
+        ensure!(
+            (MIN_CYCLE_MS..=MAX_CYCLE_MS).contains(&cycle_ms),
+            "Burst cycle must be {}-{}ms (got {}ms)",
+            MIN_CYCLE_MS,
+            MAX_CYCLE_MS,
+            cycle_ms
+        );
+        ensure!(
+            burst_ms > 0 && burst_ms < cycle_ms,
+            "Burst duration must be between 1ms and the cycle period ({}ms), got {}ms",
+            cycle_ms,
+            burst_ms
+        );
+        Ok(())
     }
 
     /// Get the next sample from the generator
     ///
     /// Returns a tuple of (sample, is_burst_start).
-    /// - `sample` is 0.0 during silence, white noise during burst
+    /// - `sample` is 0.0 during silence, the burst waveform during burst
     /// - `is_burst_start` is true only on the first sample of each burst
     ///
     /// # Example
@@ -102,7 +199,14 @@ impl BurstGenerator {
         let in_burst = self.cycle_position >= self.burst_start_position;
 
         let sample = if in_burst {
-            self.generate_noise() * self.amplitude
+            let offset = self.cycle_position - self.burst_start_position;
+            let value = match self.waveform {
+                BurstWaveform::Noise => self.generate_noise(),
+                BurstWaveform::Chirp => self.chirp_sample(offset),
+                BurstWaveform::Impulse if offset == 0 => 1.0,
+                BurstWaveform::Impulse => 0.0,
+            };
+            value * self.amplitude
         } else {
             0.0
         };
@@ -125,6 +229,19 @@ impl BurstGenerator {
         (bits as f32 / 16384.0) - 1.0
     }
 
+    /// Chirp sample `offset` samples into the burst
+    ///
+    /// A cosine, so the sweep starts at full amplitude. The phase is computed
+    /// from the offset rather than accumulated, so every burst is identical.
+    fn chirp_sample(&self, offset: usize) -> f32 {
+        let t = offset as f64 / self.sample_rate as f64;
+        let duration = self.burst_duration() as f64 / self.sample_rate as f64;
+        let sweep_rate = (CHIRP_END_HZ - CHIRP_START_HZ) as f64 / duration;
+        let phase =
+            2.0 * std::f64::consts::PI * (CHIRP_START_HZ as f64 * t + 0.5 * sweep_rate * t * t);
+        phase.cos() as f32
+    }
+
     /// Fill a buffer with sequential samples
     ///
     /// Returns a vector of frame indices where bursts started within this buffer.
@@ -146,17 +263,27 @@ impl BurstGenerator {
         burst_starts
     }
 
-    /// Get cycle length in samples (100ms at configured sample rate)
+    /// Get cycle length in samples
     pub fn cycle_length(&self) -> usize {
         self.cycle_length
     }
 
-    /// Get burst start position within cycle (90% into cycle)
+    /// Get cycle period in milliseconds
+    pub fn cycle_ms(&self) -> u32 {
+        self.cycle_ms
+    }
+
+    /// Get burst duration in milliseconds
+    pub fn burst_ms(&self) -> u32 {
+        self.burst_ms
+    }
+
+    /// Get burst start position within cycle (cycle length minus burst length)
     pub fn burst_start_position(&self) -> usize {
         self.burst_start_position
     }
 
-    /// Get burst duration in samples (10ms at configured sample rate)
+    /// Get burst duration in samples
     pub fn burst_duration(&self) -> usize {
         self.cycle_length - self.burst_start_position
     }
@@ -195,7 +322,20 @@ impl BurstGenerator {
         self.amplitude
     }
 
-    /// Get the burst update rate in Hz (10 measurements per second)
+    /// Select the burst waveform
+    ///
+    /// Detect impulses with [`BurstDetector::set_instant_attack`](crate::audio::detector::BurstDetector::set_instant_attack)
+    /// enabled, a single sample barely moves the default envelope.
+    pub fn set_waveform(&mut self, waveform: BurstWaveform) {
+        self.waveform = waveform;
+    }
+
+    /// Get the burst waveform
+    pub fn waveform(&self) -> BurstWaveform {
+        self.waveform
+    }
+
+    /// Get the burst update rate in Hz (10 measurements per second by default)
     pub fn update_rate(&self) -> f32 {
         self.sample_rate as f32 / self.cycle_length as f32
     }
@@ -343,6 +483,31 @@ mod tests {
         assert!((gen.update_rate() - 10.0).abs() < 0.01); // 10 Hz
     }
 
+    #[test]
+    fn test_custom_timing_50hz() {
+        let mut gen = BurstGenerator::with_params(96000, 20, 5).unwrap();
+        assert_eq!(gen.cycle_length(), 1920);
+        assert_eq!(gen.burst_duration(), 480);
+        assert_eq!(gen.burst_start_position(), 1440);
+        assert_eq!((gen.cycle_ms(), gen.burst_ms()), (20, 5));
+        assert!((gen.update_rate() - 50.0).abs() < 0.01);
+
+        // One burst start per 20ms cycle: 50 over one second
+        let mut buffer = vec![0.0f32; 96000];
+        let starts = gen.fill_buffer(&mut buffer);
+        assert_eq!(starts.len(), 50);
+        assert!(starts.windows(2).all(|w| w[1] - w[0] == 1920));
+    }
+
+    #[test]
+    fn test_invalid_timing_rejected() {
+        assert!(BurstGenerator::with_params(48000, 20, 20).is_err());
+        assert!(BurstGenerator::with_params(48000, 20, 25).is_err());
+        assert!(BurstGenerator::with_params(48000, 20, 0).is_err());
+        assert!(BurstGenerator::with_params(48000, MIN_CYCLE_MS - 1, 1).is_err());
+        assert!(BurstGenerator::with_params(48000, 100, 20).is_ok());
+    }
+
     #[test]
     fn test_reset() {
         let mut gen = BurstGenerator::new(48000);
@@ -372,4 +537,58 @@ mod tests {
             assert!(sample.abs() <= 0.25, "Sample {} exceeds amplitude", sample);
         }
     }
+
+    #[test]
+    fn test_waveforms() {
+        let burst = |waveform| {
+            let mut gen = BurstGenerator::new(48000);
+            gen.set_waveform(waveform);
+            let mut buffer = vec![0.0f32; gen.cycle_length()];
+            gen.fill_buffer(&mut buffer);
+            buffer.split_off(gen.burst_start_position())
+        };
+
+        // Chirp starts at full amplitude and stays within it
+        let chirp = burst(BurstWaveform::Chirp);
+        assert_eq!(chirp[0], BURST_AMPLITUDE);
+        assert!(chirp.iter().all(|s| s.abs() <= BURST_AMPLITUDE));
+        assert_eq!(
+            burst(BurstWaveform::Chirp),
+            chirp,
+            "every chirp is identical"
+        );
+
+        // Impulse is a single sample
+        let impulse = burst(BurstWaveform::Impulse);
+        assert_eq!(impulse[0], BURST_AMPLITUDE);
+        assert!(impulse[1..].iter().all(|&s| s == 0.0));
+
+        assert_eq!(serde_json::to_value(BurstWaveform::Chirp).unwrap(), "chirp");
+        assert_eq!(BurstWaveform::Impulse.to_string(), "impulse");
+    }
+
+    #[test]
+    fn test_burst_samples_scale_with_amplitude() {
+        // Same noise seed, so samples differ only by the amplitude factor
+        let mut quiet = BurstGenerator::new(48000);
+        let mut loud = BurstGenerator::new(48000);
+        quiet.set_amplitude(0.2);
+        loud.set_amplitude(0.4);
+
+        for _ in 0..quiet.burst_start_position() {
+            quiet.next_sample();
+            loud.next_sample();
+        }
+
+        let mut nonzero = 0;
+        for _ in 0..100 {
+            let (q, _) = quiet.next_sample();
+            let (l, _) = loud.next_sample();
+            assert!((l - 2.0 * q).abs() < 1e-6, "{} is not twice {}", l, q);
+            if q != 0.0 {
+                nonzero += 1;
+            }
+        }
+        assert!(nonzero > 0);
+    }
 }
@@ -0,0 +1,213 @@
+//! WAV capture of the input burst channel for offline debugging
+//!
+//! While a capture is armed, the input callback pushes channel-0 samples
+//! into a bounded ring buffer. A background thread drains the ring into a
+//! WAV file, so the audio callback never blocks on locks or disk I/O.
+
+use anyhow::{anyhow, Context, Result};
+use ringbuf::traits::{Consumer, Producer, Split};
+use ringbuf::HeapRb;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Capture ring size in samples (~2.7 seconds at 96kHz)
+const CAPTURE_RING_SIZE: usize = 1 << 18;
+
+/// How often the writer thread drains the ring
+const DRAIN_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Longest capture accepted (seconds)
+pub const MAX_CAPTURE_SECS: u32 = 300;
+
+/// Callback side of a capture channel, owned by the input closure
+pub struct CaptureTap {
+    producer: ringbuf::HeapProd<f32>,
+    armed: Arc<AtomicBool>,
+}
+
+impl CaptureTap {
+    /// Whether a capture is currently recording
+    #[inline]
+    pub fn is_armed(&self) -> bool {
+        self.armed.load(Ordering::Relaxed)
+    }
+
+    /// Queue a sample for writing (dropped if the ring is full)
+    #[inline]
+    pub fn push(&mut self, sample: f32) {
+        let _ = self.producer.try_push(sample);
+    }
+}
+
+/// Engine side of a capture channel, used to start captures
+pub struct CaptureSource {
+    consumer: Arc<Mutex<ringbuf::HeapCons<f32>>>,
+    armed: Arc<AtomicBool>,
+    sample_rate: u32,
+}
+
+/// A capture being written by its background thread
+pub struct ActiveCapture {
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<Result<()>>,
+}
+
+/// Create a connected tap/source pair for the given sample rate
+pub fn capture_channel(sample_rate: u32) -> (CaptureTap, CaptureSource) {
+    let (producer, consumer) = HeapRb::<f32>::new(CAPTURE_RING_SIZE).split();
+    let armed = Arc::new(AtomicBool::new(false));
+    (
+        CaptureTap {
+            producer,
+            armed: Arc::clone(&armed),
+        },
+        CaptureSource {
+            consumer: Arc::new(Mutex::new(consumer)),
+            armed,
+            sample_rate,
+        },
+    )
+}
+
+impl CaptureSource {
+    /// Start writing `duration_secs` of captured samples to a mono WAV at `path`
+    ///
+    /// Fails if a capture from this source is still recording.
+    pub fn start(&self, path: PathBuf, duration_secs: u32) -> Result<ActiveCapture> {
+        if self.armed.load(Ordering::Acquire) {
+            return Err(anyhow!("Capture already in progress"));
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        // Discard anything left over from a previous capture before arming
+        if let Ok(mut consumer) = self.consumer.lock() {
+            consumer.clear();
+        }
+        self.armed.store(true, Ordering::Release);
+
+        let consumer = Arc::clone(&self.consumer);
+        let armed = Arc::clone(&self.armed);
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let total = duration_secs as u64 * self.sample_rate as u64;
+
+        let handle = std::thread::Builder::new()
+            .name("wav-capture".into())
+            .spawn(move || {
+                let mut buffer = vec![0.0f32; 4096];
+                let mut written: u64 = 0;
+                let result = (|| {
+                    while written < total && !thread_stop.load(Ordering::Acquire) {
+                        std::thread::sleep(DRAIN_INTERVAL);
+                        let mut consumer = consumer
+                            .lock()
+                            .map_err(|_| anyhow!("Capture ring lock poisoned"))?;
+                        loop {
+                            let want = buffer.len().min((total - written) as usize);
+                            let n = consumer.pop_slice(&mut buffer[..want]);
+                            for &sample in &buffer[..n] {
+                                writer.write_sample(sample)?;
+                            }
+                            written += n as u64;
+                            if n < want || written >= total {
+                                break;
+                            }
+                        }
+                    }
+                    Ok(())
+                })();
+                armed.store(false, Ordering::Release);
+                result.and(writer.finalize().context("Failed to finalize WAV"))
+            })
+            .context("Failed to spawn capture thread")?;
+
+        tracing::info!(path = %path.display(), duration_secs, "WAV capture started");
+        Ok(ActiveCapture { path, stop, handle })
+    }
+}
+
+impl ActiveCapture {
+    /// File being written
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Whether the capture has reached its duration (or failed)
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Stop recording, flush and close the WAV file, returning its path
+    pub fn stop(self) -> Result<PathBuf> {
+        self.stop.store(true, Ordering::Release);
+        self.handle
+            .join()
+            .map_err(|_| anyhow!("Capture thread panicked"))??;
+        tracing::info!(path = %self.path.display(), "WAV capture finished");
+        Ok(self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_writes_requested_duration() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.wav");
+        let (mut tap, source) = capture_channel(1000);
+
+        // Samples pushed before arming are not part of the capture
+        tap.push(9.0);
+
+        let capture = source.start(path.clone(), 1).unwrap();
+        assert!(tap.is_armed());
+        for i in 0..1500 {
+            tap.push(i as f32 / 1500.0);
+        }
+        while !capture.is_finished() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert!(!tap.is_armed());
+        assert_eq!(capture.stop().unwrap(), path);
+
+        let mut reader = hound::WavReader::open(&path).unwrap();
+        assert_eq!(reader.spec().sample_rate, 1000);
+        assert_eq!(reader.spec().channels, 1);
+        let samples: Vec<f32> = reader.samples::<f32>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples.len(), 1000);
+        assert_eq!(samples[0], 0.0);
+    }
+
+    #[test]
+    fn test_second_start_while_active_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_tap, source) = capture_channel(1000);
+
+        let capture = source.start(dir.path().join("a.wav"), 10).unwrap();
+        assert!(source.start(dir.path().join("b.wav"), 10).is_err());
+
+        // Early stop flushes a valid (empty) file and allows a new capture
+        let path = capture.stop().unwrap();
+        assert!(hound::WavReader::open(path).is_ok());
+        let again = source.start(dir.path().join("c.wav"), 1).unwrap();
+        again.stop().unwrap();
+    }
+}
@@ -4,6 +4,20 @@
 //! follower with fast attack and slow release. This enables precise
 //! identification of when a burst arrives for timestamp-based latency calculation.
 
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Signal-to-noise ratio in dB, clamped to -60..120
+///
+/// A noise floor at or below 1e-6 counts as a very clean signal (60 dB).
+pub fn snr_db(signal: f32, noise_floor: f32) -> f32 {
+    if noise_floor > 1e-6 {
+        ((signal / noise_floor).log10() * 20.0).clamp(-60.0, 120.0)
+    } else {
+        60.0
+    }
+}
+
 /// Detection result from the burst detector
 #[derive(Debug, Clone)]
 pub struct DetectionResult {
@@ -60,6 +74,8 @@ pub struct BurstDetector {
     samples_since_detection: usize,
     /// Peak envelope during current burst
     peak_envelope: f32,
+    /// Envelope jumps straight to rising input (for single-sample impulses)
+    instant_attack: bool,
 }
 
 impl BurstDetector {
@@ -75,19 +91,32 @@ impl BurstDetector {
     /// let detector = BurstDetector::new(96000);
     /// ```
     pub fn new(sample_rate: u32) -> Self {
+        Self::with_cycle_ms(sample_rate, crate::BURST_CYCLE_MS)
+    }
+
+    /// Create a burst detector for bursts repeating every `cycle_ms`
+    ///
+    /// # Example
+    /// ```
+    /// use audiotester_core::audio::detector::BurstDetector;
+    ///
+    /// let detector = BurstDetector::with_cycle_ms(96000, 20); // 50Hz bursts
+    /// ```
+    pub fn with_cycle_ms(sample_rate: u32, cycle_ms: u32) -> Self {
         // Time constants tuned for 10ms bursts every 100ms
         // Attack: ~0.5ms for quick onset detection
-        // Release: ~10ms for smooth envelope during burst
+        // Release: ~10ms for smooth envelope during burst, shortened for
+        // faster cycles so the envelope falls back before the next burst
         let attack_time_ms = 0.5;
-        let release_time_ms = 10.0;
+        let release_time_ms = (cycle_ms as f32 / 10.0).min(10.0);
         let noise_adapt_time_ms = 100.0;
 
         let attack_coeff = Self::time_to_coeff(attack_time_ms, sample_rate);
         let release_coeff = Self::time_to_coeff(release_time_ms, sample_rate);
         let noise_adapt_coeff = Self::time_to_coeff(noise_adapt_time_ms, sample_rate);
 
-        // Minimum gap between detections (80ms - safely within 100ms cycle)
-        let min_gap_samples = (sample_rate as f64 * 0.08) as usize;
+        // Minimum gap between detections (80% of the cycle, e.g. 80ms of 100ms)
+        let min_gap_samples = (sample_rate as u64 * cycle_ms as u64 * 8 / 10_000) as usize;
 
         Self {
             sample_rate,
@@ -101,6 +130,7 @@ impl BurstDetector {
             min_gap_samples,
             samples_since_detection: min_gap_samples, // Allow immediate first detection
             peak_envelope: 0.0,
+            instant_attack: false,
         }
     }
 
@@ -125,7 +155,9 @@ impl BurstDetector {
         self.samples_since_detection += 1;
 
         // Envelope follower with fast attack, slow release
-        if abs > self.envelope {
+        if abs > self.envelope && self.instant_attack {
+            self.envelope = abs;
+        } else if abs > self.envelope {
             // Fast attack
             self.envelope = self.envelope * self.attack_coeff + abs * (1.0 - self.attack_coeff);
         } else {
@@ -150,11 +182,7 @@ impl BurstDetector {
             self.samples_since_detection = 0;
             self.peak_envelope = self.envelope;
 
-            let snr_estimate = if self.noise_floor > 1e-6 {
-                ((self.envelope / self.noise_floor).log10() * 20.0).clamp(-60.0, 120.0)
-            } else {
-                60.0 // Very clean signal
-            };
+            let snr_estimate = snr_db(self.envelope, self.noise_floor);
 
             return Some(DetectionResult {
                 onset_index: index,
@@ -240,6 +268,15 @@ impl BurstDetector {
         self.threshold_ratio = ratio.max(2.0);
     }
 
+    /// Let the envelope follow rising input without the attack smoothing
+    ///
+    /// Needed for impulse bursts: a single sample only lifts the smoothed
+    /// envelope to a few percent of its amplitude. With instant attack the
+    /// envelope peaks at the impulse and decays with the normal release.
+    pub fn set_instant_attack(&mut self, instant: bool) {
+        self.instant_attack = instant;
+    }
+
     /// Reset detector state
     pub fn reset(&mut self) {
         self.envelope = 0.0;
@@ -253,6 +290,66 @@ impl BurstDetector {
     pub fn sample_rate(&self) -> u32 {
         self.sample_rate
     }
+
+    /// SNR of the most recent burst peak over the noise floor (dB)
+    pub fn peak_snr_db(&self) -> f32 {
+        snr_db(self.peak_envelope, self.noise_floor)
+    }
+}
+
+/// Snapshot of the burst detector state for diagnostics
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DetectorDiagnostics {
+    /// Envelope level at the end of the last input callback
+    pub envelope: f32,
+    /// Estimated noise floor
+    pub noise_floor: f32,
+    /// SNR of the most recent burst peak (dB)
+    pub snr_db: f32,
+    /// Envelope level a burst must exceed to be detected
+    pub threshold: f32,
+}
+
+/// Detector state published by the input callback
+///
+/// The detector is owned by the input closure, so its state is copied into
+/// these atomics (f32 bits) once per callback for the engine thread to read.
+#[derive(Debug, Default)]
+pub struct DetectorMeter {
+    envelope: AtomicU32,
+    noise_floor: AtomicU32,
+    snr_db: AtomicU32,
+    threshold: AtomicU32,
+}
+
+impl DetectorMeter {
+    /// Create a meter reading all zeros
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Publish the detector's current state
+    pub fn update(&self, detector: &BurstDetector) {
+        self.envelope
+            .store(detector.envelope().to_bits(), Ordering::Relaxed);
+        self.noise_floor
+            .store(detector.noise_floor().to_bits(), Ordering::Relaxed);
+        self.snr_db
+            .store(detector.peak_snr_db().to_bits(), Ordering::Relaxed);
+        self.threshold
+            .store(detector.threshold().to_bits(), Ordering::Relaxed);
+    }
+
+    /// Read the last published state
+    pub fn snapshot(&self) -> DetectorDiagnostics {
+        let load = |value: &AtomicU32| f32::from_bits(value.load(Ordering::Relaxed));
+        DetectorDiagnostics {
+            envelope: load(&self.envelope),
+            noise_floor: load(&self.noise_floor),
+            snr_db: load(&self.snr_db),
+            threshold: load(&self.threshold),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -266,6 +363,33 @@ mod tests {
         assert!(!detector.is_detected());
     }
 
+    #[test]
+    fn test_snr_db() {
+        assert!((snr_db(0.5, 0.005) - 40.0).abs() < 1e-4);
+        assert!((snr_db(0.01, 0.01)).abs() < 1e-4);
+        assert!((snr_db(0.001, 0.01) + 20.0).abs() < 1e-4);
+        // Clean signal and clamping
+        assert_eq!(snr_db(0.5, 0.0), 60.0);
+        assert_eq!(snr_db(1e-9, 1.0), -60.0);
+    }
+
+    #[test]
+    fn test_meter_publishes_detector_state() {
+        let mut detector = BurstDetector::new(48000);
+        for i in 0..1000 {
+            detector.process(0.0, i);
+        }
+        detector.process(0.5, 1000);
+
+        let meter = DetectorMeter::new();
+        meter.update(&detector);
+        let diag = meter.snapshot();
+        assert_eq!(diag.envelope, detector.envelope());
+        assert_eq!(diag.noise_floor, detector.noise_floor());
+        assert_eq!(diag.threshold, detector.threshold());
+        assert_eq!(diag.snr_db, detector.peak_snr_db());
+    }
+
     #[test]
     fn test_silence_no_detection() {
         let mut detector = BurstDetector::new(48000);
@@ -454,4 +578,66 @@ mod tests {
             "Noise floor should adapt upward with noise present"
         );
     }
+
+    #[test]
+    fn test_detects_every_waveform() {
+        use crate::audio::burst::{BurstGenerator, BurstWaveform};
+
+        for waveform in [
+            BurstWaveform::Noise,
+            BurstWaveform::Chirp,
+            BurstWaveform::Impulse,
+        ] {
+            for sample_rate in [48000, 96000] {
+                let mut gen = BurstGenerator::new(sample_rate);
+                gen.set_waveform(waveform);
+                let mut detector = BurstDetector::new(sample_rate);
+                detector.set_instant_attack(waveform == BurstWaveform::Impulse);
+
+                let mut buffer = vec![0.0f32; sample_rate as usize];
+                let starts = gen.fill_buffer(&mut buffer);
+                let detections = detector.process_buffer(&buffer);
+
+                assert_eq!(
+                    detections.len(),
+                    starts.len(),
+                    "{} at {}Hz",
+                    waveform,
+                    sample_rate
+                );
+                for (detection, start) in detections.iter().zip(&starts) {
+                    assert!(
+                        detection.onset_index >= *start && detection.onset_index < start + 96,
+                        "{}: onset {} too far from burst start {}",
+                        waveform,
+                        detection.onset_index,
+                        start
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_detects_every_burst_at_50hz() {
+        use crate::audio::burst::BurstGenerator;
+
+        let mut gen = BurstGenerator::with_params(96000, 20, 5).unwrap();
+        let mut detector = BurstDetector::with_cycle_ms(96000, 20);
+        assert_eq!(detector.min_gap_samples, 1536); // 16ms at 96kHz
+
+        let mut buffer = vec![0.0f32; 96000];
+        let starts = gen.fill_buffer(&mut buffer);
+        let detections = detector.process_buffer(&buffer);
+
+        assert_eq!(detections.len(), starts.len());
+        for (detection, start) in detections.iter().zip(&starts) {
+            assert!(
+                detection.onset_index >= *start && detection.onset_index < start + 96,
+                "onset {} too far from burst start {}",
+                detection.onset_index,
+                start
+            );
+        }
+    }
 }
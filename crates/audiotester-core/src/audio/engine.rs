@@ -1,7 +1,7 @@
 //! ASIO audio engine for device management and stream handling
 //!
 //! Provides high-level interface for:
-//! - Enumerating ASIO devices
+//! - Enumerating ASIO devices (with WASAPI fallback, see [`HostPreference`])
 //! - Opening input/output streams
 //! - Managing audio callbacks
 //!
@@ -15,24 +15,73 @@
 //! input and output callbacks, providing sample-accurate timing.
 //! This eliminates the artificial delays caused by ring buffer accumulation.
 
-use crate::audio::analyzer::Analyzer;
-use crate::audio::burst::{BurstEvent, BurstGenerator, DetectionEvent};
-use crate::audio::detector::BurstDetector;
-use crate::audio::latency::{LatencyAnalyzer, LatencyResult};
+use crate::audio::analyzer::{Analyzer, FrameLossResult, DEFAULT_SILENCE_THRESHOLD_MS};
+use crate::audio::burst::{
+    BurstEvent, BurstGenerator, BurstWaveform, DetectionEvent, BURST_AMPLITUDE, MAX_BURST_AMPLITUDE,
+};
+use crate::audio::capture::{capture_channel, ActiveCapture, CaptureSource};
+use crate::audio::detector::{BurstDetector, DetectorDiagnostics, DetectorMeter};
+use crate::audio::fallback::{
+    CorrelationWindow, FallbackTrigger, LatencyMethod, DEFAULT_FALLBACK_CYCLES,
+};
+use crate::audio::latency::{samples_to_us, LatencyAnalyzer, LatencyResult, DEFAULT_ECHO_GUARD_MS};
+use crate::audio::levels::{InputLevels, LevelAccumulator, LevelMeter};
+use crate::audio::tone::{ToneControl, ToneGenerator, MAX_TONE_DURATION_MS};
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{Device, Host, Stream, StreamConfig};
+use cpal::{Device, FromSample, Host, SampleFormat, SizedSample, Stream, StreamConfig};
 use ringbuf::traits::{Consumer, Observer, Producer, Split};
 use ringbuf::HeapRb;
 use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use thiserror::Error;
 
 // Re-export crossbeam for lock-free audio callback channels
 use crossbeam_channel;
 
-/// Ring buffer size in samples (enough for ~0.5 second at 96kHz)
-const RING_BUFFER_SIZE: usize = 65536;
+/// Smallest counter ring buffer in samples (~0.7 second at 96kHz)
+const MIN_RING_BUFFER_SIZE: usize = 65536;
+
+/// Seconds of counter samples the ring buffer holds at any sample rate
+const RING_BUFFER_SECS: f64 = 0.5;
+
+/// Counter ring buffer size for a sample rate
+///
+/// At least [`RING_BUFFER_SECS`] of audio, rounded up to a power of two, so
+/// high rates (192/384kHz) do not overflow between analysis ticks.
+fn ring_buffer_size(sample_rate: u32) -> usize {
+    ((sample_rate as f64 * RING_BUFFER_SECS) as usize)
+        .next_power_of_two()
+        .max(MIN_RING_BUFFER_SIZE)
+}
+
+/// Approximate capture time of the first counter gap in an analysis cycle
+///
+/// The newest analyzed counter sample left the input callback at about
+/// `analyzed_at`; the gap is older by the samples analyzed after it plus the
+/// `queued` samples still waiting in the ring buffer. Returns None without
+/// a gap.
+pub fn loss_capture_time(
+    frame: &FrameLossResult,
+    queued: usize,
+    analyzed_at: DateTime<Utc>,
+    sample_rate: u32,
+) -> Option<DateTime<Utc>> {
+    let index = frame.first_gap_index?;
+    let frames_ago = (frame.samples_analyzed.saturating_sub(index) + queued) as i64;
+    let micros = frames_ago * 1_000_000 / sample_rate.max(1) as i64;
+    Some(analyzed_at - chrono::Duration::microseconds(micros))
+}
+
+/// Number of input channels with peak level metering (ch0 burst, ch1 counter, ...)
+pub const LEVEL_METER_CHANNELS: usize = 4;
+
+/// Smallest buffer size accepted by [`AudioEngine::set_buffer_size`] (frames)
+pub const MIN_BUFFER_SIZE: u32 = 16;
+
+/// Largest buffer size accepted by [`AudioEngine::set_buffer_size`] (frames)
+pub const MAX_BUFFER_SIZE: u32 = 8192;
 
 /// Errors that can occur during audio engine operations
 #[derive(Error, Debug)]
@@ -57,6 +106,74 @@ pub enum AudioEngineError {
 
     #[error("No output channels available")]
     NoOutputChannels,
+
+    #[error("{role} channel {channel} out of range (device has {available} {direction} channels)")]
+    ChannelOutOfRange {
+        role: &'static str,
+        channel: usize,
+        available: u16,
+        direction: &'static str,
+    },
+
+    #[error("Burst and counter must use different channels (both set to {0})")]
+    ChannelConflict(usize),
+
+    #[error(
+        "Counter channel requires at least 2 channels (device has {available} {direction} channel)"
+    )]
+    TooFewChannels {
+        available: u16,
+        direction: &'static str,
+    },
+}
+
+/// Which cpal host the engine uses for devices
+///
+/// WASAPI is a fallback for machines without ASIO drivers (e.g. smoke
+/// tests). Latency measured through WASAPI includes the Windows audio
+/// engine's mixing and buffering, so it is not comparable to ASIO figures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HostPreference {
+    /// ASIO only
+    Asio,
+    /// WASAPI only
+    Wasapi,
+    /// ASIO, falling back to WASAPI when ASIO is unavailable or has no devices
+    #[default]
+    Auto,
+}
+
+impl std::str::FromStr for HostPreference {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "asio" => Ok(HostPreference::Asio),
+            "wasapi" => Ok(HostPreference::Wasapi),
+            "auto" => Ok(HostPreference::Auto),
+            other => Err(format!(
+                "Unknown audio host: {} (expected asio, wasapi or auto)",
+                other
+            )),
+        }
+    }
+}
+
+/// How burst and detection times are put on a common clock
+///
+/// With one device, input and output share the driver's sample clock and
+/// the output frame counter is the timing reference. With separate input
+/// and output devices the clocks are independent, so both callbacks stamp
+/// events with the time elapsed since a shared start instant instead. That
+/// adds the callbacks' scheduling jitter to every measurement.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeasurementMethod {
+    /// Shared output frame counter (single device, sample accurate)
+    #[default]
+    FrameCounter,
+    /// Callback timestamps against a shared start instant (two devices)
+    Timestamp,
 }
 
 /// Audio device information
@@ -64,6 +181,8 @@ pub enum AudioEngineError {
 pub struct DeviceInfo {
     /// Device name
     pub name: String,
+    /// Host (driver API) the device belongs to, e.g. "ASIO" or "WASAPI"
+    pub host: String,
     /// Whether this is the default device
     pub is_default: bool,
     /// Supported sample rates
@@ -108,6 +227,8 @@ pub struct AnalysisResult {
     pub latency_samples: usize,
     /// Measured latency in milliseconds
     pub latency_ms: f64,
+    /// Measured latency in whole microseconds
+    pub latency_us: u64,
     /// Correlation confidence (0.0 to 1.0)
     pub confidence: f32,
     /// Number of lost samples detected
@@ -118,6 +239,15 @@ pub struct AnalysisResult {
     pub is_healthy: bool,
     /// True when ch1 counter signal is absent (muted loopback)
     pub counter_silent: bool,
+    /// True when ch1 counter is frozen on a constant value (repeating buffer)
+    pub counter_stuck: bool,
+    /// How the latency was measured
+    pub method: LatencyMethod,
+    /// Approximate time the first lost sample was captured (None without loss)
+    pub loss_timestamp: Option<DateTime<Utc>>,
+    /// No new measurement this cycle: latency repeats the last one with
+    /// time-decayed confidence
+    pub stale: bool,
 }
 
 impl From<LatencyResult> for AnalysisResult {
@@ -125,39 +255,147 @@ impl From<LatencyResult> for AnalysisResult {
         Self {
             latency_samples: lr.latency_samples,
             latency_ms: lr.latency_ms,
+            latency_us: lr.latency_us,
             confidence: lr.confidence,
             lost_samples: 0,
             corrupted_samples: 0,
             is_healthy: lr.confidence > 0.5,
             counter_silent: false,
+            counter_stuck: false,
+            method: LatencyMethod::Burst,
+            loss_timestamp: None,
+            stale: false,
+        }
+    }
+}
+
+impl AnalysisResult {
+    /// Merge counter-channel loss detection into this result
+    ///
+    /// Carries the silence and stuck flags through so a muted loopback is
+    /// reported as such rather than as plain signal loss.
+    pub fn apply_frame_loss(&mut self, frame: &FrameLossResult) {
+        self.lost_samples = frame.confirmed_lost;
+        self.counter_silent = frame.counter_silent;
+        self.counter_stuck = frame.counter_stuck;
+        if frame.confirmed_lost > 0 || frame.counter_stuck {
+            self.is_healthy = false;
         }
     }
 }
 
-/// Shared state between audio callbacks and main thread
+/// Engine side of the MLS correlation fallback
 ///
-/// Only contains items accessed from the main thread via `analyze()`.
-/// BurstGenerator and BurstDetector are moved directly into their respective
-/// closures to eliminate all Mutex contention in the audio callback hot path.
-struct SharedState {
-    /// Latency analyzer for frame-based measurement (main thread only)
-    latency_analyzer: Mutex<LatencyAnalyzer>,
-    /// Frame-based loss detector for counter channel (main thread only)
-    frame_analyzer: Mutex<Analyzer>,
-    /// Latest analysis result (main thread only)
-    last_result: Mutex<Option<AnalysisResult>>,
+/// The callbacks push every burst-channel output and input sample into
+/// two ring buffers; [`AudioEngine::analyze`] drains them into aligned
+/// correlation windows (see [`crate::audio::fallback`]).
+struct FallbackTap {
+    output: ringbuf::HeapCons<f32>,
+    input: ringbuf::HeapCons<f32>,
+    window: CorrelationWindow,
+    trigger: FallbackTrigger,
+    /// Whether the last cycle reported a fallback estimate (for logging)
+    active: bool,
+}
+
+impl FallbackTap {
+    /// Move the buffered samples into the windows
+    ///
+    /// A full ring buffer has dropped samples on one side, so the windows
+    /// are no longer aligned and start over.
+    fn drain(&mut self, scratch: &mut [f32]) {
+        let overrun = self.output.is_full() || self.input.is_full();
+        loop {
+            let n = self.output.pop_slice(scratch);
+            if n == 0 {
+                break;
+            }
+            self.window.push_output(&scratch[..n]);
+        }
+        loop {
+            let n = self.input.pop_slice(scratch);
+            if n == 0 {
+                break;
+            }
+            self.window.push_input(&scratch[..n]);
+        }
+        if overrun {
+            self.window.clear();
+        }
+    }
+}
+
+/// Analysis state owned by the engine thread while streams run
+///
+/// Only accessed from the engine's own methods (`analyze()` and the
+/// accessors). The audio callbacks reach it solely through the crossbeam
+/// channels and the counter ring buffer, and BurstGenerator/BurstDetector
+/// are moved directly into their closures, so nothing here needs a lock.
+struct AnalysisState {
+    /// Latency analyzer for frame-based measurement
+    latency_analyzer: LatencyAnalyzer,
+    /// Frame-based loss detector for counter channel
+    frame_analyzer: Analyzer,
+    /// Latest analysis result
+    last_result: Option<AnalysisResult>,
+}
+
+impl AnalysisState {
+    fn new(sample_rate: u32, silence_threshold_ms: u32, echo_guard_ms: u32) -> Self {
+        let mut frame_analyzer = Analyzer::new(&[], sample_rate);
+        frame_analyzer.set_silence_threshold_ms(silence_threshold_ms);
+        let mut latency_analyzer = LatencyAnalyzer::new(sample_rate);
+        latency_analyzer.set_echo_guard_ms(echo_guard_ms);
+        Self {
+            latency_analyzer,
+            frame_analyzer,
+            last_result: None,
+        }
+    }
 }
 
 /// ASIO audio engine for managing audio streams
 pub struct AudioEngine {
     state: EngineState,
+    /// Which host(s) devices are looked up on
+    host_preference: HostPreference,
+    /// Requested sample rate
     sample_rate: u32,
+    /// Rate the streams actually run at (None while stopped)
+    effective_sample_rate: Option<u32>,
+    /// Counter silence threshold applied to the frame analyzer (ms)
+    silence_threshold_ms: u32,
+    /// Requested ASIO buffer size in frames (None = driver default)
+    buffer_size: Option<u32>,
+    /// Channel index carrying the burst signal (output and input)
+    burst_channel: usize,
+    /// Channel index carrying the frame counter (output and input)
+    counter_channel: usize,
+    /// Burst cycle period in milliseconds
+    burst_cycle_ms: u32,
+    /// Burst duration in milliseconds
+    burst_duration_ms: u32,
+    /// Burst amplitude (0.0 to MAX_BURST_AMPLITUDE)
+    burst_amplitude: f32,
+    /// Waveform played during the burst
+    burst_waveform: BurstWaveform,
+    /// Unmatched analysis cycles before the MLS fallback (0 = disabled)
+    mls_fallback_cycles: u32,
+    /// Time after a burst match during which detections count as echoes (ms)
+    echo_guard_ms: u32,
+    /// When streams were last started (None while stopped)
+    started_at: Option<std::time::Instant>,
     device_name: Option<String>,
     host: Option<Host>,
     device: Option<Device>,
+    /// Separate input device name (None = input on the output device)
+    input_device_name: Option<String>,
+    /// Separate input device handle, resolved on select
+    input_device: Option<Device>,
     input_stream: Option<Stream>,
     output_stream: Option<Stream>,
-    shared_state: Option<Arc<SharedState>>,
+    /// Analyzers and last result (None while stopped)
+    analysis: Option<AnalysisState>,
     /// Consumer for counter samples (frame counter channel for loss detection)
     counter_consumer: Option<ringbuf::HeapCons<f32>>,
     /// Receiver for burst events from output callback (lock-free crossbeam)
@@ -179,8 +417,27 @@ pub struct AudioEngine {
     /// Set by error callbacks when ASIO sends kAsioResetRequest (cpal 0.17+).
     /// The monitoring loop checks this flag and triggers a full engine restart.
     stream_invalidated: Option<Arc<AtomicBool>>,
+    /// Driver-reported output latency (callback → playback) in nanoseconds
+    output_latency_ns: Option<Arc<AtomicU64>>,
+    /// Driver-reported input latency (capture → callback) in nanoseconds
+    input_latency_ns: Option<Arc<AtomicU64>>,
+    /// Burst detector state published by the input callback
+    detector_meter: Option<Arc<DetectorMeter>>,
+    /// Input channel levels and peak hold published by the input callback
+    level_meter: Option<Arc<LevelMeter>>,
+    /// Burst/tone output mode, read by the output callback
+    tone_control: Option<Arc<ToneControl>>,
+    /// Output channel count of the running stream (0 while stopped)
+    output_channel_count: u16,
+    /// Engine side of the input capture tap (present while streams run)
+    capture_source: Option<CaptureSource>,
+    /// WAV capture in progress or finished but not yet collected
+    capture: Option<ActiveCapture>,
     /// Pre-allocated buffer for counter sample reads
     counter_buffer: Vec<f32>,
+    /// MLS correlation fallback (None while stopped, disabled or with
+    /// separate input/output devices)
+    fallback: Option<FallbackTap>,
 }
 
 impl AudioEngine {
@@ -188,13 +445,28 @@ impl AudioEngine {
     pub fn new() -> Self {
         Self {
             state: EngineState::Stopped,
+            host_preference: HostPreference::default(),
             sample_rate: crate::DEFAULT_SAMPLE_RATE,
+            effective_sample_rate: None,
+            silence_threshold_ms: DEFAULT_SILENCE_THRESHOLD_MS,
+            buffer_size: None,
+            burst_channel: 0,
+            counter_channel: 1,
+            burst_cycle_ms: crate::BURST_CYCLE_MS,
+            burst_duration_ms: crate::BURST_DURATION_MS,
+            burst_amplitude: BURST_AMPLITUDE,
+            burst_waveform: BurstWaveform::Noise,
+            mls_fallback_cycles: DEFAULT_FALLBACK_CYCLES,
+            echo_guard_ms: DEFAULT_ECHO_GUARD_MS,
+            started_at: None,
             device_name: None,
             host: None,
             device: None,
+            input_device_name: None,
+            input_device: None,
             input_stream: None,
             output_stream: None,
-            shared_state: None,
+            analysis: None,
             counter_consumer: None,
             burst_event_rx: None,
             detection_event_rx: None,
@@ -204,20 +476,70 @@ impl AudioEngine {
             shared_frame_counter: None,
             buffer_size_frames: None,
             stream_invalidated: None,
+            output_latency_ns: None,
+            input_latency_ns: None,
+            detector_meter: None,
+            level_meter: None,
+            tone_control: None,
+            output_channel_count: 0,
+            capture_source: None,
+            capture: None,
             counter_buffer: Vec::new(),
+            fallback: None,
         }
     }
 
+    /// Use the given host preference for device lookup
+    pub fn with_host(mut self, preference: HostPreference) -> Self {
+        self.host_preference = preference;
+        self
+    }
+
+    /// Get the host preference
+    pub fn host_preference(&self) -> HostPreference {
+        self.host_preference
+    }
+
     /// Get current engine state
     pub fn state(&self) -> EngineState {
         self.state
     }
 
-    /// Get configured sample rate
+    /// Get the sample rate in use: the effective rate while running,
+    /// otherwise the requested one
     pub fn sample_rate(&self) -> u32 {
+        self.effective_sample_rate.unwrap_or(self.sample_rate)
+    }
+
+    /// Get the requested (configured) sample rate
+    pub fn requested_sample_rate(&self) -> u32 {
         self.sample_rate
     }
 
+    /// Get the rate the streams actually run at (None while stopped)
+    pub fn effective_sample_rate(&self) -> Option<u32> {
+        self.effective_sample_rate
+    }
+
+    /// Whether the device rejected the requested rate and the engine fell
+    /// back to the device default
+    pub fn rate_fallback(&self) -> bool {
+        self.effective_sample_rate
+            .is_some_and(|rate| rate != self.sample_rate)
+    }
+
+    /// Record the rate the streams were opened with
+    fn set_effective_sample_rate(&mut self, rate: u32) {
+        if rate != self.sample_rate {
+            tracing::warn!(
+                requested = self.sample_rate,
+                effective = rate,
+                "Running at a different sample rate than requested"
+            );
+        }
+        self.effective_sample_rate = Some(rate);
+    }
+
     /// Set sample rate (must be called before start)
     pub fn set_sample_rate(&mut self, rate: u32) {
         if (8000..=384000).contains(&rate) {
@@ -225,27 +547,248 @@ impl AudioEngine {
         }
     }
 
-    /// Get the ASIO host
-    fn get_asio_host() -> Result<Host> {
-        #[cfg(target_os = "windows")]
-        {
-            cpal::host_from_id(cpal::HostId::Asio)
-                .map_err(|e| anyhow!("Failed to get ASIO host: {}", e))
+    /// Get the requested buffer size in frames (None = driver default)
+    pub fn buffer_size(&self) -> Option<u32> {
+        self.buffer_size
+    }
+
+    /// Request a fixed buffer size in frames (must be called before start)
+    ///
+    /// `0` restores the driver default. If the device rejects the size at
+    /// start, the driver default is used instead.
+    pub fn set_buffer_size(&mut self, frames: u32) {
+        if frames == 0 {
+            self.buffer_size = None;
+        } else if (MIN_BUFFER_SIZE..=MAX_BUFFER_SIZE).contains(&frames) {
+            self.buffer_size = Some(frames);
+        }
+    }
+
+    /// Get the (burst, counter) channel indices
+    pub fn signal_channels(&self) -> (usize, usize) {
+        (self.burst_channel, self.counter_channel)
+    }
+
+    /// Place the burst and frame counter on the given channel indices
+    ///
+    /// Applies to both output and input (must be called before start).
+    /// Indices are checked against the device channel count at start.
+    pub fn set_signal_channels(&mut self, burst_ch: usize, counter_ch: usize) {
+        self.burst_channel = burst_ch;
+        self.counter_channel = counter_ch;
+    }
+
+    /// Get the (cycle_ms, burst_ms) burst timing
+    pub fn burst_timing(&self) -> (u32, u32) {
+        (self.burst_cycle_ms, self.burst_duration_ms)
+    }
+
+    /// Set the burst cycle period and duration in milliseconds
+    ///
+    /// The generator and detector are rebuilt with the new timing on the
+    /// next start. Fails if `burst_ms` is not shorter than `cycle_ms`.
+    pub fn set_burst_timing(&mut self, cycle_ms: u32, burst_ms: u32) -> Result<()> {
+        BurstGenerator::validate_timing(cycle_ms, burst_ms)?;
+        self.burst_cycle_ms = cycle_ms;
+        self.burst_duration_ms = burst_ms;
+        Ok(())
+    }
+
+    /// Get the burst amplitude
+    pub fn burst_amplitude(&self) -> f32 {
+        self.burst_amplitude
+    }
+
+    /// Set the burst amplitude (0.0 to 1.0), applied on the next start
+    ///
+    /// Values above [`MAX_BURST_AMPLITUDE`] are clamped to it so noise peaks
+    /// cannot clip the output.
+    pub fn set_burst_amplitude(&mut self, amp: f32) {
+        self.burst_amplitude = amp.clamp(0.0, MAX_BURST_AMPLITUDE);
+    }
+
+    /// Get the burst waveform
+    pub fn burst_waveform(&self) -> BurstWaveform {
+        self.burst_waveform
+    }
+
+    /// Set the burst waveform, applied on the next start
+    pub fn set_burst_waveform(&mut self, waveform: BurstWaveform) {
+        self.burst_waveform = waveform;
+    }
+
+    /// Check the signal channels against a device's channel counts
+    fn validate_signal_channels(
+        &self,
+        output_channels: u16,
+        input_channels: u16,
+    ) -> Result<(), AudioEngineError> {
+        if self.burst_channel == self.counter_channel {
+            return Err(AudioEngineError::ChannelConflict(self.burst_channel));
+        }
+        // Burst and counter need separate channels on both sides; a mono
+        // device would silently drop the counter and hide all loss
+        for (direction, available) in [("output", output_channels), ("input", input_channels)] {
+            if available < 2 {
+                return Err(AudioEngineError::TooFewChannels {
+                    available,
+                    direction,
+                });
+            }
+        }
+        for (role, channel) in [
+            ("Burst", self.burst_channel),
+            ("Counter", self.counter_channel),
+        ] {
+            for (direction, available) in [("output", output_channels), ("input", input_channels)] {
+                if channel >= available as usize {
+                    return Err(AudioEngineError::ChannelOutOfRange {
+                        role,
+                        channel,
+                        available,
+                        direction,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the buffer size the driver is actually delivering (frames, 0 if unknown)
+    pub fn actual_buffer_size(&self) -> u32 {
+        self.buffer_size_frames
+            .as_ref()
+            .map(|b| b.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// Get when the streams were last started (None while stopped)
+    pub fn started_at(&self) -> Option<std::time::Instant> {
+        self.started_at
+    }
+
+    /// Get the counter silence threshold (ms)
+    pub fn silence_threshold_ms(&self) -> u32 {
+        self.silence_threshold_ms
+    }
+
+    /// Set the counter silence threshold (ms)
+    ///
+    /// Applied immediately when running, and to every subsequent start.
+    pub fn set_silence_threshold_ms(&mut self, ms: u32) {
+        self.silence_threshold_ms = ms;
+        if let Some(analysis) = self.analysis.as_mut() {
+            analysis.frame_analyzer.set_silence_threshold_ms(ms);
+        }
+    }
+
+    /// Set how many consecutive analysis cycles without a burst match (while
+    /// the input has signal) switch to the MLS correlation fallback
+    ///
+    /// 0 disables the fallback. Applied immediately when running, and to
+    /// every subsequent start; enabling it from 0 takes effect on the next
+    /// start.
+    pub fn set_mls_fallback_cycles(&mut self, cycles: u32) {
+        self.mls_fallback_cycles = cycles;
+        if let Some(ref mut tap) = self.fallback {
+            tap.trigger = FallbackTrigger::new(cycles);
+        }
+    }
+
+    /// Get the MLS fallback trigger (unmatched cycles, 0 = disabled)
+    pub fn mls_fallback_cycles(&self) -> u32 {
+        self.mls_fallback_cycles
+    }
+
+    /// Set the echo guard: detections this soon after a burst match are
+    /// treated as reflections and ignored (ms, 0 disables)
+    ///
+    /// Applied immediately when running, and to every subsequent start.
+    /// Limited to half the burst cycle so the next burst is never suppressed.
+    pub fn set_echo_guard_ms(&mut self, ms: u32) {
+        self.echo_guard_ms = ms;
+        let guard_ms = self.effective_echo_guard_ms();
+        if let Some(analysis) = self.analysis.as_mut() {
+            analysis.latency_analyzer.set_echo_guard_ms(guard_ms);
         }
+    }
+
+    /// Get the configured echo guard (ms)
+    pub fn echo_guard_ms(&self) -> u32 {
+        self.echo_guard_ms
+    }
+
+    /// Echo guard limited to half the burst cycle
+    fn effective_echo_guard_ms(&self) -> u32 {
+        self.echo_guard_ms.min(self.burst_cycle_ms / 2)
+    }
 
+    /// Get the hosts to search, in order of preference
+    fn get_hosts(preference: HostPreference) -> Result<Vec<Host>> {
+        #[cfg(target_os = "windows")]
+        let ids = match preference {
+            HostPreference::Asio => vec![cpal::HostId::Asio],
+            HostPreference::Wasapi => vec![cpal::HostId::Wasapi],
+            HostPreference::Auto => vec![cpal::HostId::Asio, cpal::HostId::Wasapi],
+        };
+
+        // On non-Windows, use the default host for testing
         #[cfg(not(target_os = "windows"))]
-        {
-            // On non-Windows, return default host for testing
-            Ok(cpal::default_host())
+        let ids = {
+            let _ = preference;
+            vec![cpal::default_host().id()]
+        };
+
+        let mut hosts = Vec::new();
+        let mut last_error = None;
+        for id in ids {
+            match cpal::host_from_id(id) {
+                Ok(host) => hosts.push(host),
+                Err(e) => {
+                    tracing::debug!(host = id.name(), error = %e, "Audio host unavailable");
+                    last_error = Some(e);
+                }
+            }
+        }
+        match (hosts.is_empty(), last_error) {
+            (true, Some(e)) => Err(anyhow!("Failed to get audio host: {}", e)),
+            _ => Ok(hosts),
         }
     }
 
-    /// List available ASIO devices
+    /// List available devices using the default host preference
     ///
     /// # Returns
-    /// Vector of device information for all available ASIO devices
+    /// Vector of device information for all available devices
     pub fn list_devices() -> Result<Vec<DeviceInfo>> {
-        let host = Self::get_asio_host()?;
+        Self::list_devices_for(HostPreference::default())
+    }
+
+    /// List available devices on the hosts selected by `preference`
+    ///
+    /// With [`HostPreference::Auto`], WASAPI devices are only listed when
+    /// ASIO fails or reports no devices.
+    pub fn list_devices_for(preference: HostPreference) -> Result<Vec<DeviceInfo>> {
+        let mut last_error = None;
+        for host in Self::get_hosts(preference)? {
+            match Self::host_devices(&host) {
+                Ok(devices) if !devices.is_empty() => return Ok(devices),
+                Ok(_) => tracing::info!(host = host.id().name(), "No devices on audio host"),
+                Err(e) => {
+                    tracing::warn!(host = host.id().name(), error = %e, "Device enumeration failed");
+                    last_error = Some(e);
+                }
+            }
+        }
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// List the devices of a single host
+    fn host_devices(host: &Host) -> Result<Vec<DeviceInfo>> {
+        let host_name = host.id().name().to_string();
         let mut devices = Vec::new();
 
         let default_input = host
@@ -301,6 +844,7 @@ impl AudioEngine {
 
             devices.push(DeviceInfo {
                 name,
+                host: host_name.clone(),
                 is_default,
                 sample_rates,
                 input_channels,
@@ -311,26 +855,125 @@ impl AudioEngine {
         Ok(devices)
     }
 
+    /// Find a device by name on `host`
+    fn find_device(host: &Host, name: &str) -> Option<Device> {
+        host.devices().ok()?.find(|d| {
+            d.description()
+                .map(|desc| desc.name() == name)
+                .unwrap_or(false)
+        })
+    }
+
     /// Select an ASIO device by name
     ///
+    /// A separate input device chosen with [`AudioEngine::select_devices`]
+    /// is kept and re-opened as well, so reconnecting by name preserves a
+    /// cross-device setup.
+    ///
     /// # Arguments
     /// * `name` - Name of the ASIO device to use
     pub fn select_device(&mut self, name: &str) -> Result<()> {
-        let host = Self::get_asio_host()?;
-
-        let device = host
-            .devices()?
-            .find(|d| {
-                d.description()
-                    .map(|desc| desc.name() == name)
-                    .unwrap_or(false)
+        if let Some(input_name) = self.input_device_name.clone() {
+            return self.select_devices(name, &input_name);
+        }
+
+        let (host, device) = Self::get_hosts(self.host_preference)?
+            .into_iter()
+            .find_map(|host| {
+                let device = Self::find_device(&host, name)?;
+                Some((host, device))
             })
             .ok_or_else(|| AudioEngineError::DeviceNotFound(name.to_string()))?;
+        tracing::info!(device = %name, host = host.id().name(), "Device selected");
 
         self.host = Some(host);
         self.device = Some(device);
         self.device_name = Some(name.to_string());
 
+        // Averages from the previous device must not leak into the new path
+        self.reset_analysis();
+
+        Ok(())
+    }
+
+    /// Send on one device and receive on another
+    ///
+    /// Both devices must belong to the same host. The two clocks are not
+    /// shared, so latency is measured from callback timestamps
+    /// ([`MeasurementMethod::Timestamp`]). Passing the same name twice
+    /// returns to single-device mode.
+    ///
+    /// The ASIO SDK loads one driver per process, so two different ASIO
+    /// devices are rejected; use the WASAPI host for cross-device paths.
+    pub fn select_devices(&mut self, output_name: &str, input_name: &str) -> Result<()> {
+        if output_name == input_name {
+            self.input_device = None;
+            self.input_device_name = None;
+            return self.select_device(output_name);
+        }
+
+        let (host, output, input) = Self::get_hosts(self.host_preference)?
+            .into_iter()
+            .find_map(|host| {
+                let output = Self::find_device(&host, output_name)?;
+                let input = Self::find_device(&host, input_name)?;
+                Some((host, output, input))
+            })
+            .ok_or_else(|| {
+                AudioEngineError::DeviceNotFound(format!("{} / {}", output_name, input_name))
+            })?;
+        if host.id().name() == "ASIO" {
+            return Err(anyhow!(
+                "ASIO can only open one driver at a time; use the WASAPI host to monitor across two devices"
+            ));
+        }
+        tracing::info!(
+            output = %output_name,
+            input = %input_name,
+            host = host.id().name(),
+            "Separate input and output devices selected (timestamp latency)"
+        );
+
+        self.host = Some(host);
+        self.device = Some(output);
+        self.device_name = Some(output_name.to_string());
+        self.input_device = Some(input);
+        self.input_device_name = Some(input_name.to_string());
+
+        // Averages from the previous device must not leak into the new path
+        self.reset_analysis();
+
+        Ok(())
+    }
+
+    /// Discard accumulated latency averages and pending bursts
+    ///
+    /// Called on device change so the first measurements on the new path
+    /// are not blended with the previous device's history. No-op when
+    /// streams are not running (each start creates a fresh analyzer).
+    pub fn reset_analysis(&mut self) {
+        if let Some(analysis) = self.analysis.as_mut() {
+            analysis.latency_analyzer.reset();
+            analysis.last_result = None;
+            tracing::debug!("Latency analysis state reset");
+        }
+    }
+
+    /// Release the selected device without quitting
+    ///
+    /// Stops processing if running and drops the ASIO host and device
+    /// handles so another application can open the driver.
+    pub fn deselect_device(&mut self) -> Result<()> {
+        if self.state == EngineState::Running {
+            self.stop()?;
+        }
+        self.device = None;
+        self.host = None;
+        self.input_device = None;
+        self.input_device_name = None;
+        if let Some(name) = self.device_name.take() {
+            tracing::info!(device = %name, "Device deselected");
+        }
         Ok(())
     }
 
@@ -339,19 +982,35 @@ impl AudioEngine {
         self.device_name.as_deref()
     }
 
+    /// Get the separate input device name (None when input and output share a device)
+    pub fn input_device_name(&self) -> Option<&str> {
+        self.input_device_name.as_deref()
+    }
+
+    /// How latency is measured with the current device selection
+    pub fn measurement_method(&self) -> MeasurementMethod {
+        if self.input_device_name.is_some() {
+            MeasurementMethod::Timestamp
+        } else {
+            MeasurementMethod::FrameCounter
+        }
+    }
+
     /// Start audio processing
     ///
-    /// Opens input and output streams on the selected device and begins
+    /// Opens input and output streams on the selected device(s) and begins
     /// generating burst signals and analyzing received audio for latency.
     pub fn start(&mut self) -> Result<()> {
         let device = self
             .device
             .as_ref()
             .ok_or_else(|| anyhow!("No device selected"))?;
+        let input_device = self.input_device.as_ref().unwrap_or(device);
+        let method = self.measurement_method();
 
         // Get device's default output config
         let default_output = device.default_output_config();
-        let default_input = device.default_input_config();
+        let default_input = input_device.default_input_config();
 
         tracing::info!(
             "Device default output config: {:?}",
@@ -380,6 +1039,13 @@ impl AudioEngine {
             );
         }
 
+        // Capture in the device's native format; analysis always runs on f32
+        let input_format = default_input
+            .as_ref()
+            .map(|c| c.sample_format())
+            .unwrap_or(SampleFormat::F32);
+        tracing::info!("Input sample format: {}", input_format);
+
         // Get device channel counts
         let output_channels = default_output.as_ref().map(|c| c.channels()).unwrap_or(2);
         let input_channels = default_input.as_ref().map(|c| c.channels()).unwrap_or(2);
@@ -389,6 +1055,9 @@ impl AudioEngine {
             output_channels,
             input_channels
         );
+        self.validate_signal_channels(output_channels, input_channels)?;
+        let (burst_ch, counter_ch) = (self.burst_channel, self.counter_channel);
+        tracing::info!(burst_ch, counter_ch, "Signal channels");
 
         // Try configured rate first, fall back to device default if it fails
         let rates_to_try = if device_rate != actual_sample_rate {
@@ -397,125 +1066,214 @@ impl AudioEngine {
             vec![actual_sample_rate]
         };
 
+        // Try the requested buffer size first, fall back to the driver default
+        let buffer_sizes_to_try = match self.buffer_size {
+            Some(frames) => vec![cpal::BufferSize::Fixed(frames), cpal::BufferSize::Default],
+            None => vec![cpal::BufferSize::Default],
+        };
+
         let mut effective_rate = actual_sample_rate;
+        let mut effective_buffer_size = buffer_sizes_to_try[0];
         let mut output_config = StreamConfig {
             channels: output_channels,
             sample_rate: actual_sample_rate,
-            buffer_size: cpal::BufferSize::Default,
+            buffer_size: effective_buffer_size,
         };
         let mut input_config = StreamConfig {
             channels: input_channels,
             sample_rate: actual_sample_rate,
-            buffer_size: cpal::BufferSize::Default,
+            buffer_size: effective_buffer_size,
         };
 
-        // Test which sample rate works by trying a dummy build
-        for &rate in &rates_to_try {
-            output_config.sample_rate = rate;
-            input_config.sample_rate = rate;
-            match device.build_output_stream(
-                &output_config,
-                |_: &mut [f32], _: &cpal::OutputCallbackInfo| {},
-                |_| {},
-                None,
-            ) {
-                Ok(_stream) => {
-                    effective_rate = rate;
-                    if rate != actual_sample_rate {
-                        tracing::warn!(
-                            "Configured rate {} Hz failed, using device default {} Hz",
-                            actual_sample_rate,
-                            rate
-                        );
+        // Test which sample rate and buffer size work by trying a dummy build
+        'probe: for &rate in &rates_to_try {
+            for &buffer_size in &buffer_sizes_to_try {
+                output_config.sample_rate = rate;
+                output_config.buffer_size = buffer_size;
+                match device.build_output_stream(
+                    &output_config,
+                    |_: &mut [f32], _: &cpal::OutputCallbackInfo| {},
+                    |_| {},
+                    None,
+                ) {
+                    Ok(_stream) => {
+                        effective_rate = rate;
+                        effective_buffer_size = buffer_size;
+                        if rate != actual_sample_rate {
+                            tracing::warn!(
+                                "Configured rate {} Hz failed, using device default {} Hz",
+                                actual_sample_rate,
+                                rate
+                            );
+                        }
+                        break 'probe;
+                    }
+                    Err(e) => {
+                        if let cpal::BufferSize::Fixed(frames) = buffer_size {
+                            tracing::warn!(
+                                "Buffer size {} frames rejected at {} Hz: {}, trying driver default",
+                                frames,
+                                rate,
+                                e
+                            );
+                        } else {
+                            tracing::warn!("Sample rate {} Hz failed: {}", rate, e);
+                        }
                     }
-                    break;
-                }
-                Err(e) => {
-                    tracing::warn!("Sample rate {} Hz failed: {}", rate, e);
-                    continue;
                 }
             }
         }
 
-        // Update configs with the effective rate
+        // Update configs with the effective rate and buffer size
         output_config.sample_rate = effective_rate;
         input_config.sample_rate = effective_rate;
+        output_config.buffer_size = effective_buffer_size;
+        input_config.buffer_size = effective_buffer_size;
         tracing::info!("Effective sample rate: {} Hz", effective_rate);
+        tracing::info!("Effective buffer size: {:?}", effective_buffer_size);
 
         // Counter ring buffer: ch1 samples for loss detection only
         // NOTE: Burst samples are NOT buffered - detection happens inline in callback
-        let counter_ring = HeapRb::<f32>::new(RING_BUFFER_SIZE);
+        let ring_size = ring_buffer_size(effective_rate);
+        let counter_ring = HeapRb::<f32>::new(ring_size);
         let (mut counter_producer, counter_consumer) = counter_ring.split();
 
+        // Burst-channel output/input for the MLS fallback. Correlating the two
+        // needs a common sample clock, so it is only set up for one device.
+        let (mut fallback_out, mut fallback_in, fallback) =
+            if method == MeasurementMethod::FrameCounter && self.mls_fallback_cycles > 0 {
+                let (out_producer, out_consumer) = HeapRb::<f32>::new(ring_size).split();
+                let (in_producer, in_consumer) = HeapRb::<f32>::new(ring_size).split();
+                let tap = FallbackTap {
+                    output: out_consumer,
+                    input: in_consumer,
+                    window: CorrelationWindow::new(effective_rate),
+                    trigger: FallbackTrigger::new(self.mls_fallback_cycles),
+                    active: false,
+                };
+                (Some(out_producer), Some(in_producer), Some(tap))
+            } else {
+                (None, None, None)
+            };
+
         // Lock-free crossbeam channels for burst/detection events
         let (burst_event_tx, burst_event_rx) = crossbeam_channel::bounded::<BurstEvent>(32);
         let (detection_event_tx, detection_event_rx) =
             crossbeam_channel::bounded::<DetectionEvent>(32);
 
         // BurstGenerator and BurstDetector are moved directly into closures (no Mutex)
-        let mut burst_gen = BurstGenerator::new(effective_rate);
-        let mut burst_detector = BurstDetector::new(effective_rate);
-
-        // Main-thread-only analyzers
-        let latency_analyzer = LatencyAnalyzer::new(effective_rate);
-        let frame_analyzer = Analyzer::new(&[], effective_rate);
-
-        let shared_state = Arc::new(SharedState {
-            latency_analyzer: Mutex::new(latency_analyzer),
-            frame_analyzer: Mutex::new(frame_analyzer),
-            last_result: Mutex::new(None),
-        });
+        let mut burst_gen = BurstGenerator::with_params(
+            effective_rate,
+            self.burst_cycle_ms,
+            self.burst_duration_ms,
+        )?;
+        burst_gen.set_amplitude(self.burst_amplitude);
+        burst_gen.set_waveform(self.burst_waveform);
+        let mut burst_detector = BurstDetector::with_cycle_ms(effective_rate, self.burst_cycle_ms);
+        burst_detector.set_instant_attack(self.burst_waveform == BurstWaveform::Impulse);
+
+        // Engine-thread-only analyzers
+        let analysis = AnalysisState::new(
+            effective_rate,
+            self.silence_threshold_ms,
+            self.effective_echo_guard_ms(),
+        );
 
-        // Standalone atomics shared with callbacks via Arc (no SharedState contention)
+        // Standalone atomics shared with callbacks via Arc
         let running = Arc::new(AtomicBool::new(true));
         // Single shared frame counter: incremented by output callback, read by input callback.
         // This eliminates I/O phase offset artifacts (issue #26) because both burst generation
         // and burst detection reference the same monotonic counter.
         let shared_frame_counter = Arc::new(AtomicU64::new(0));
+        // Separate devices have no common sample clock: stamp events with the
+        // frames elapsed since this instant instead
+        let clock_epoch = (method == MeasurementMethod::Timestamp).then(std::time::Instant::now);
         // ASIO buffer size detected from first output callback
         let buffer_size_frames = Arc::new(AtomicU32::new(0));
         // Flag set by error callback when ASIO driver sends kAsioResetRequest
         let stream_invalidated = Arc::new(AtomicBool::new(false));
         let output_samples = Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let input_samples = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        // Hardware latency as reported through the callback timestamps
+        let output_latency_ns = Arc::new(AtomicU64::new(0));
+        let input_latency_ns = Arc::new(AtomicU64::new(0));
+        // Meter the first LEVEL_METER_CHANNELS inputs, extended to reach the
+        // burst and counter channels
+        let level_meter = Arc::new(LevelMeter::new(
+            (input_channels as usize)
+                .min(LEVEL_METER_CHANNELS.max(burst_ch + 1).max(counter_ch + 1)),
+            burst_ch,
+            counter_ch,
+        ));
 
         // Create output stream - BurstGenerator moved into closure (lock-free)
         let output_running = Arc::clone(&running);
         let output_counter = Arc::clone(&shared_frame_counter);
         let output_buf_size = Arc::clone(&buffer_size_frames);
         let output_sample_count = Arc::clone(&output_samples);
+        let output_latency = Arc::clone(&output_latency_ns);
+        let tone_control = Arc::new(ToneControl::new());
+        let output_tone = Arc::clone(&tone_control);
+        let mut tone_gen = ToneGenerator::new(0.0, effective_rate);
         let num_output_channels = output_channels as usize;
         let output_stream = device.build_output_stream(
             &output_config,
-            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            move |data: &mut [f32], info: &cpal::OutputCallbackInfo| {
                 if output_running.load(Ordering::Relaxed) {
+                    let ts = info.timestamp();
+                    if let Some(delay) = ts.playback.duration_since(&ts.callback) {
+                        output_latency.store(delay.as_nanos() as u64, Ordering::Relaxed);
+                    }
+
                     let start_counter = output_counter.load(Ordering::Acquire);
+                    let event_base = clock_epoch
+                        .map(|epoch| frames_since(epoch, effective_rate))
+                        .unwrap_or(start_counter);
                     let mut frame_count = 0usize;
 
-                    for (i, frame) in data.chunks_mut(num_output_channels).enumerate() {
-                        // Channel 0: Burst signal (generator owned by this closure)
-                        let (sample, is_burst_start) = burst_gen.next_sample();
-                        if !frame.is_empty() {
-                            frame[0] = sample;
-                        }
+                    // Test tone replaces the burst for the frames it has left
+                    let tone = output_tone.take((data.len() / num_output_channels) as u64);
+                    if let Some(tone) = tone {
+                        tone_gen.set_frequency(tone.freq_hz, effective_rate);
+                    }
 
-                        // Send burst event via lock-free crossbeam channel
-                        if is_burst_start {
-                            let _ = burst_event_tx.try_send(BurstEvent {
-                                start_frame: start_counter + i as u64,
-                            });
+                    for (i, frame) in data.chunks_mut(num_output_channels).enumerate() {
+                        // Unused channels carry silence
+                        frame.fill(0.0);
+
+                        match tone.filter(|t| (i as u64) < t.frames) {
+                            Some(tone) => {
+                                if let Some(out) = frame.get_mut(tone.channel) {
+                                    *out = tone_gen.next_sample();
+                                }
+                            }
+                            None => {
+                                // Burst signal (generator owned by this closure)
+                                let (sample, is_burst_start) = burst_gen.next_sample();
+                                if let Some(out) = frame.get_mut(burst_ch) {
+                                    *out = sample;
+                                }
+
+                                // Send burst event via lock-free crossbeam channel
+                                if is_burst_start {
+                                    let _ = burst_event_tx.try_send(BurstEvent {
+                                        start_frame: event_base + i as u64,
+                                    });
+                                }
+                            }
                         }
 
-                        // Channel 1: Frame counter as normalized sawtooth (0.0 to 1.0)
-                        if frame.len() > 1 {
+                        // Frame counter as normalized sawtooth (0.0 to 1.0),
+                        // kept running under a tone so loss detection continues
+                        if let Some(out) = frame.get_mut(counter_ch) {
                             let counter = (start_counter + i as u64) & 0xFFFF;
-                            frame[1] = (counter as f32) / 65536.0;
+                            *out = (counter as f32) / 65536.0;
                         }
 
-                        // Fill remaining channels with silence
-                        for ch in frame.iter_mut().skip(2) {
-                            *ch = 0.0;
+                        if let Some(producer) = fallback_out.as_mut() {
+                            let _ = producer.try_push(frame.get(burst_ch).copied().unwrap_or(0.0));
                         }
+
                         frame_count += 1;
                     }
 
@@ -526,11 +1284,11 @@ impl AudioEngine {
                         // Record ASIO buffer size from first callback
                         output_buf_size.store(frame_count as u32, Ordering::Relaxed);
                         tracing::info!(
-                            "Output callback started: {} frames ({} channels), burst mode, ch0={:.4}, ch1={:.4}",
+                            "Output callback started: {} frames ({} channels), burst mode, burst={:.4}, counter={:.4}",
                             frame_count,
                             num_output_channels,
-                            data.first().copied().unwrap_or(0.0),
-                            data.get(1).copied().unwrap_or(0.0)
+                            data.get(burst_ch).copied().unwrap_or(0.0),
+                            data.get(counter_ch).copied().unwrap_or(0.0)
                         );
                     }
                 } else {
@@ -558,70 +1316,101 @@ impl AudioEngine {
         let input_running = Arc::clone(&running);
         let input_shared_counter = Arc::clone(&shared_frame_counter);
         let input_sample_count = Arc::clone(&input_samples);
+        let input_latency = Arc::clone(&input_latency_ns);
+        let detector_meter = Arc::new(DetectorMeter::new());
+        let input_meter = Arc::clone(&detector_meter);
+        let input_level_meter = Arc::clone(&level_meter);
+        // Allocated here, reset per buffer, so the callback does not allocate
+        let mut channel_levels = vec![LevelAccumulator::default(); level_meter.channel_count()];
         let num_input_channels = input_channels as usize;
+        let (mut capture_tap, capture_source) = capture_channel(effective_rate);
 
-        let input_stream = device.build_input_stream(
-            &input_config,
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                if input_running.load(Ordering::Relaxed) {
-                    let frame_count = data.len() / num_input_channels;
-                    // Read the shared frame counter (incremented by output callback).
-                    // In ASIO's bufferSwitch, cpal processes output before input,
-                    // so the counter is current when we read it here.
-                    let current_shared_frame = input_shared_counter.load(Ordering::Acquire);
-
-                    // Inline burst detection (detector owned by this closure, no Mutex)
-                    for (i, frame) in data.chunks(num_input_channels).enumerate() {
-                        if !frame.is_empty() {
-                            let sample = frame[0];
-
-                            if burst_detector.process(sample, i).is_some() {
-                                let _ = detection_event_tx.try_send(DetectionEvent {
-                                    input_frame: current_shared_frame + i as u64,
-                                });
-                            }
+        let process_input = move |data: &[f32], info: &cpal::InputCallbackInfo| {
+            if input_running.load(Ordering::Relaxed) {
+                let ts = info.timestamp();
+                if let Some(delay) = ts.callback.duration_since(&ts.capture) {
+                    input_latency.store(delay.as_nanos() as u64, Ordering::Relaxed);
+                }
+
+                let frame_count = data.len() / num_input_channels;
+                // Read the shared frame counter (incremented by output callback).
+                // In ASIO's bufferSwitch, cpal processes output before input,
+                // so the counter is current when we read it here.
+                let current_shared_frame = clock_epoch
+                    .map(|epoch| frames_since(epoch, effective_rate))
+                    .unwrap_or_else(|| input_shared_counter.load(Ordering::Acquire));
+                channel_levels.fill(LevelAccumulator::default());
+                let capturing = capture_tap.is_armed();
+
+                // Inline burst detection (detector owned by this closure, no Mutex)
+                for (i, frame) in data.chunks(num_input_channels).enumerate() {
+                    for (level, &sample) in channel_levels.iter_mut().zip(frame) {
+                        level.push(sample);
+                    }
+
+                    if let Some(&sample) = frame.get(burst_ch) {
+                        if capturing {
+                            capture_tap.push(sample);
+                        }
+                        if let Some(producer) = fallback_in.as_mut() {
+                            let _ = producer.try_push(sample);
                         }
 
-                        // Counter ring buffer for loss detection (producer owned, no Mutex)
-                        if frame.len() > 1 {
-                            let _ = counter_producer.try_push(frame[1]);
+                        if burst_detector.process(sample, i).is_some() {
+                            let _ = detection_event_tx.try_send(DetectionEvent {
+                                input_frame: current_shared_frame + i as u64,
+                            });
                         }
                     }
 
-                    let prev = input_sample_count.fetch_add(frame_count, Ordering::Relaxed);
-                    if prev == 0 {
-                        let max_level_ch0 = data
-                            .chunks(num_input_channels)
-                            .filter_map(|f| f.first())
-                            .map(|x| x.abs())
-                            .fold(0.0f32, f32::max);
-                        let max_level_ch1 = data
-                            .chunks(num_input_channels)
-                            .filter_map(|f| f.get(1))
-                            .map(|x| x.abs())
-                            .fold(0.0f32, f32::max);
-                        tracing::info!(
-                            "Input callback started: {} frames ({} channels), ch0 max: {:.4}, ch1 max: {:.4}",
+                    // Counter ring buffer for loss detection (producer owned, no Mutex)
+                    if let Some(&counter) = frame.get(counter_ch) {
+                        let _ = counter_producer.try_push(counter);
+                    }
+                }
+
+                input_meter.update(&burst_detector);
+                input_level_meter.update(&channel_levels);
+
+                let prev = input_sample_count.fetch_add(frame_count, Ordering::Relaxed);
+                if prev == 0 {
+                    let max_level_burst = data
+                        .chunks(num_input_channels)
+                        .filter_map(|f| f.get(burst_ch))
+                        .map(|x| x.abs())
+                        .fold(0.0f32, f32::max);
+                    let max_level_counter = data
+                        .chunks(num_input_channels)
+                        .filter_map(|f| f.get(counter_ch))
+                        .map(|x| x.abs())
+                        .fold(0.0f32, f32::max);
+                    tracing::info!(
+                            "Input callback started: {} frames ({} channels), burst max: {:.4}, counter max: {:.4}",
                             frame_count,
                             num_input_channels,
-                            max_level_ch0,
-                            max_level_ch1
+                            max_level_burst,
+                            max_level_counter
                         );
-                    }
                 }
-            },
-            {
-                let invalidated = Arc::clone(&stream_invalidated);
-                move |err| {
-                    if matches!(err, cpal::StreamError::StreamInvalidated) {
-                        tracing::warn!("Input stream invalidated (ASIO driver reset)");
-                        invalidated.store(true, Ordering::Release);
-                    } else {
-                        tracing::error!("Input stream error: {}", err);
-                    }
+            }
+        };
+        let input_error = {
+            let invalidated = Arc::clone(&stream_invalidated);
+            move |err| {
+                if matches!(err, cpal::StreamError::StreamInvalidated) {
+                    tracing::warn!("Input stream invalidated (ASIO driver reset)");
+                    invalidated.store(true, Ordering::Release);
+                } else {
+                    tracing::error!("Input stream error: {}", err);
                 }
-            },
-            None,
+            }
+        };
+        let input_stream = build_native_input_stream(
+            input_device,
+            &input_config,
+            input_format,
+            process_input,
+            input_error,
         )?;
 
         // Start streams
@@ -631,8 +1420,9 @@ impl AudioEngine {
         // Store everything
         self.output_stream = Some(output_stream);
         self.input_stream = Some(input_stream);
-        self.shared_state = Some(shared_state);
+        self.analysis = Some(analysis);
         self.counter_consumer = Some(counter_consumer);
+        self.fallback = fallback;
         self.burst_event_rx = Some(burst_event_rx);
         self.detection_event_rx = Some(detection_event_rx);
         self.running = Some(running);
@@ -640,30 +1430,89 @@ impl AudioEngine {
         self.input_samples = Some(input_samples);
         self.shared_frame_counter = Some(shared_frame_counter);
         self.buffer_size_frames = Some(buffer_size_frames);
+        self.capture_source = Some(capture_source);
         self.stream_invalidated = Some(stream_invalidated);
-        self.counter_buffer = vec![0.0f32; RING_BUFFER_SIZE / 2];
+        self.output_latency_ns = Some(output_latency_ns);
+        self.input_latency_ns = Some(input_latency_ns);
+        self.tone_control = Some(tone_control);
+        self.detector_meter = Some(detector_meter);
+        self.level_meter = Some(level_meter);
+        self.output_channel_count = output_channels;
+        self.counter_buffer = vec![0.0f32; ring_size / 2];
         self.state = EngineState::Running;
-        self.sample_rate = effective_rate;
+        self.set_effective_sample_rate(effective_rate);
+        self.started_at = Some(std::time::Instant::now());
 
         tracing::info!(
-            "Audio engine started (burst mode): {} @ {}Hz, 10Hz latency updates",
+            "Audio engine started (burst mode): {} @ {}Hz, 10Hz latency updates, {} burst amplitude {:.2}",
             self.device_name.as_deref().unwrap_or("unknown"),
-            effective_rate
+            effective_rate,
+            self.burst_waveform,
+            self.burst_amplitude
         );
+        if let Some(input_name) = self.input_device_name.as_deref() {
+            tracing::info!(
+                input = %input_name,
+                method = ?method,
+                "Receiving on a separate input device"
+            );
+        }
 
         Ok(())
     }
 
     /// Stop audio processing
     pub fn stop(&mut self) -> Result<()> {
+        self.teardown_streams();
+
+        // Release ASIO host and device references so the driver can be
+        // re-acquired by a subsequent select_device() call.  ASIO is
+        // single-client: keeping the old Host alive prevents a new one
+        // from opening the same driver (VASIO-8 returns "device no
+        // longer available").
+        self.device = None;
+        self.host = None;
+        self.input_device = None;
+
+        self.state = EngineState::Stopped;
+
+        tracing::info!("Audio engine stopped");
+
+        Ok(())
+    }
+
+    /// Rebuild the input/output streams on the currently selected device
+    ///
+    /// Lighter than a full stop → select → start cycle: the ASIO host and
+    /// device handle are kept, so the driver is not re-enumerated. Suitable
+    /// for transient stream errors; a stale device handle still needs a
+    /// full re-select.
+    pub fn restart_streams(&mut self) -> Result<()> {
+        self.teardown_streams();
+        self.state = EngineState::Stopped;
+        tracing::info!("Audio streams torn down, restarting on same device");
+        self.start()
+    }
+
+    /// Drop streams and all per-run state shared with the audio callbacks
+    fn teardown_streams(&mut self) {
+        // Flush any capture before its source goes away
+        if self.capture.is_some() {
+            if let Err(e) = self.stop_capture() {
+                tracing::warn!(error = %e, "Failed to finish capture on stop");
+            }
+        }
+        self.capture_source = None;
+
         if let Some(ref running) = self.running {
             running.store(false, Ordering::Relaxed);
         }
 
         self.input_stream = None;
         self.output_stream = None;
-        self.shared_state = None;
+        self.analysis = None;
         self.counter_consumer = None;
+        self.fallback = None;
         self.burst_event_rx = None;
         self.detection_event_rx = None;
         self.running = None;
@@ -672,23 +1521,104 @@ impl AudioEngine {
         self.shared_frame_counter = None;
         self.buffer_size_frames = None;
         self.stream_invalidated = None;
+        self.output_latency_ns = None;
+        self.input_latency_ns = None;
+        self.tone_control = None;
+        self.detector_meter = None;
+        self.level_meter = None;
+        self.output_channel_count = 0;
         self.counter_buffer = Vec::new();
+        self.started_at = None;
+        self.effective_sample_rate = None;
+    }
 
-        // Release ASIO host and device references so the driver can be
-        // re-acquired by a subsequent select_device() call.  ASIO is
-        // single-client: keeping the old Host alive prevents a new one
-        // from opening the same driver (VASIO-8 returns "device no
-        // longer available").
-        self.device = None;
-        self.host = None;
-
-        self.state = EngineState::Stopped;
+    /// Start recording input channel 0 to a WAV file at `path`
+    ///
+    /// Requires running streams. Fails if a capture is still recording;
+    /// a capture that already reached its duration is collected first.
+    pub fn start_capture(&mut self, path: std::path::PathBuf, duration_secs: u32) -> Result<()> {
+        if let Some(capture) = self.capture.take() {
+            if !capture.is_finished() {
+                self.capture = Some(capture);
+                return Err(anyhow!("Capture already in progress"));
+            }
+            if let Err(e) = capture.stop() {
+                tracing::warn!(error = %e, "Previous capture failed");
+            }
+        }
+        let source = self
+            .capture_source
+            .as_ref()
+            .ok_or_else(|| anyhow!("Engine not running"))?;
+        self.capture = Some(source.start(path, duration_secs)?);
+        Ok(())
+    }
 
-        tracing::info!("Audio engine stopped");
+    /// Stop the current capture, flush and close the WAV, returning its path
+    pub fn stop_capture(&mut self) -> Result<std::path::PathBuf> {
+        self.capture
+            .take()
+            .ok_or_else(|| anyhow!("No capture in progress"))?
+            .stop()
+    }
 
+    /// Play a sine test tone on `channel` for `duration_ms`, then revert to bursts
+    ///
+    /// The tone replaces the burst signal, so no latency is measured while it
+    /// plays; the frame counter keeps running. Requires running streams.
+    pub fn play_test_tone(&mut self, freq_hz: f32, channel: usize, duration_ms: u32) -> Result<()> {
+        let tone = self
+            .tone_control
+            .as_ref()
+            .ok_or_else(|| anyhow!("Engine not running"))?;
+        let rate = self.effective_sample_rate.unwrap_or(self.sample_rate);
+        let nyquist = rate as f32 / 2.0;
+        if !(freq_hz > 0.0 && freq_hz < nyquist) {
+            return Err(anyhow!(
+                "Tone frequency must be between 0 and {nyquist}Hz, got {freq_hz}"
+            ));
+        }
+        if duration_ms == 0 || duration_ms > MAX_TONE_DURATION_MS {
+            return Err(anyhow!(
+                "Tone duration must be 1-{MAX_TONE_DURATION_MS}ms, got {duration_ms}"
+            ));
+        }
+        if channel >= self.output_channel_count as usize {
+            return Err(AudioEngineError::ChannelOutOfRange {
+                role: "Tone",
+                channel,
+                available: self.output_channel_count,
+                direction: "output",
+            }
+            .into());
+        }
+        let frames = rate as u64 * duration_ms as u64 / 1000;
+        tone.start(freq_hz, channel, frames);
+        tracing::info!(freq_hz, channel, duration_ms, "Playing test tone");
         Ok(())
     }
 
+    /// Burst detector envelope, noise floor and SNR (None while stopped)
+    pub fn detector_diagnostics(&self) -> Option<DetectorDiagnostics> {
+        self.detector_meter.as_ref().map(|m| m.snapshot())
+    }
+
+    /// Peak and RMS of the burst and counter inputs over the last callback
+    /// buffer (None while stopped)
+    pub fn signal_levels(&self) -> Option<InputLevels> {
+        self.level_meter.as_ref().map(|m| m.snapshot())
+    }
+
+    /// Whether a test tone is currently replacing the burst output
+    pub fn test_tone_active(&self) -> bool {
+        self.tone_control.as_ref().is_some_and(|t| t.is_active())
+    }
+
+    /// Whether a WAV capture is currently recording
+    pub fn capture_active(&self) -> bool {
+        self.capture.as_ref().is_some_and(|c| !c.is_finished())
+    }
+
     /// Analyze and match burst detections with bursts
     ///
     /// Call this periodically from the main thread to:
@@ -702,18 +1632,19 @@ impl AudioEngine {
     /// # Returns
     /// Analysis result if a detection was matched with a burst
     pub fn analyze(&mut self) -> Option<AnalysisResult> {
+        let analyzed_at = Utc::now();
+        let sample_rate = self.sample_rate();
         let counter_consumer = self.counter_consumer.as_mut()?;
-        let shared_state = self.shared_state.as_ref()?;
+        let analysis = self.analysis.as_mut()?;
+        let latency_analyzer = &mut analysis.latency_analyzer;
         let burst_event_rx = self.burst_event_rx.as_ref()?;
         let detection_event_rx = self.detection_event_rx.as_ref()?;
 
         // Register any pending burst events from output callback
         let mut burst_count = 0usize;
-        if let Ok(mut latency_analyzer) = shared_state.latency_analyzer.lock() {
-            while let Ok(event) = burst_event_rx.try_recv() {
-                latency_analyzer.register_burst(event);
-                burst_count += 1;
-            }
+        while let Ok(event) = burst_event_rx.try_recv() {
+            latency_analyzer.register_burst(event);
+            burst_count += 1;
         }
 
         // Process detection events from input callback using frame-based matching
@@ -721,34 +1652,34 @@ impl AudioEngine {
         let mut had_detection = false;
         let mut detection_count = 0usize;
 
-        if let Ok(mut latency_analyzer) = shared_state.latency_analyzer.lock() {
-            while let Ok(detection) = detection_event_rx.try_recv() {
-                detection_count += 1;
-                tracing::trace!(
-                    input_frame = detection.input_frame,
-                    "detection_event_received"
-                );
-                // Frame-based matching - simple arithmetic, no timestamps!
-                if let Some(latency_result) = latency_analyzer.match_detection(&detection) {
-                    result = latency_result.into();
-                    result.is_healthy = result.confidence > 0.5;
-                    had_detection = true;
-                }
+        while let Ok(detection) = detection_event_rx.try_recv() {
+            detection_count += 1;
+            tracing::trace!(
+                input_frame = detection.input_frame,
+                "detection_event_received"
+            );
+            // Frame-based matching - simple arithmetic, no timestamps!
+            if let Some(latency_result) = latency_analyzer.match_detection(&detection) {
+                result = latency_result.into();
+                result.is_healthy = result.confidence > 0.5;
+                had_detection = true;
             }
+        }
 
-            // If no new detection, use last known result with time-based confidence decay.
-            // When input goes silent (e.g. VBMatrix route muted), burst detections stop.
-            // Confidence must decay to 0 so signal_lost triggers within ~1 second.
-            if !had_detection {
-                if let Some(last) = latency_analyzer.last_result() {
-                    result.latency_samples = last.latency_samples;
-                    result.latency_ms = last.latency_ms;
-                    // Time-based decay: half-life of 0.3 seconds
-                    // ~0.5s: confidence ≈ 0.31, ~0.6s: confidence ≈ 0.25 (below 0.3 threshold)
-                    let elapsed = last.timestamp.elapsed().as_secs_f32();
-                    result.confidence = last.confidence * 0.5f32.powf(elapsed / 0.3);
-                    result.is_healthy = result.confidence > 0.3;
-                }
+        // If no new detection, use last known result with time-based confidence decay.
+        // When input goes silent (e.g. VBMatrix route muted), burst detections stop.
+        // Confidence must decay to 0 so signal_lost triggers within ~1 second.
+        if !had_detection {
+            if let Some(last) = latency_analyzer.last_result() {
+                result.latency_samples = last.latency_samples;
+                result.latency_ms = last.latency_ms;
+                result.latency_us = last.latency_us;
+                // Time-based decay: half-life of 0.3 seconds
+                // ~0.5s: confidence ≈ 0.31, ~0.6s: confidence ≈ 0.25 (below 0.3 threshold)
+                let elapsed = last.timestamp.elapsed().as_secs_f32();
+                result.confidence = last.confidence * 0.5f32.powf(elapsed / 0.3);
+                result.is_healthy = result.confidence > 0.3;
+                result.stale = true;
             }
         }
 
@@ -760,6 +1691,38 @@ impl AudioEngine {
             );
         }
 
+        // Coarse correlation estimate while burst detection fails with signal
+        // present (e.g. a compressor or limiter flattening the burst)
+        if let Some(tap) = self.fallback.as_mut() {
+            tap.drain(&mut self.counter_buffer);
+            let estimate = tap
+                .trigger
+                .update(had_detection, tap.window.input_rms())
+                .then(|| tap.window.estimate())
+                .flatten();
+            match estimate {
+                Some(estimate) => {
+                    if !tap.active {
+                        tracing::warn!(
+                            latency_ms = %format!("{:.3}", estimate.latency_ms),
+                            confidence = %format!("{:.3}", estimate.confidence),
+                            "Burst detection failing with signal present, using MLS fallback"
+                        );
+                    }
+                    result.latency_samples = estimate.latency_samples;
+                    result.latency_ms = estimate.latency_ms;
+                    result.latency_us = samples_to_us(estimate.latency_samples as f64, sample_rate);
+                    result.confidence = estimate.confidence;
+                    result.is_healthy = estimate.confidence > 0.5;
+                    result.method = LatencyMethod::MlsFallback;
+                    result.stale = false;
+                }
+                None if tap.active => tracing::info!("MLS fallback ended"),
+                None => {}
+            }
+            tap.active = estimate.is_some();
+        }
+
         // Frame-based loss detection from counter channel (pre-allocated buffer)
         let counter_available = counter_consumer.occupied_len();
         if counter_available > 0 {
@@ -767,30 +1730,25 @@ impl AudioEngine {
             let counter_read = counter_consumer.pop_slice(&mut self.counter_buffer[..read_count]);
             let counter_samples = &self.counter_buffer[..counter_read];
 
-            if let Ok(mut frame_analyzer) = shared_state.frame_analyzer.lock() {
-                let frame_result = frame_analyzer.detect_frame_loss(counter_samples);
-                result.lost_samples = frame_result.confirmed_lost;
-                result.counter_silent = frame_result.counter_silent;
-                if frame_result.confirmed_lost > 0 {
-                    result.is_healthy = false;
-                }
-            }
+            let frame_result = analysis.frame_analyzer.detect_frame_loss(counter_samples);
+            result.apply_frame_loss(&frame_result);
+            result.loss_timestamp = loss_capture_time(
+                &frame_result,
+                counter_consumer.occupied_len(),
+                analyzed_at,
+                sample_rate,
+            );
         }
 
         // Store result
-        if let Ok(mut last) = shared_state.last_result.lock() {
-            *last = Some(result.clone());
-        }
+        analysis.last_result = Some(result.clone());
 
         Some(result)
     }
 
     /// Get the last analysis result
     pub fn last_result(&self) -> Option<AnalysisResult> {
-        self.shared_state
-            .as_ref()
-            .and_then(|s| s.last_result.lock().ok())
-            .and_then(|r| r.clone())
+        self.analysis.as_ref().and_then(|a| a.last_result.clone())
     }
 
     /// Get sample counts for debugging (output, input)
@@ -808,6 +1766,35 @@ impl AudioEngine {
         (out, inp)
     }
 
+    /// Get the driver-reported hardware latency as (input_ms, output_ms)
+    ///
+    /// Derived from the cpal callback timestamps (capture → callback for
+    /// input, callback → playback for output). For ASIO, cpal computes these
+    /// from the driver buffer size, so they represent the theoretical floor
+    /// of the round trip before any routing or processing.
+    /// Returns None until both callbacks have run.
+    pub fn hardware_latency_ms(&self) -> Option<(f64, f64)> {
+        let input = self.input_latency_ns.as_ref()?.load(Ordering::Relaxed);
+        let output = self.output_latency_ns.as_ref()?.load(Ordering::Relaxed);
+        if input == 0 || output == 0 {
+            return None;
+        }
+        Some((input as f64 / 1_000_000.0, output as f64 / 1_000_000.0))
+    }
+
+    /// Take the peak input level of each metered channel since the last call
+    ///
+    /// Returns one linear peak (0.0 to 1.0) per input channel, up to
+    /// [`LEVEL_METER_CHANNELS`]. Reading resets the peak hold, so callers
+    /// polling at a fixed interval get the peak for that interval.
+    /// Returns an empty vector when the engine is not running.
+    pub fn take_input_levels(&self) -> Vec<f32> {
+        self.level_meter
+            .as_ref()
+            .map(|meter| meter.take_peak_hold(LEVEL_METER_CHANNELS))
+            .unwrap_or_default()
+    }
+
     /// Check if the ASIO driver sent a reset request (stream invalidated).
     ///
     /// Returns true if kAsioResetRequest was received, meaning the streams
@@ -823,34 +1810,106 @@ impl AudioEngine {
 
     /// Get latency measurement update rate in Hz
     pub fn update_rate(&self) -> f32 {
-        // Burst-based system runs at 10Hz (100ms cycles)
-        10.0
+        // One measurement per burst cycle (10Hz at the default 100ms)
+        1000.0 / self.burst_cycle_ms as f32
     }
 
     /// Get average latency from analyzer
     pub fn average_latency_ms(&self) -> Option<f64> {
-        self.shared_state.as_ref().and_then(|s| {
-            s.latency_analyzer
-                .lock()
-                .ok()
-                .map(|a| a.average_latency_ms())
-        })
+        self.analysis
+            .as_ref()
+            .map(|a| a.latency_analyzer.average_latency_ms())
+    }
+
+    /// Get the estimated input/output clock drift in ppm, once known
+    pub fn clock_drift_ppm(&self) -> Option<f64> {
+        self.analysis
+            .as_ref()
+            .and_then(|a| a.latency_analyzer.drift_ppm())
     }
 
     /// Get measurement count from analyzer
     pub fn measurement_count(&self) -> u64 {
-        self.shared_state
+        self.analysis
             .as_ref()
-            .and_then(|s| {
-                s.latency_analyzer
-                    .lock()
-                    .ok()
-                    .map(|a| a.measurement_count())
-            })
+            .map(|a| a.latency_analyzer.measurement_count())
             .unwrap_or(0)
     }
 }
 
+/// Frames elapsed at `sample_rate` since `epoch`
+fn frames_since(epoch: std::time::Instant, sample_rate: u32) -> u64 {
+    (epoch.elapsed().as_secs_f64() * sample_rate as f64) as u64
+}
+
+/// Convert native input samples to f32 in `[-1.0, 1.0]`
+///
+/// `dst` is cleared and refilled, so its allocation is reused across callbacks.
+pub fn convert_input_samples<T>(src: &[T], dst: &mut Vec<f32>)
+where
+    T: Copy,
+    f32: FromSample<T>,
+{
+    dst.clear();
+    dst.extend(src.iter().map(|&s| f32::from_sample_(s)));
+}
+
+/// Build an input stream in the device's native sample format
+///
+/// Some ASIO drivers only expose integer formats and reject an f32 stream.
+/// The format is matched once here so `process` always receives f32 samples
+/// without a per-buffer format check.
+fn build_native_input_stream<P, E>(
+    device: &Device,
+    config: &StreamConfig,
+    format: SampleFormat,
+    process: P,
+    error: E,
+) -> Result<Stream>
+where
+    P: FnMut(&[f32], &cpal::InputCallbackInfo) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    match format {
+        SampleFormat::F32 => Ok(device.build_input_stream(config, process, error, None)?),
+        SampleFormat::I16 => {
+            build_converting_input_stream::<i16, _, _>(device, config, process, error)
+        }
+        SampleFormat::I24 => {
+            build_converting_input_stream::<cpal::I24, _, _>(device, config, process, error)
+        }
+        SampleFormat::I32 => {
+            build_converting_input_stream::<i32, _, _>(device, config, process, error)
+        }
+        other => Err(anyhow!("Unsupported input sample format: {}", other)),
+    }
+}
+
+/// Build an integer-format input stream that converts each buffer to f32
+fn build_converting_input_stream<T, P, E>(
+    device: &Device,
+    config: &StreamConfig,
+    mut process: P,
+    error: E,
+) -> Result<Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+    P: FnMut(&[f32], &cpal::InputCallbackInfo) + Send + 'static,
+    E: FnMut(cpal::StreamError) + Send + 'static,
+{
+    let mut converted = Vec::new();
+    Ok(device.build_input_stream(
+        config,
+        move |data: &[T], info: &cpal::InputCallbackInfo| {
+            convert_input_samples(data, &mut converted);
+            process(&converted, info);
+        },
+        error,
+        None,
+    )?)
+}
+
 impl Default for AudioEngine {
     fn default() -> Self {
         Self::new()
@@ -866,13 +1925,98 @@ impl Drop for AudioEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::time::Instant;
+
+    #[test]
+    fn test_ring_buffer_scales_with_sample_rate() {
+        assert_eq!(ring_buffer_size(48000), MIN_RING_BUFFER_SIZE);
+        assert_eq!(ring_buffer_size(96000), MIN_RING_BUFFER_SIZE);
+        for rate in [192000, 384000] {
+            let size = ring_buffer_size(rate);
+            assert!(size as f64 >= rate as f64 * RING_BUFFER_SECS);
+            assert!(size.is_power_of_two());
+        }
+    }
+
+    #[test]
+    fn test_loss_capture_time_precedes_analysis() {
+        let mut analyzer = Analyzer::new(&[], 48000);
+        let analyzed_at = Utc::now();
+
+        // Gap at index 100 of 4900 analyzed samples, 2400 more still queued:
+        // the loss happened 4800 + 2400 frames (150ms) before the analysis
+        let mut samples: Vec<f32> = (0..100).map(|i| i as f32 / 65536.0).collect();
+        samples.extend((110..4910).map(|i| i as f32 / 65536.0));
+        let frame = analyzer.detect_frame_loss(&samples);
+        assert!(frame.confirmed_lost > 0);
+
+        let stamp = loss_capture_time(&frame, 2400, analyzed_at, 48000).unwrap();
+        assert_eq!(analyzed_at - stamp, chrono::Duration::milliseconds(150));
+
+        // No gap, no timestamp
+        let clean: Vec<f32> = (4910..5000).map(|i| i as f32 / 65536.0).collect();
+        let frame = analyzer.detect_frame_loss(&clean);
+        assert_eq!(loss_capture_time(&frame, 0, analyzed_at, 48000), None);
+    }
+
+    #[test]
+    fn test_muted_counter_propagates_to_analysis_result() {
+        let mut analyzer = Analyzer::new(&[], 48000);
+        let mut result = AnalysisResult::from(LatencyResult {
+            latency_samples: 240,
+            latency_ms: 5.0,
+            raw_latency_ms: 5.0,
+            confidence: 0.9,
+            ..Default::default()
+        });
+
+        // Healthy counter, then 200ms of a muted route
+        let mut samples: Vec<f32> = (0..100).map(|i| i as f32 / 65536.0).collect();
+        samples.extend(std::iter::repeat_n(0.0f32, 9600));
+        result.apply_frame_loss(&analyzer.detect_frame_loss(&samples));
+        assert!(result.counter_silent);
+        assert!(!result.counter_stuck);
+        assert_eq!(result.lost_samples, 0);
+        assert!(result.is_healthy, "Muted counter is not a confirmed loss");
+
+        // Counter comes back
+        let resumed: Vec<f32> = (30000..30100).map(|i| i as f32 / 65536.0).collect();
+        result.apply_frame_loss(&analyzer.detect_frame_loss(&resumed));
+        assert!(!result.counter_silent);
+    }
+
+    #[test]
+    fn test_convert_i16_full_scale() {
+        let mut out = Vec::new();
+        convert_input_samples(&[i16::MIN, 0i16, i16::MAX], &mut out);
+        assert_eq!(out[0], -1.0);
+        assert_eq!(out[1], 0.0);
+        assert!((out[2] - 1.0).abs() < 1e-4);
+
+        // Buffer is refilled, not appended to
+        convert_input_samples(&[16384i16], &mut out);
+        assert_eq!(out, vec![0.5]);
+    }
+
+    #[test]
+    fn test_convert_i32_and_i24_full_scale() {
+        let mut out = Vec::new();
+        convert_input_samples(&[i32::MIN, i32::MAX], &mut out);
+        assert_eq!(out[0], -1.0);
+        assert!((out[1] - 1.0).abs() < 1e-6);
+
+        let min24 = cpal::I24::new(-(1 << 23)).unwrap();
+        let max24 = cpal::I24::new((1 << 23) - 1).unwrap();
+        convert_input_samples(&[min24, max24], &mut out);
+        assert_eq!(out[0], -1.0);
+        assert!((out[1] - 1.0).abs() < 1e-6);
+    }
 
     #[test]
     fn test_engine_creation() {
         let engine = AudioEngine::new();
         assert_eq!(engine.state(), EngineState::Stopped);
         assert_eq!(engine.sample_rate(), crate::DEFAULT_SAMPLE_RATE);
+        assert!(engine.started_at().is_none());
     }
 
     #[test]
@@ -881,6 +2025,59 @@ mod tests {
         assert_eq!(engine.state(), EngineState::Stopped);
     }
 
+    #[test]
+    fn test_sample_rate_fallback_reported() {
+        let mut engine = AudioEngine::new();
+        engine.set_sample_rate(96000);
+        assert_eq!(engine.effective_sample_rate(), None);
+        assert!(!engine.rate_fallback());
+
+        // Simulate the device only accepting its 48kHz default
+        engine.set_effective_sample_rate(48000);
+        assert_eq!(engine.requested_sample_rate(), 96000);
+        assert_eq!(engine.sample_rate(), 48000);
+        assert!(engine.rate_fallback());
+
+        engine.teardown_streams();
+        assert_eq!(engine.sample_rate(), 96000);
+        assert!(!engine.rate_fallback());
+    }
+
+    #[test]
+    fn test_burst_timing() {
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.burst_timing(), (100, 10));
+
+        engine.set_burst_timing(20, 5).unwrap();
+        assert_eq!(engine.burst_timing(), (20, 5));
+        assert!((engine.update_rate() - 50.0).abs() < 0.01);
+
+        // Invalid timing leaves the previous values in place
+        assert!(engine.set_burst_timing(20, 20).is_err());
+        assert_eq!(engine.burst_timing(), (20, 5));
+    }
+
+    #[test]
+    fn test_burst_amplitude_clamped() {
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.burst_amplitude(), BURST_AMPLITUDE);
+
+        engine.set_burst_amplitude(0.3);
+        assert_eq!(engine.burst_amplitude(), 0.3);
+        engine.set_burst_amplitude(1.0);
+        assert_eq!(engine.burst_amplitude(), MAX_BURST_AMPLITUDE);
+        engine.set_burst_amplitude(-0.5);
+        assert_eq!(engine.burst_amplitude(), 0.0);
+    }
+
+    #[test]
+    fn test_burst_waveform() {
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.burst_waveform(), BurstWaveform::Noise);
+        engine.set_burst_waveform(BurstWaveform::Chirp);
+        assert_eq!(engine.burst_waveform(), BurstWaveform::Chirp);
+    }
+
     #[test]
     fn test_update_rate() {
         let engine = AudioEngine::new();
@@ -907,13 +2104,253 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_host_preference() {
+        assert_eq!(AudioEngine::new().host_preference(), HostPreference::Auto);
+        let engine = AudioEngine::new().with_host(HostPreference::Wasapi);
+        assert_eq!(engine.host_preference(), HostPreference::Wasapi);
+
+        assert_eq!("ASIO".parse::<HostPreference>(), Ok(HostPreference::Asio));
+        assert_eq!(
+            " wasapi ".parse::<HostPreference>(),
+            Ok(HostPreference::Wasapi)
+        );
+        assert!("coreaudio".parse::<HostPreference>().is_err());
+    }
+
+    #[test]
+    fn test_select_devices_unknown_keeps_frame_counter() {
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.measurement_method(), MeasurementMethod::FrameCounter);
+
+        assert!(engine
+            .select_devices("Missing Output 12345", "Missing Input 12345")
+            .is_err());
+        assert_eq!(engine.input_device_name(), None);
+        assert_eq!(engine.measurement_method(), MeasurementMethod::FrameCounter);
+    }
+
+    #[test]
+    fn test_deselect_device_clears_selection() {
+        let mut engine = AudioEngine::new();
+        engine.device_name = Some("Test ASIO".to_string());
+        engine.input_device_name = Some("Test Input".to_string());
+        engine.deselect_device().unwrap();
+        assert_eq!(engine.device_name(), None);
+        assert_eq!(engine.input_device_name(), None);
+        assert_eq!(engine.measurement_method(), MeasurementMethod::FrameCounter);
+        assert_eq!(engine.state(), EngineState::Stopped);
+    }
+
+    #[test]
+    fn test_restart_streams_requires_device() {
+        let mut engine = AudioEngine::new();
+        assert!(engine.restart_streams().is_err());
+        assert_eq!(engine.state(), EngineState::Stopped);
+    }
+
+    #[test]
+    fn test_set_buffer_size() {
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.buffer_size(), None);
+
+        engine.set_buffer_size(128);
+        assert_eq!(engine.buffer_size(), Some(128));
+
+        // Out-of-range values are ignored
+        engine.set_buffer_size(8);
+        assert_eq!(engine.buffer_size(), Some(128));
+        engine.set_buffer_size(MAX_BUFFER_SIZE + 1);
+        assert_eq!(engine.buffer_size(), Some(128));
+
+        // Zero restores the driver default
+        engine.set_buffer_size(0);
+        assert_eq!(engine.buffer_size(), None);
+        assert_eq!(engine.actual_buffer_size(), 0);
+    }
+
+    #[test]
+    fn test_capture_requires_running_streams() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut engine = AudioEngine::new();
+        assert!(engine
+            .start_capture(dir.path().join("capture.wav"), 1)
+            .is_err());
+        assert!(!engine.capture_active());
+        assert!(engine.stop_capture().is_err());
+    }
+
+    #[test]
+    fn test_signal_channel_validation() {
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.signal_channels(), (0, 1));
+        assert!(engine.validate_signal_channels(2, 2).is_ok());
+
+        engine.set_signal_channels(2, 3);
+        assert!(engine.validate_signal_channels(8, 8).is_ok());
+        let err = engine.validate_signal_channels(8, 2).unwrap_err();
+        assert!(matches!(
+            err,
+            AudioEngineError::ChannelOutOfRange {
+                role: "Burst",
+                channel: 2,
+                available: 2,
+                direction: "input",
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Burst channel 2 out of range (device has 2 input channels)"
+        );
+
+        engine.set_signal_channels(3, 3);
+        assert!(matches!(
+            engine.validate_signal_channels(8, 8),
+            Err(AudioEngineError::ChannelConflict(3))
+        ));
+    }
+
+    #[test]
+    fn test_mono_device_rejected() {
+        let engine = AudioEngine::new();
+        let err = engine.validate_signal_channels(2, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            AudioEngineError::TooFewChannels {
+                available: 1,
+                direction: "input",
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Counter channel requires at least 2 channels (device has 1 input channel)"
+        );
+        assert!(matches!(
+            engine.validate_signal_channels(1, 2),
+            Err(AudioEngineError::TooFewChannels {
+                direction: "output",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_analyze_matches_standalone_analyzers() {
+        let mut engine = AudioEngine::new();
+        let rate = engine.sample_rate();
+        let (mut counter_tx, counter_rx) = HeapRb::<f32>::new(4096).split();
+        let (burst_tx, burst_rx) = crossbeam_channel::bounded(32);
+        let (detection_tx, detection_rx) = crossbeam_channel::bounded(32);
+        engine.analysis = Some(AnalysisState::new(
+            rate,
+            engine.silence_threshold_ms(),
+            DEFAULT_ECHO_GUARD_MS,
+        ));
+        engine.counter_consumer = Some(counter_rx);
+        engine.burst_event_rx = Some(burst_rx);
+        engine.detection_event_rx = Some(detection_rx);
+        engine.counter_buffer = vec![0.0; 4096];
+
+        // The same burst, detection and counter gap fed to standalone analyzers
+        let burst = BurstEvent { start_frame: 48000 };
+        let detection = DetectionEvent { input_frame: 48480 };
+        let counter: Vec<f32> = (0..100)
+            .chain(105..200)
+            .map(|i| i as f32 / 65536.0)
+            .collect();
+        let mut latency = LatencyAnalyzer::new(rate);
+        latency.register_burst(burst.clone());
+        let expected = latency.match_detection(&detection).unwrap();
+        let expected_loss = Analyzer::new(&[], rate).detect_frame_loss(&counter);
+
+        burst_tx.send(burst).unwrap();
+        detection_tx.send(detection).unwrap();
+        counter_tx.push_slice(&counter);
+        let result = engine.analyze().unwrap();
+
+        assert_eq!(result.latency_samples, expected.latency_samples);
+        assert_eq!(result.latency_ms, expected.latency_ms);
+        assert_eq!(result.confidence, expected.confidence);
+        assert_eq!(result.lost_samples, expected_loss.confirmed_lost);
+        assert!(result.loss_timestamp.is_some());
+        assert_eq!(engine.measurement_count(), 1);
+        assert_eq!(
+            engine.last_result().map(|r| r.latency_samples),
+            Some(expected.latency_samples)
+        );
+
+        engine.reset_analysis();
+        assert_eq!(engine.measurement_count(), 0);
+        assert!(engine.last_result().is_none());
+    }
+
+    #[test]
+    fn test_reset_analysis_clears_previous_device_history() {
+        let mut engine = AudioEngine::new();
+        let rate = engine.sample_rate();
+        let mut analysis =
+            AnalysisState::new(rate, engine.silence_threshold_ms(), DEFAULT_ECHO_GUARD_MS);
+
+        // 12 s of bursts on the old path at ~10 ms, creeping up by one sample
+        // every second: enough for a running average and a drift estimate
+        let cycle = rate as u64 / 10;
+        for i in 0..120u64 {
+            let start_frame = rate as u64 + i * cycle;
+            analysis
+                .latency_analyzer
+                .register_burst(BurstEvent { start_frame });
+            let detection = DetectionEvent {
+                input_frame: start_frame + rate as u64 / 100 + i / 10,
+            };
+            assert!(analysis
+                .latency_analyzer
+                .match_detection(&detection)
+                .is_some());
+        }
+        analysis.latency_analyzer.register_burst(BurstEvent {
+            start_frame: rate as u64 * 20,
+        });
+        engine.analysis = Some(analysis);
+
+        assert_eq!(engine.measurement_count(), 120);
+        assert!(engine.average_latency_ms().unwrap() > 9.0);
+        assert!(engine.clock_drift_ppm().is_some());
+
+        // What select_device() does once the new device is resolved
+        engine.reset_analysis();
+
+        assert_eq!(engine.measurement_count(), 0);
+        assert_eq!(engine.average_latency_ms(), Some(0.0));
+        assert!(engine.clock_drift_ppm().is_none());
+        let analyzer = &engine.analysis.as_ref().unwrap().latency_analyzer;
+        assert_eq!(analyzer.pending_burst_count(), 0);
+        assert!(analyzer.last_result().is_none());
+    }
+
+    #[test]
+    fn test_reset_analysis_when_stopped() {
+        let mut engine = AudioEngine::new();
+        engine.reset_analysis();
+        assert_eq!(engine.measurement_count(), 0);
+        assert!(engine.average_latency_ms().is_none());
+    }
+
+    #[test]
+    fn test_input_levels_empty_when_stopped() {
+        let engine = AudioEngine::new();
+        assert!(engine.take_input_levels().is_empty());
+        assert!(engine.signal_levels().is_none());
+        assert!(engine.hardware_latency_ms().is_none());
+    }
+
     #[test]
     fn test_analysis_result_from_latency() {
         let lr = LatencyResult {
             latency_ms: 5.0,
+            raw_latency_ms: 5.0,
             latency_samples: 480,
             confidence: 0.8,
-            timestamp: Instant::now(),
+            ..Default::default()
         };
 
         let ar: AnalysisResult = lr.into();
@@ -0,0 +1,282 @@
+//! MLS correlation fallback for paths that defeat burst detection
+//!
+//! A compressor or limiter in the loopback path can flatten the burst
+//! envelope until the [`BurstDetector`](super::detector::BurstDetector)
+//! never fires, although audio clearly arrives. When the burst analyzer
+//! produces no match for a configurable number of consecutive analysis
+//! cycles while the input carries signal, the engine switches to the legacy
+//! MLS cross-correlation of [`Analyzer`] over the recent output and input of
+//! the burst channel. The white-noise burst correlates like an MLS, so this
+//! yields a coarse latency estimate that does not depend on the envelope.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+
+use crate::audio::analyzer::Analyzer;
+
+/// Default number of consecutive unmatched analysis cycles before falling back
+pub const DEFAULT_FALLBACK_CYCLES: u32 = 20;
+
+/// Input RMS (linear) above which the burst channel counts as carrying
+/// signal (-40 dBFS); below it a missing match is plain signal loss
+pub const FALLBACK_MIN_RMS: f32 = 0.01;
+
+/// Length of the output/input windows correlated by the fallback (ms)
+///
+/// Two default burst cycles plus the 100ms correlation search range, so at
+/// least one whole burst is always inside the window.
+pub const FALLBACK_WINDOW_MS: u32 = 300;
+
+/// How a latency measurement was obtained
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyMethod {
+    /// Burst detection matched against the output frame counter
+    #[default]
+    Burst,
+    /// Coarse MLS cross-correlation while burst detection fails
+    MlsFallback,
+}
+
+impl std::fmt::Display for LatencyMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            LatencyMethod::Burst => "burst",
+            LatencyMethod::MlsFallback => "mls_fallback",
+        })
+    }
+}
+
+/// Decides when to fall back to correlation
+///
+/// Counts consecutive analysis cycles without a burst match while the input
+/// has signal. A match or a silent input resets the count.
+#[derive(Debug, Clone)]
+pub struct FallbackTrigger {
+    cycles: u32,
+    misses: u32,
+}
+
+impl FallbackTrigger {
+    /// Fall back after `cycles` unmatched cycles (0 disables the fallback)
+    pub fn new(cycles: u32) -> Self {
+        Self { cycles, misses: 0 }
+    }
+
+    /// Record one analysis cycle, returning whether the fallback is active
+    pub fn update(&mut self, matched: bool, input_rms: f32) -> bool {
+        if matched || input_rms < FALLBACK_MIN_RMS {
+            self.misses = 0;
+        } else {
+            self.misses = self.misses.saturating_add(1);
+        }
+        self.is_active()
+    }
+
+    /// Whether enough unmatched cycles with signal have passed
+    pub fn is_active(&self) -> bool {
+        self.cycles > 0 && self.misses >= self.cycles
+    }
+}
+
+impl Default for FallbackTrigger {
+    fn default() -> Self {
+        Self::new(DEFAULT_FALLBACK_CYCLES)
+    }
+}
+
+/// Coarse latency estimate from correlation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FallbackEstimate {
+    /// Lag of the correlation peak in samples
+    pub latency_samples: usize,
+    /// Lag of the correlation peak in milliseconds
+    pub latency_ms: f64,
+    /// Normalized correlation at the peak (0.0 to 1.0)
+    pub confidence: f32,
+}
+
+/// Recent burst-channel output and input, aligned by sample index
+///
+/// Both sides count the samples pushed since the last clear, so a window
+/// that is ahead (the output callback runs before the input one) is trimmed
+/// back to the frames both cover before correlating.
+#[derive(Debug)]
+pub struct CorrelationWindow {
+    sample_rate: u32,
+    capacity: usize,
+    output: VecDeque<f32>,
+    input: VecDeque<f32>,
+    output_total: u64,
+    input_total: u64,
+}
+
+impl CorrelationWindow {
+    /// Create empty windows of [`FALLBACK_WINDOW_MS`]
+    pub fn new(sample_rate: u32) -> Self {
+        let capacity = (sample_rate as u64 * FALLBACK_WINDOW_MS as u64 / 1000) as usize;
+        Self {
+            sample_rate,
+            capacity: capacity.max(1),
+            output: VecDeque::with_capacity(capacity),
+            input: VecDeque::with_capacity(capacity),
+            output_total: 0,
+            input_total: 0,
+        }
+    }
+
+    /// Append output samples, dropping the oldest beyond the window length
+    pub fn push_output(&mut self, samples: &[f32]) {
+        Self::push(&mut self.output, samples, self.capacity);
+        self.output_total += samples.len() as u64;
+    }
+
+    /// Append input samples, dropping the oldest beyond the window length
+    pub fn push_input(&mut self, samples: &[f32]) {
+        Self::push(&mut self.input, samples, self.capacity);
+        self.input_total += samples.len() as u64;
+    }
+
+    fn push(window: &mut VecDeque<f32>, samples: &[f32], capacity: usize) {
+        let skip = samples.len().saturating_sub(capacity);
+        window.extend(&samples[skip..]);
+        let excess = window.len().saturating_sub(capacity);
+        window.drain(..excess);
+    }
+
+    /// Discard both windows (e.g. after a ring buffer overrun broke alignment)
+    pub fn clear(&mut self) {
+        self.output.clear();
+        self.input.clear();
+        self.output_total = 0;
+        self.input_total = 0;
+    }
+
+    /// RMS of the input window (0 while empty)
+    pub fn input_rms(&self) -> f32 {
+        if self.input.is_empty() {
+            return 0.0;
+        }
+        let sum: f64 = self.input.iter().map(|&x| x as f64 * x as f64).sum();
+        (sum / self.input.len() as f64).sqrt() as f32
+    }
+
+    /// Estimate latency by MLS cross-correlation of the two windows
+    ///
+    /// Returns None until both windows are full, when one side is more than
+    /// half a window ahead, or when either window is silent.
+    pub fn estimate(&self) -> Option<FallbackEstimate> {
+        if self.output.len() < self.capacity || self.input.len() < self.capacity {
+            return None;
+        }
+        let output_ahead = self.output_total.saturating_sub(self.input_total) as usize;
+        let input_ahead = self.input_total.saturating_sub(self.output_total) as usize;
+        let len = self
+            .capacity
+            .checked_sub(output_ahead + input_ahead)
+            .filter(|&len| len >= self.capacity / 2)?;
+        let output: Vec<f32> = self
+            .output
+            .iter()
+            .skip(input_ahead)
+            .take(len)
+            .copied()
+            .collect();
+        let input: Vec<f32> = self
+            .input
+            .iter()
+            .skip(output_ahead)
+            .take(len)
+            .copied()
+            .collect();
+        let output_energy: f64 = output.iter().map(|&x| x as f64 * x as f64).sum();
+        let input_energy: f64 = input.iter().map(|&x| x as f64 * x as f64).sum();
+        if output_energy < 1e-10 || input_energy < 1e-10 {
+            return None;
+        }
+
+        let lag = Analyzer::new(&output, self.sample_rate)
+            .analyze(&input)
+            .latency_samples;
+
+        // The legacy analyzer's confidence is not normalized for arbitrary
+        // references; use the normalized correlation at the peak instead
+        let peak: f64 = output
+            .iter()
+            .zip(input.iter().skip(lag))
+            .map(|(&o, &i)| o as f64 * i as f64)
+            .sum();
+        let confidence = (peak / (output_energy * input_energy).sqrt()).clamp(0.0, 1.0) as f32;
+
+        Some(FallbackEstimate {
+            latency_samples: lag,
+            latency_ms: lag as f64 * 1000.0 / self.sample_rate as f64,
+            confidence,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::burst::BurstGenerator;
+
+    #[test]
+    fn test_fallback_trigger() {
+        let mut trigger = FallbackTrigger::new(3);
+        assert!(!trigger.update(false, 0.2));
+        assert!(!trigger.update(false, 0.2));
+        assert!(trigger.update(false, 0.2));
+        assert!(trigger.update(false, 0.2));
+
+        // A burst match ends the fallback
+        assert!(!trigger.update(true, 0.2));
+
+        // Silence is signal loss, not a filtered burst
+        trigger.update(false, 0.2);
+        trigger.update(false, 0.2);
+        assert!(!trigger.update(false, 0.0));
+        assert!(!trigger.update(false, 0.2));
+
+        let mut disabled = FallbackTrigger::new(0);
+        for _ in 0..100 {
+            assert!(!disabled.update(false, 0.2));
+        }
+    }
+
+    #[test]
+    fn test_correlation_estimate() {
+        // 48kHz burst signal looped back with a 5ms (240 frame) delay and
+        // a hard clip standing in for a limiter
+        let delay = 240;
+        let mut gen = BurstGenerator::new(48000);
+        let output: Vec<f32> = (0..48000).map(|_| gen.next_sample().0).collect();
+        let input: Vec<f32> = std::iter::repeat_n(0.0, delay)
+            .chain(output.iter().map(|&x| (x * 4.0).clamp(-0.3, 0.3)))
+            .take(output.len())
+            .collect();
+
+        let mut window = CorrelationWindow::new(48000);
+        assert_eq!(window.estimate(), None);
+        for (out, inp) in output.chunks(256).zip(input.chunks(256)) {
+            window.push_output(out);
+            window.push_input(inp);
+        }
+
+        assert!(window.input_rms() > FALLBACK_MIN_RMS);
+        let estimate = window.estimate().unwrap();
+        assert_eq!(estimate.latency_samples, delay);
+        assert!((estimate.latency_ms - 5.0).abs() < 1e-9);
+        assert!(
+            estimate.confidence > 0.5,
+            "confidence {}",
+            estimate.confidence
+        );
+
+        // An output buffer drained ahead of its input does not shift the lag
+        let mut ahead = CorrelationWindow::new(48000);
+        ahead.push_output(&output);
+        ahead.push_input(&input[..input.len() - 256]);
+        assert_eq!(ahead.estimate().unwrap().latency_samples, delay);
+    }
+}
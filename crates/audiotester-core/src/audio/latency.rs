@@ -16,16 +16,52 @@ use super::detector::BurstDetector;
 /// Maximum number of pending bursts to track
 const MAX_PENDING_BURSTS: usize = 16;
 
-/// Maximum latency in frames before discarding a burst (500ms at 96kHz)
-const MAX_LATENCY_FRAMES: u64 = 48000; // 500ms at 96kHz
+/// Longest latency a burst can be matched at before it is discarded (ms)
+pub const MAX_LATENCY_MS: u64 = 500;
+
+/// Number of recent raw measurements used for outlier rejection
+const OUTLIER_WINDOW: usize = 5;
+
+/// Measurements further than this many MADs from the median are outliers
+const OUTLIER_MAD_FACTOR: f64 = 3.0;
+
+/// Lower bound for the MAD (in samples) so a perfectly stable history
+/// does not turn one sample of jitter into an outlier
+const MIN_MAD_SAMPLES: f64 = 1.0;
+
+/// Confidence reported for rejected outliers: below "healthy" (0.5) but
+/// above the signal-loss threshold, since the signal is still present
+const OUTLIER_CONFIDENCE: f32 = 0.4;
+
+/// Number of recent measurements used for the clock drift estimate
+/// (one minute at the default 10Hz update rate)
+const DRIFT_WINDOW: usize = 600;
+
+/// Shortest span of output clock time (seconds) before drift is estimated;
+/// over shorter spans one sample of jitter reads as tens of ppm
+const MIN_DRIFT_SPAN_SECS: f64 = 10.0;
+
+/// Drift magnitude above which a warning is logged (ppm)
+pub const DRIFT_WARNING_PPM: f64 = 50.0;
+
+/// Default time after a match during which further detections are
+/// treated as echoes (ms)
+///
+/// On acoustic loopbacks a reflection arrives a few ms after the direct
+/// sound and would otherwise produce a second, longer latency.
+pub const DEFAULT_ECHO_GUARD_MS: u32 = 20;
 
 /// Latency measurement result
 #[derive(Debug, Clone)]
 pub struct LatencyResult {
-    /// Measured latency in milliseconds
+    /// Measured latency in milliseconds (median of recent values for outliers)
     pub latency_ms: f64,
+    /// Unfiltered latency of this burst match in milliseconds
+    pub raw_latency_ms: f64,
     /// Measured latency in samples
     pub latency_samples: usize,
+    /// Measured latency in whole microseconds (same value as `latency_ms`)
+    pub latency_us: u64,
     /// Confidence of the measurement (0.0 to 1.0)
     pub confidence: f32,
     /// Timestamp of when this measurement was taken
@@ -36,13 +72,34 @@ impl Default for LatencyResult {
     fn default() -> Self {
         Self {
             latency_ms: 0.0,
+            raw_latency_ms: 0.0,
             latency_samples: 0,
+            latency_us: 0,
             confidence: 0.0,
             timestamp: Instant::now(),
         }
     }
 }
 
+/// Convert a latency in samples to whole microseconds
+///
+/// Sub-millisecond loopbacks need more resolution than the dashboard's
+/// two-decimal `latency_ms`.
+///
+/// # Example
+/// ```
+/// use audiotester_core::audio::latency::samples_to_us;
+///
+/// assert_eq!(samples_to_us(48.0, 48000), 1000);
+/// assert_eq!(samples_to_us(50.0, 192000), 260);
+/// ```
+pub fn samples_to_us(samples: f64, sample_rate: u32) -> u64 {
+    if sample_rate == 0 {
+        return 0;
+    }
+    (samples * 1_000_000.0 / sample_rate as f64).round() as u64
+}
+
 /// Frame-based latency analyzer
 ///
 /// Tracks burst generation events and matches them with detected bursts
@@ -86,6 +143,8 @@ pub struct LatencyAnalyzer {
     detector: BurstDetector,
     /// Queue of pending (unmatched) burst events
     pending_bursts: VecDeque<BurstEvent>,
+    /// Matching window: [`MAX_LATENCY_MS`] in frames at this sample rate
+    max_latency_frames: u64,
     /// Most recent latency measurement
     last_result: Option<LatencyResult>,
     /// Running average of latency for smoothing
@@ -94,6 +153,18 @@ pub struct LatencyAnalyzer {
     average_alpha: f64,
     /// Number of measurements taken
     measurement_count: u64,
+    /// Whether median/MAD outlier rejection is applied
+    outlier_filter: bool,
+    /// Last raw measurements in samples (newest at the back)
+    recent_samples: VecDeque<usize>,
+    /// (burst start frame, latency in samples) of recent accepted measurements
+    drift_points: VecDeque<(u64, usize)>,
+    /// Whether the drift is currently above [`DRIFT_WARNING_PPM`]
+    drift_warned: bool,
+    /// Frames after a match during which detections are suppressed as echoes
+    echo_guard_frames: u64,
+    /// Input frame of the last matched detection
+    last_match_frame: Option<u64>,
 }
 
 impl LatencyAnalyzer {
@@ -106,13 +177,34 @@ impl LatencyAnalyzer {
             sample_rate,
             detector: BurstDetector::new(sample_rate),
             pending_bursts: VecDeque::with_capacity(MAX_PENDING_BURSTS),
+            max_latency_frames: sample_rate as u64 * MAX_LATENCY_MS / 1000,
             last_result: None,
             latency_average: 0.0,
             average_alpha: 0.3, // Faster adaptation
             measurement_count: 0,
+            outlier_filter: true,
+            recent_samples: VecDeque::with_capacity(OUTLIER_WINDOW),
+            drift_points: VecDeque::with_capacity(DRIFT_WINDOW),
+            drift_warned: false,
+            echo_guard_frames: sample_rate as u64 * DEFAULT_ECHO_GUARD_MS as u64 / 1000,
+            last_match_frame: None,
         }
     }
 
+    /// Set the echo guard time after a match (ms, 0 disables)
+    ///
+    /// Detections within this time of the last matched one are reflections
+    /// of the same burst and are ignored. Keep it below the burst cycle.
+    pub fn set_echo_guard_ms(&mut self, ms: u32) {
+        self.echo_guard_frames = self.sample_rate as u64 * ms as u64 / 1000;
+    }
+
+    /// Enable or disable median/MAD outlier rejection (enabled by default)
+    pub fn set_outlier_filter(&mut self, enabled: bool) {
+        self.outlier_filter = enabled;
+        self.recent_samples.clear();
+    }
+
     /// Register a burst generation event
     ///
     /// Call this when a burst is generated on output. The analyzer will
@@ -143,11 +235,25 @@ impl LatencyAnalyzer {
     /// # Returns
     /// Latency result if a matching burst was found
     pub fn match_detection(&mut self, detection: &DetectionEvent) -> Option<LatencyResult> {
+        // Only the first detection of a burst counts; a reflection shortly
+        // after it must not consume the next burst or skew the latency
+        if let Some(last) = self.last_match_frame {
+            let since_match = detection.input_frame.saturating_sub(last);
+            if detection.input_frame >= last && since_match < self.echo_guard_frames {
+                tracing::trace!(
+                    detection_frame = detection.input_frame,
+                    frames_since_match = since_match,
+                    "echo_suppressed"
+                );
+                return None;
+            }
+        }
+
         // Find the NEWEST burst within latency window. Using newest-first
         // naturally handles signal recovery: after a period of no detections,
         // stale bursts have large frame diffs and are skipped, while the
         // most recent burst matches with the correct latency.
-        let max_latency_frames = MAX_LATENCY_FRAMES;
+        let max_latency_frames = self.max_latency_frames;
 
         let mut matched_index = None;
 
@@ -177,6 +283,7 @@ impl LatencyAnalyzer {
                 "latency_matched"
             );
             self.last_result = Some(result.clone());
+            self.last_match_frame = Some(detection.input_frame);
             return Some(result);
         }
 
@@ -211,17 +318,26 @@ impl LatencyAnalyzer {
         let latency_samples = frame_diff as usize;
         let latency_ms = (frame_diff as f64 / self.sample_rate as f64) * 1000.0;
 
-        // Update running average
-        if self.measurement_count == 0 {
-            self.latency_average = latency_ms;
-        } else {
-            self.latency_average =
-                self.latency_average * (1.0 - self.average_alpha) + latency_ms * self.average_alpha;
+        if let Some(median) = self.check_outlier(latency_samples) {
+            let median_ms = median / self.sample_rate as f64 * 1000.0;
+            tracing::debug!(
+                raw_samples = latency_samples,
+                median_samples = median,
+                "latency_outlier_rejected"
+            );
+            // Report the median and keep the spike out of the running average
+            return LatencyResult {
+                latency_ms: median_ms,
+                raw_latency_ms: latency_ms,
+                latency_samples: median.round() as usize,
+                latency_us: samples_to_us(median, self.sample_rate),
+                confidence: OUTLIER_CONFIDENCE,
+                timestamp: Instant::now(),
+            };
         }
-        self.measurement_count += 1;
 
-        // Confidence based on stability
-        let stability_confidence = if self.measurement_count > 5 {
+        // Confidence based on stability against the average so far
+        let stability_confidence = if self.measurement_count >= 5 {
             // Reduce confidence if current measurement differs significantly from average
             let deviation = (latency_ms - self.latency_average).abs();
             let relative_deviation = deviation / self.latency_average.max(1.0);
@@ -234,14 +350,121 @@ impl LatencyAnalyzer {
         // Only reduce confidence for instability
         let confidence = (0.8 + stability_confidence * 0.2).min(1.0);
 
+        self.update_average(latency_ms, confidence);
+        self.measurement_count += 1;
+        self.track_drift(burst_event.start_frame, latency_samples);
+
         LatencyResult {
             latency_ms,
+            raw_latency_ms: latency_ms,
             latency_samples,
+            latency_us: samples_to_us(frame_diff as f64, self.sample_rate),
             confidence,
             timestamp: Instant::now(),
         }
     }
 
+    /// Fold a measurement into the running average, weighted by confidence
+    ///
+    /// A 0.5-confidence measurement moves the average half as far as a
+    /// 1.0-confidence one. The first measurement seeds the average.
+    fn update_average(&mut self, latency_ms: f64, confidence: f32) {
+        if self.measurement_count == 0 {
+            self.latency_average = latency_ms;
+        } else {
+            let alpha = self.average_alpha * confidence.clamp(0.0, 1.0) as f64;
+            self.latency_average = self.latency_average * (1.0 - alpha) + latency_ms * alpha;
+        }
+    }
+
+    /// Record a measurement for drift estimation and warn on excessive drift
+    ///
+    /// The warning is logged once when the drift crosses
+    /// [`DRIFT_WARNING_PPM`] and re-armed when it falls back below it.
+    fn track_drift(&mut self, start_frame: u64, latency_samples: usize) {
+        if self.drift_points.len() >= DRIFT_WINDOW {
+            self.drift_points.pop_front();
+        }
+        self.drift_points.push_back((start_frame, latency_samples));
+
+        let Some(ppm) = self.drift_ppm() else {
+            return;
+        };
+        let excessive = ppm.abs() > DRIFT_WARNING_PPM;
+        if excessive && !self.drift_warned {
+            tracing::warn!(
+                drift_ppm = %format!("{:.1}", ppm),
+                "Clock drift between input and output: latency is changing by {:.1} ppm \
+                 (separate I/O clocks?)",
+                ppm
+            );
+        }
+        self.drift_warned = excessive;
+    }
+
+    /// Estimated clock drift between input and output in ppm
+    ///
+    /// Least-squares slope of latency against output frame time over the
+    /// last [`DRIFT_WINDOW`] measurements: positive when latency grows (the
+    /// input clock runs slower than the output clock). Returns `None` until
+    /// the measurements span [`MIN_DRIFT_SPAN_SECS`] of output time.
+    pub fn drift_ppm(&self) -> Option<f64> {
+        let (first_frame, first_latency) = *self.drift_points.front()?;
+        let (last_frame, _) = *self.drift_points.back()?;
+        let span_frames = last_frame.saturating_sub(first_frame) as f64;
+        if span_frames < MIN_DRIFT_SPAN_SECS * self.sample_rate as f64 {
+            return None;
+        }
+
+        // Offsets from the first point keep the sums well inside f64 precision
+        let n = self.drift_points.len() as f64;
+        let points = self.drift_points.iter().map(|&(frame, latency)| {
+            (
+                frame.saturating_sub(first_frame) as f64,
+                latency as f64 - first_latency as f64,
+            )
+        });
+        let (sum_x, sum_y) = points
+            .clone()
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+        let (cov, var) = points.fold((0.0, 0.0), |(cov, var), (x, y)| {
+            (
+                cov + (x - mean_x) * (y - mean_y),
+                var + (x - mean_x) * (x - mean_x),
+            )
+        });
+        // Latency samples gained per output frame, scaled to parts per million
+        Some(cov / var * 1e6)
+    }
+
+    /// Record a raw measurement and test it against the recent history
+    ///
+    /// Returns the median of the window (in samples) when the newest value
+    /// deviates from it by more than [`OUTLIER_MAD_FACTOR`] times the median
+    /// absolute deviation. Outliers stay in the window, so a genuine latency
+    /// change is accepted once it makes up the majority of recent values.
+    fn check_outlier(&mut self, latency_samples: usize) -> Option<f64> {
+        if !self.outlier_filter {
+            return None;
+        }
+        if self.recent_samples.len() >= OUTLIER_WINDOW {
+            self.recent_samples.pop_front();
+        }
+        self.recent_samples.push_back(latency_samples);
+        if self.recent_samples.len() < 3 {
+            return None;
+        }
+
+        let values: Vec<f64> = self.recent_samples.iter().map(|&v| v as f64).collect();
+        let median = median_of(values.clone());
+        let mad =
+            median_of(values.iter().map(|v| (v - median).abs()).collect()).max(MIN_MAD_SAMPLES);
+
+        let deviation = (latency_samples as f64 - median).abs();
+        (deviation > OUTLIER_MAD_FACTOR * mad).then_some(median)
+    }
+
     /// Analyze input samples for burst detection (legacy interface)
     ///
     /// This method is kept for backward compatibility but should be avoided.
@@ -336,6 +559,21 @@ impl LatencyAnalyzer {
         self.last_result = None;
         self.latency_average = 0.0;
         self.measurement_count = 0;
+        self.recent_samples.clear();
+        self.drift_points.clear();
+        self.drift_warned = false;
+        self.last_match_frame = None;
+    }
+}
+
+/// Median of a non-empty set of values
+fn median_of(mut values: Vec<f64>) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
     }
 }
 
@@ -406,6 +644,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sub_ms_latency_in_microseconds() {
+        let mut analyzer = LatencyAnalyzer::new(192000);
+
+        // 50 samples at 192kHz = 260.4us
+        analyzer.register_burst(BurstEvent { start_frame: 8000 });
+        let result = analyzer
+            .match_detection(&DetectionEvent { input_frame: 8050 })
+            .expect("Should match burst");
+        assert_eq!(result.latency_samples, 50);
+        assert_eq!(result.latency_us, 260);
+        assert_eq!(
+            result.latency_us,
+            (result.latency_ms * 1000.0).round() as u64
+        );
+    }
+
+    #[test]
+    fn test_echo_after_match_suppressed() {
+        let mut analyzer = LatencyAnalyzer::new(48000);
+
+        // Direct sound at 5ms, reflection 4ms later while the next burst
+        // is already pending
+        analyzer.register_burst(BurstEvent { start_frame: 10000 });
+        let direct = analyzer
+            .match_detection(&DetectionEvent { input_frame: 10240 })
+            .expect("direct sound should match");
+        assert_eq!(direct.latency_samples, 240);
+        analyzer.register_burst(BurstEvent { start_frame: 10300 });
+        assert!(analyzer
+            .match_detection(&DetectionEvent { input_frame: 10432 })
+            .is_none());
+        assert_eq!(analyzer.last_result().unwrap().latency_samples, 240);
+        assert_eq!(analyzer.measurement_count(), 1);
+
+        // The next burst's direct sound still matches after the guard time
+        analyzer.register_burst(BurstEvent { start_frame: 14800 });
+        let next = analyzer
+            .match_detection(&DetectionEvent { input_frame: 15040 })
+            .expect("next burst should match");
+        assert_eq!(next.latency_samples, 240);
+
+        // Without the guard the reflection is measured as a latency
+        let mut unguarded = LatencyAnalyzer::new(48000);
+        unguarded.set_echo_guard_ms(0);
+        unguarded.register_burst(BurstEvent { start_frame: 10000 });
+        unguarded.match_detection(&DetectionEvent { input_frame: 10240 });
+        unguarded.register_burst(BurstEvent { start_frame: 10300 });
+        assert!(unguarded
+            .match_detection(&DetectionEvent { input_frame: 10432 })
+            .is_some());
+    }
+
     #[test]
     fn test_no_pending_no_match() {
         let mut analyzer = LatencyAnalyzer::new(48000);
@@ -441,8 +732,7 @@ mod tests {
         analyzer.register_burst(event);
 
         // Detection at frame way beyond max latency window
-        // MAX_LATENCY_FRAMES is 48000 (500ms at 96kHz)
-        // At 48kHz, 500ms = 24000 samples
+        // At 48kHz, MAX_LATENCY_MS (500ms) = 24000 samples
         let detection = DetectionEvent {
             input_frame: 100000,
         };
@@ -461,6 +751,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_long_latency_at_high_sample_rate() {
+        // 300ms at 192kHz is 57600 frames, beyond a fixed 48000-frame window
+        let mut analyzer = LatencyAnalyzer::new(192000);
+        analyzer.register_burst(BurstEvent { start_frame: 0 });
+        let result = analyzer
+            .match_detection(&DetectionEvent { input_frame: 57600 })
+            .expect("300ms latency should match at 192kHz");
+        assert!((result.latency_ms - 300.0).abs() < 0.01);
+
+        // Still bounded by MAX_LATENCY_MS
+        analyzer.register_burst(BurstEvent {
+            start_frame: 100000,
+        });
+        let late = DetectionEvent {
+            input_frame: 100000 + 192 * MAX_LATENCY_MS,
+        };
+        assert!(analyzer.match_detection(&late).is_none());
+    }
+
     #[test]
     fn test_latency_averaging() {
         let mut analyzer = LatencyAnalyzer::new(48000);
@@ -589,4 +899,127 @@ mod tests {
             }
         }
     }
+
+    /// Feed one burst/detection pair with the given latency in samples
+    fn measure(analyzer: &mut LatencyAnalyzer, i: u64, latency: u64) -> LatencyResult {
+        analyzer.register_burst(BurstEvent {
+            start_frame: i * 9600,
+        });
+        analyzer
+            .match_detection(&DetectionEvent {
+                input_frame: i * 9600 + latency,
+            })
+            .expect("burst should match")
+    }
+
+    #[test]
+    fn test_average_weighted_by_confidence() {
+        let mut weighted = LatencyAnalyzer::new(48000);
+        let mut plain = LatencyAnalyzer::new(48000);
+
+        // Alternate a trusted 5ms cluster with untrusted 8ms readings
+        for i in 0..40 {
+            let (latency, confidence) = if i % 2 == 0 { (5.0, 1.0) } else { (8.0, 0.2) };
+            weighted.update_average(latency, confidence);
+            weighted.measurement_count += 1;
+            plain.update_average(latency, 1.0);
+            plain.measurement_count += 1;
+        }
+
+        let weighted_error = (weighted.average_latency_ms() - 5.0).abs();
+        let plain_error = (plain.average_latency_ms() - 5.0).abs();
+        assert!(
+            weighted_error < plain_error / 2.0,
+            "weighted {:.3}ms vs plain {:.3}ms from the 5ms cluster",
+            weighted.average_latency_ms(),
+            plain.average_latency_ms()
+        );
+        assert_eq!(weighted.measurement_count(), 40);
+    }
+
+    #[test]
+    fn test_drift_ppm_from_linear_latency_ramp() {
+        let mut analyzer = LatencyAnalyzer::new(96000);
+
+        // Latency grows by 100 ppm of elapsed output time (quantized to samples)
+        for i in 0..700 {
+            let latency = 480 + i * 9600 * 100 / 1_000_000;
+            measure(&mut analyzer, i, latency);
+            if i < 100 {
+                // Less than 10s of output time: no estimate yet
+                assert_eq!(analyzer.drift_ppm(), None);
+            }
+        }
+        let ppm = analyzer.drift_ppm().expect("drift estimate");
+        assert!((ppm - 100.0).abs() < 1.0, "estimated {:.2} ppm", ppm);
+        assert!(analyzer.drift_warned);
+
+        analyzer.reset();
+        assert_eq!(analyzer.drift_ppm(), None);
+    }
+
+    #[test]
+    fn test_no_drift_for_constant_latency() {
+        let mut analyzer = LatencyAnalyzer::new(48000);
+        for i in 0..200 {
+            measure(&mut analyzer, i, 240);
+        }
+        assert_eq!(analyzer.drift_ppm(), Some(0.0));
+        assert!(!analyzer.drift_warned);
+    }
+
+    #[test]
+    fn test_outlier_filter_passes_clean_ramp() {
+        let mut analyzer = LatencyAnalyzer::new(96000);
+
+        for i in 0..20 {
+            let latency = 800 + i * 10;
+            let result = measure(&mut analyzer, i, latency);
+            assert_eq!(result.latency_samples, latency as usize);
+            assert_eq!(result.latency_ms, result.raw_latency_ms);
+        }
+        assert_eq!(analyzer.measurement_count(), 20);
+    }
+
+    #[test]
+    fn test_outlier_filter_rejects_single_spike() {
+        let mut analyzer = LatencyAnalyzer::new(96000);
+
+        for i in 0..10 {
+            measure(&mut analyzer, i, 800);
+        }
+        let average = analyzer.average_latency_ms();
+
+        let spike = measure(&mut analyzer, 10, 500);
+        assert_eq!(spike.latency_samples, 800);
+        assert!((spike.raw_latency_ms - 500.0 / 96.0).abs() < 1e-9);
+        assert!((spike.latency_ms - 800.0 / 96.0).abs() < 1e-9);
+        assert!(spike.confidence < 0.5, "outlier should be low confidence");
+        assert_eq!(analyzer.average_latency_ms(), average);
+        assert_eq!(analyzer.measurement_count(), 10);
+
+        // Stable measurements afterwards are accepted again
+        let next = measure(&mut analyzer, 11, 800);
+        assert_eq!(next.latency_ms, next.raw_latency_ms);
+        assert!(next.confidence > 0.8);
+    }
+
+    #[test]
+    fn test_outlier_filter_follows_real_latency_change() {
+        let mut analyzer = LatencyAnalyzer::new(96000);
+
+        for i in 0..10 {
+            measure(&mut analyzer, i, 800);
+        }
+        // A sustained step is accepted once it dominates the window
+        let accepted = (10..15)
+            .map(|i| measure(&mut analyzer, i, 1200))
+            .filter(|r| r.latency_ms == r.raw_latency_ms)
+            .count();
+        assert!(accepted >= 2, "only {} of 5 accepted", accepted);
+
+        analyzer.set_outlier_filter(false);
+        let result = measure(&mut analyzer, 15, 500);
+        assert_eq!(result.latency_samples, 500);
+    }
 }
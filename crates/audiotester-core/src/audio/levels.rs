@@ -0,0 +1,221 @@
+//! Peak and RMS input level metering
+//!
+//! The input callback accumulates one [`LevelAccumulator`] per metered
+//! channel over each buffer and publishes the result into a [`LevelMeter`]
+//! as fixed-point atomics, so "no signal" can be told apart from a weak or
+//! clipped one without locking in the audio thread. The same meter keeps a
+//! per-channel peak hold for the dashboard's input level bars.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Fixed-point scale for stored levels (1e-6 full-scale resolution)
+pub const LEVEL_SCALE: f32 = 1_000_000.0;
+
+/// Encode a linear level as fixed-point
+///
+/// Negative and NaN levels encode as 0; levels beyond the u32 range
+/// saturate.
+#[inline]
+pub fn encode_level(level: f32) -> u32 {
+    // Float-to-int `as` casts saturate and map NaN to 0
+    (level * LEVEL_SCALE).round() as u32
+}
+
+/// Decode a fixed-point level written by [`encode_level`]
+#[inline]
+pub fn decode_level(raw: u32) -> f32 {
+    raw as f32 / LEVEL_SCALE
+}
+
+/// Peak and RMS level of one channel over one callback buffer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct ChannelLevel {
+    /// Largest absolute sample (linear, 1.0 = full scale)
+    pub peak: f32,
+    /// Root mean square of the buffer (linear)
+    pub rms: f32,
+}
+
+/// Levels of the burst (ch0) and counter (ch1) input channels
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct InputLevels {
+    /// Burst channel level
+    pub burst: ChannelLevel,
+    /// Frame counter channel level
+    pub counter: ChannelLevel,
+}
+
+/// Running peak and sum of squares over one buffer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LevelAccumulator {
+    peak: f32,
+    sum_squares: f64,
+    count: usize,
+}
+
+impl LevelAccumulator {
+    /// Add one sample
+    #[inline]
+    pub fn push(&mut self, sample: f32) {
+        self.peak = self.peak.max(sample.abs());
+        self.sum_squares += sample as f64 * sample as f64;
+        self.count += 1;
+    }
+
+    /// Peak and RMS of the samples pushed so far (zero when empty)
+    pub fn level(&self) -> ChannelLevel {
+        let rms = if self.count > 0 {
+            (self.sum_squares / self.count as f64).sqrt() as f32
+        } else {
+            0.0
+        };
+        ChannelLevel {
+            peak: self.peak,
+            rms,
+        }
+    }
+}
+
+/// Levels of one metered input channel
+#[derive(Debug, Default)]
+struct ChannelMeter {
+    /// Peak of the latest buffer
+    peak: AtomicU32,
+    /// RMS of the latest buffer
+    rms: AtomicU32,
+    /// Highest peak since the hold was last taken
+    peak_hold: AtomicU32,
+}
+
+/// Input levels published by the input callback
+///
+/// Holds, per metered channel, the peak and RMS of the most recent callback
+/// buffer plus a peak hold, all as fixed-point values (see [`encode_level`])
+/// for the engine thread to read. The encoding is monotonic, so the peak
+/// hold is kept with `fetch_max`.
+#[derive(Debug)]
+pub struct LevelMeter {
+    channels: Vec<ChannelMeter>,
+    burst_channel: usize,
+    counter_channel: usize,
+}
+
+impl LevelMeter {
+    /// Create a meter for the first `channels` inputs, reading all zeros
+    ///
+    /// `burst_channel` and `counter_channel` select the channels reported
+    /// by [`LevelMeter::snapshot`].
+    pub fn new(channels: usize, burst_channel: usize, counter_channel: usize) -> Self {
+        Self {
+            channels: (0..channels).map(|_| ChannelMeter::default()).collect(),
+            burst_channel,
+            counter_channel,
+        }
+    }
+
+    /// Number of metered channels
+    pub fn channel_count(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Publish the levels of the latest buffer, one per metered channel
+    pub fn update(&self, levels: &[LevelAccumulator]) {
+        for (meter, level) in self.channels.iter().zip(levels) {
+            let level = level.level();
+            let peak = encode_level(level.peak);
+            meter.peak.store(peak, Ordering::Relaxed);
+            meter.rms.store(encode_level(level.rms), Ordering::Relaxed);
+            meter.peak_hold.fetch_max(peak, Ordering::Relaxed);
+        }
+    }
+
+    /// Latest buffer level of one channel (zero when not metered)
+    pub fn channel_level(&self, channel: usize) -> ChannelLevel {
+        self.channels
+            .get(channel)
+            .map(|meter| ChannelLevel {
+                peak: decode_level(meter.peak.load(Ordering::Relaxed)),
+                rms: decode_level(meter.rms.load(Ordering::Relaxed)),
+            })
+            .unwrap_or_default()
+    }
+
+    /// Read the last published burst and counter levels
+    pub fn snapshot(&self) -> InputLevels {
+        InputLevels {
+            burst: self.channel_level(self.burst_channel),
+            counter: self.channel_level(self.counter_channel),
+        }
+    }
+
+    /// Take the peak hold of the first `channels` metered channels
+    ///
+    /// Reading resets the hold, so callers polling at a fixed interval get
+    /// the peak for that interval.
+    pub fn take_peak_hold(&self, channels: usize) -> Vec<f32> {
+        self.channels
+            .iter()
+            .take(channels)
+            .map(|meter| decode_level(meter.peak_hold.swap(0, Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_encoding() {
+        for level in [0.0, 1e-6, 0.001, 0.5, 1.0, 2.5] {
+            let decoded = decode_level(encode_level(level));
+            assert!((decoded - level).abs() <= 1e-6, "{} -> {}", level, decoded);
+        }
+        assert_eq!(encode_level(-0.5), 0);
+        assert_eq!(encode_level(f32::NAN), 0);
+        assert_eq!(encode_level(f32::INFINITY), u32::MAX);
+    }
+
+    #[test]
+    fn test_accumulator_and_meter() {
+        let mut levels = [LevelAccumulator::default(); 3];
+        for sample in [0.5, -0.5, 0.5, -0.5] {
+            levels[0].push(sample);
+            levels[2].push(sample * 0.5);
+        }
+        // Burst on ch2, counter on ch1 (silent)
+        let meter = LevelMeter::new(3, 2, 1);
+        meter.update(&levels);
+
+        let snapshot = meter.snapshot();
+        assert!((snapshot.burst.peak - 0.25).abs() < 1e-6);
+        assert!((snapshot.burst.rms - 0.25).abs() < 1e-6);
+        assert_eq!(snapshot.counter, ChannelLevel::default());
+        assert_eq!(meter.channel_level(7), ChannelLevel::default());
+    }
+
+    #[test]
+    fn test_peak_hold() {
+        let meter = LevelMeter::new(2, 0, 1);
+        let buffer = |peak: f32| {
+            let mut levels = [LevelAccumulator::default(); 2];
+            levels[0].push(peak);
+            levels
+        };
+
+        // The hold keeps the loudest buffer; the snapshot shows the latest
+        meter.update(&buffer(0.8));
+        meter.update(&buffer(0.1));
+        assert!((meter.snapshot().burst.peak - 0.1).abs() < 1e-6);
+        let held = meter.take_peak_hold(2);
+        assert_eq!(held.len(), 2);
+        assert!((held[0] - 0.8).abs() < 1e-6);
+        assert_eq!(held[1], 0.0);
+
+        // Taking resets the hold but not the latest level
+        assert_eq!(meter.take_peak_hold(2), vec![0.0, 0.0]);
+        assert!((meter.snapshot().burst.peak - 0.1).abs() < 1e-6);
+        assert_eq!(meter.take_peak_hold(1).len(), 1);
+    }
+}
@@ -2,15 +2,27 @@
 //!
 //! This module contains all audio-related functionality including:
 //! - ASIO device management ([`engine`])
+//! - WAV capture of the input channel for debugging ([`capture`])
 //! - Burst signal generation for latency measurement ([`burst`])
 //! - Envelope-based burst detection ([`detector`])
 //! - Timestamp-based latency calculation ([`latency`])
+//! - Peak/RMS metering of the burst and counter inputs ([`levels`])
 //! - Frame counter analysis for loss detection ([`analyzer`])
+//! - MLS correlation fallback when burst detection fails ([`fallback`])
+//! - Offline latency analysis of recorded loopback WAVs ([`offline`])
+//! - Hardware-free check of the latency path ([`selftest`])
+//! - Sine test tone for routing verification ([`tone`])
 //! - MLS test signal generation (legacy, [`signal`])
 
 pub mod analyzer;
 pub mod burst;
+pub mod capture;
 pub mod detector;
 pub mod engine;
+pub mod fallback;
 pub mod latency;
+pub mod levels;
+pub mod offline;
+pub mod selftest;
 pub mod signal;
+pub mod tone;
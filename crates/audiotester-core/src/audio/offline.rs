@@ -0,0 +1,157 @@
+//! Offline latency analysis of recorded loopback WAV files
+//!
+//! Feeds a generated output recording and the matching captured input
+//! through the same [`BurstDetector`] + [`LatencyAnalyzer`] pipeline the
+//! engine callbacks use, without any audio device. Burst starts are taken
+//! from detections on the output file, so both sides carry the same
+//! detector onset delay and it cancels out of the measured latency.
+
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
+
+use crate::audio::burst::{BurstEvent, DetectionEvent};
+use crate::audio::detector::BurstDetector;
+use crate::audio::latency::{LatencyAnalyzer, LatencyResult};
+
+/// Read channel 0 of a WAV file as normalized f32 samples
+///
+/// Accepts float and integer PCM of any channel count; the burst signal
+/// lives on channel 0 of both the engine output and input captures.
+pub fn read_burst_channel(path: &Path) -> Result<(Vec<f32>, u32)> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|v| v as f32 * scale))
+                .collect::<Result<_, _>>()
+                .with_context(|| format!("Failed to read {}", path.display()))?
+        }
+    };
+
+    let samples = interleaved.into_iter().step_by(channels).collect();
+    Ok((samples, spec.sample_rate))
+}
+
+/// Measure latency between a recorded output and its captured loopback input
+///
+/// Both files must share one sample rate. Returns one result per matched
+/// burst, in order.
+///
+/// # Example
+/// ```no_run
+/// use audiotester_core::audio::offline::analyze_wav;
+/// use std::path::Path;
+///
+/// let results = analyze_wav(Path::new("out.wav"), Path::new("in.wav")).unwrap();
+/// for r in &results {
+///     println!("{:.3} ms", r.latency_ms);
+/// }
+/// ```
+pub fn analyze_wav(out_wav: &Path, in_wav: &Path) -> Result<Vec<LatencyResult>> {
+    let (output, out_rate) = read_burst_channel(out_wav)?;
+    let (input, in_rate) = read_burst_channel(in_wav)?;
+    if out_rate != in_rate {
+        return Err(anyhow!(
+            "Sample rate mismatch: output {} Hz, input {} Hz",
+            out_rate,
+            in_rate
+        ));
+    }
+    Ok(analyze_samples(&output, &input, out_rate))
+}
+
+/// Run burst detection and frame matching over aligned output/input samples
+///
+/// Frame N of `output` and frame N of `input` are treated as sharing one
+/// sample clock, as the engine's output and input callbacks do.
+pub fn analyze_samples(output: &[f32], input: &[f32], sample_rate: u32) -> Vec<LatencyResult> {
+    let mut out_detector = BurstDetector::new(sample_rate);
+    let mut in_detector = BurstDetector::new(sample_rate);
+    let mut analyzer = LatencyAnalyzer::new(sample_rate);
+    let mut results = Vec::new();
+
+    for frame in 0..output.len().max(input.len()) {
+        if let Some(&sample) = output.get(frame) {
+            if out_detector.process(sample, 0).is_some() {
+                analyzer.register_burst(BurstEvent {
+                    start_frame: frame as u64,
+                });
+            }
+        }
+        if let Some(&sample) = input.get(frame) {
+            if in_detector.process(sample, 0).is_some() {
+                if let Some(result) = analyzer.match_detection(&DetectionEvent {
+                    input_frame: frame as u64,
+                }) {
+                    results.push(result);
+                }
+            }
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn test_fixture_latency() {
+        // 48kHz loopback recorded with a 5ms (240 frame) path delay
+        let results = analyze_wav(
+            &fixture("loopback_48k_5ms_out.wav"),
+            &fixture("loopback_48k_5ms_in.wav"),
+        )
+        .unwrap();
+
+        assert!(results.len() >= 4, "only {} bursts matched", results.len());
+        for r in &results {
+            assert!(
+                (r.raw_latency_ms - 5.0).abs() <= 0.1,
+                "measured {} ms",
+                r.raw_latency_ms
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_rate_mismatch_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, rate: u32| {
+            let path = dir.path().join(name);
+            let spec = hound::WavSpec {
+                channels: 1,
+                sample_rate: rate,
+                bits_per_sample: 32,
+                sample_format: hound::SampleFormat::Float,
+            };
+            hound::WavWriter::create(&path, spec)
+                .unwrap()
+                .finalize()
+                .unwrap();
+            path
+        };
+
+        let out = write("out.wav", 48000);
+        let input = write("in.wav", 96000);
+        assert!(analyze_wav(&out, &input).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! Hardware-free self-test of the latency measurement path
+//!
+//! Runs the burst generator output through a simulated loopback delay into a
+//! fresh [`BurstDetector`] and [`LatencyAnalyzer`], exactly as the engine
+//! callbacks would, and checks the measured latency against the injected
+//! delay. Useful for CI and remote diagnostics where no audio device exists.
+
+use crate::audio::burst::{BurstEvent, BurstGenerator, DetectionEvent};
+use crate::audio::detector::BurstDetector;
+use crate::audio::latency::LatencyAnalyzer;
+
+/// Largest accepted difference between measured and injected latency (ms)
+pub const SELFTEST_TOLERANCE_MS: f64 = 0.5;
+
+/// Number of burst cycles pushed through the pipeline
+const SELFTEST_CYCLES: usize = 3;
+
+/// Outcome of a self-test run
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestResult {
+    /// Measured latency is within [`SELFTEST_TOLERANCE_MS`] of the injected delay
+    pub ok: bool,
+    /// Latency reported by the analyzer (0 when nothing was matched)
+    pub measured_ms: f64,
+    /// Injected loopback delay
+    pub expected_ms: f64,
+}
+
+/// Measure a synthetic loopback of `delay_frames` at `sample_rate`
+///
+/// # Example
+/// ```
+/// use audiotester_core::audio::selftest::run_selftest;
+///
+/// let result = run_selftest(48000, 240); // 5ms loopback
+/// assert!(result.ok);
+/// ```
+pub fn run_selftest(sample_rate: u32, delay_frames: usize) -> SelfTestResult {
+    let mut generator = BurstGenerator::new(sample_rate);
+    let mut detector = BurstDetector::new(sample_rate);
+    let mut analyzer = LatencyAnalyzer::new(sample_rate);
+
+    // Ring of output samples still "in flight" through the simulated loopback
+    let mut line = vec![0.0f32; delay_frames + 1];
+    let line_len = line.len();
+    let total_frames = generator.cycle_length() * SELFTEST_CYCLES;
+    let mut measured_ms = 0.0;
+
+    for frame in 0..total_frames {
+        // Output callback: generate and register bursts by frame counter
+        let (sample, is_burst_start) = generator.next_sample();
+        if is_burst_start {
+            analyzer.register_burst(BurstEvent {
+                start_frame: frame as u64,
+            });
+        }
+        line[frame % line_len] = sample;
+
+        // Input callback: the sample written `delay_frames` ago arrives now
+        let delayed = line[(frame + 1) % line_len];
+        if detector.process(delayed, 0).is_some() {
+            if let Some(result) = analyzer.match_detection(&DetectionEvent {
+                input_frame: frame as u64,
+            }) {
+                measured_ms = result.latency_ms;
+            }
+        }
+    }
+
+    let expected_ms = delay_frames as f64 * 1000.0 / sample_rate as f64;
+    SelfTestResult {
+        ok: measured_ms > 0.0 && (measured_ms - expected_ms).abs() <= SELFTEST_TOLERANCE_MS,
+        measured_ms,
+        expected_ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selftest_matches_injected_delay() {
+        for (rate, delay) in [(48000, 240), (96000, 1200), (44100, 2205)] {
+            let result = run_selftest(rate, delay);
+            assert!(result.ok, "{:?} at {} Hz", result, rate);
+        }
+    }
+}
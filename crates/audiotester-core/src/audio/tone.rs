@@ -0,0 +1,161 @@
+//! Sine test tone for speaker and routing verification
+//!
+//! While a tone plays, the output callback replaces the burst with a sine on
+//! the chosen channel. Requests are published through [`ToneControl`] atomics
+//! so the audio thread switches between burst and tone output without
+//! rebuilding the stream, and falls back to bursts once the requested
+//! duration has been played.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+/// Longest test tone accepted in milliseconds
+pub const MAX_TONE_DURATION_MS: u32 = 10_000;
+
+/// Tone amplitude (-12dB, quieter than the burst to spare ears and speakers)
+const TONE_AMPLITUDE: f32 = 0.25;
+
+/// Phase-accumulating sine generator
+///
+/// # Example
+/// ```
+/// use audiotester_core::audio::tone::ToneGenerator;
+///
+/// let mut gen = ToneGenerator::new(1000.0, 48000);
+/// let sample = gen.next_sample();
+/// ```
+#[derive(Debug)]
+pub struct ToneGenerator {
+    /// Current phase in cycles (0.0..1.0)
+    phase: f64,
+    /// Phase advance per sample in cycles
+    phase_step: f64,
+}
+
+impl ToneGenerator {
+    /// Create a generator for `freq_hz` at `sample_rate`
+    pub fn new(freq_hz: f32, sample_rate: u32) -> Self {
+        let mut gen = Self {
+            phase: 0.0,
+            phase_step: 0.0,
+        };
+        gen.set_frequency(freq_hz, sample_rate);
+        gen
+    }
+
+    /// Change the frequency, keeping the phase continuous
+    pub fn set_frequency(&mut self, freq_hz: f32, sample_rate: u32) {
+        self.phase_step = freq_hz as f64 / sample_rate as f64;
+    }
+
+    /// Generate the next sample
+    pub fn next_sample(&mut self) -> f32 {
+        let sample = (self.phase * std::f64::consts::TAU).sin() as f32 * TONE_AMPLITUDE;
+        self.phase = (self.phase + self.phase_step).fract();
+        sample
+    }
+}
+
+/// Tone slice claimed by one output callback
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneSlice {
+    /// Tone frequency in Hz
+    pub freq_hz: f32,
+    /// Output channel carrying the tone
+    pub channel: usize,
+    /// Number of frames of this callback that carry the tone
+    pub frames: u64,
+}
+
+/// Output mode shared between the engine thread and the output callback
+///
+/// A non-zero frame budget means tone mode; the callback consumes it and
+/// returns to burst mode when it reaches zero.
+#[derive(Debug, Default)]
+pub struct ToneControl {
+    /// Tone frames still to be played (0 = burst mode)
+    remaining_frames: AtomicU64,
+    /// Tone frequency (f32 bits)
+    freq_bits: AtomicU32,
+    /// Output channel carrying the tone
+    channel: AtomicUsize,
+}
+
+impl ToneControl {
+    /// Create a control in burst mode
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Switch to tone mode for `frames` frames
+    ///
+    /// Replaces any tone still playing.
+    pub fn start(&self, freq_hz: f32, channel: usize, frames: u64) {
+        self.freq_bits.store(freq_hz.to_bits(), Ordering::Relaxed);
+        self.channel.store(channel, Ordering::Relaxed);
+        // Release publishes the parameters above to the callback
+        self.remaining_frames.store(frames, Ordering::Release);
+    }
+
+    /// Whether a tone is currently playing
+    pub fn is_active(&self) -> bool {
+        self.remaining_frames.load(Ordering::Acquire) > 0
+    }
+
+    /// Claim up to `frames` tone frames for one callback (None in burst mode)
+    pub fn take(&self, frames: u64) -> Option<ToneSlice> {
+        let previous = self
+            .remaining_frames
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |remaining| {
+                (remaining > 0).then(|| remaining.saturating_sub(frames))
+            })
+            .ok()?;
+        Some(ToneSlice {
+            freq_hz: f32::from_bits(self.freq_bits.load(Ordering::Relaxed)),
+            channel: self.channel.load(Ordering::Relaxed),
+            frames: previous.min(frames),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tone_period_matches_frequency() {
+        // 1kHz at 48kHz repeats every 48 samples
+        let mut gen = ToneGenerator::new(1000.0, 48000);
+        let samples: Vec<f32> = (0..480).map(|_| gen.next_sample()).collect();
+
+        for i in 0..(samples.len() - 48) {
+            assert!((samples[i] - samples[i + 48]).abs() < 1e-4, "sample {i}");
+        }
+
+        // Exactly one rising zero crossing per period
+        let rising = samples
+            .windows(2)
+            .filter(|w| w[0] < 0.0 && w[1] >= 0.0)
+            .count();
+        assert_eq!(rising, 9);
+
+        let peak = samples.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+        assert!((peak - TONE_AMPLITUDE).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_tone_control_consumes_budget() {
+        let control = ToneControl::new();
+        assert!(control.take(256).is_none());
+
+        control.start(440.0, 3, 600);
+        assert!(control.is_active());
+        let slice = control.take(256).unwrap();
+        assert_eq!(slice.freq_hz, 440.0);
+        assert_eq!(slice.channel, 3);
+        assert_eq!(slice.frames, 256);
+        assert_eq!(control.take(256).unwrap().frames, 256);
+        assert_eq!(control.take(256).unwrap().frames, 88);
+        assert!(!control.is_active());
+        assert!(control.take(256).is_none());
+    }
+}
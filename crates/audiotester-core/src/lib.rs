@@ -29,7 +29,10 @@ pub use audio::analyzer::{Analyzer, FrameLossResult};
 // Legacy MLS exports (for backward compatibility and fallback)
 pub use audio::signal::MlsGenerator;
 
-pub use stats::store::{DisconnectionEvent, LossEvent, StatsStore};
+pub use stats::baseline::{Baseline, BaselineDiff};
+pub use stats::store::{
+    AsioEvent, AsioEventKind, DisconnectionEvent, LossEvent, SessionSummary, SpikeEvent, StatsStore,
+};
 
 /// Application version from Cargo.toml
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -40,10 +43,10 @@ pub const BUILD_DATE: &str = env!("BUILD_DATE");
 /// Default sample rate for audio processing (96kHz for professional setups)
 pub const DEFAULT_SAMPLE_RATE: u32 = 96000;
 
-/// Burst cycle duration in milliseconds (100ms = 10Hz update rate)
+/// Default burst cycle duration in milliseconds (100ms = 10Hz update rate)
 pub const BURST_CYCLE_MS: u32 = 100;
 
-/// Burst duration in milliseconds (10ms of noise per cycle)
+/// Default burst duration in milliseconds (10ms of noise per cycle)
 pub const BURST_DURATION_MS: u32 = 10;
 
 /// MLS sequence order (2^ORDER - 1 samples) - legacy, for fallback
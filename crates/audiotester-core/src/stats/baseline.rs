@@ -0,0 +1,159 @@
+//! Reference latency/loss profile for regression checks
+//!
+//! After commissioning, the current profile is saved as a [`Baseline`].
+//! Comparing a later profile against it ([`Baseline::diff`]) shows whether
+//! a firmware or driver update made the path slower or lossier.
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Latency and loss profile of a monitoring session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// When the profile was taken
+    pub saved_at: DateTime<Utc>,
+    /// Average latency (ms)
+    pub avg_latency_ms: f64,
+    /// 95th percentile of the recent latency history (ms)
+    pub p95_latency_ms: f64,
+    /// Maximum latency (ms)
+    pub max_latency_ms: f64,
+    /// Latency jitter (ms)
+    pub jitter_ms: f64,
+    /// Samples lost per minute, averaged over the monitored time
+    pub loss_rate_per_min: f64,
+}
+
+/// One metric of a baseline comparison
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BaselineDelta {
+    /// Value when the baseline was saved
+    pub baseline: f64,
+    /// Value now
+    pub current: f64,
+    /// `current - baseline` (positive means worse)
+    pub delta: f64,
+}
+
+impl BaselineDelta {
+    fn new(baseline: f64, current: f64) -> Self {
+        Self {
+            baseline,
+            current,
+            delta: current - baseline,
+        }
+    }
+}
+
+/// Current profile compared against a saved baseline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BaselineDiff {
+    /// When the baseline was saved
+    pub baseline_saved_at: DateTime<Utc>,
+    /// Average latency (ms)
+    pub avg_latency_ms: BaselineDelta,
+    /// 95th percentile latency (ms)
+    pub p95_latency_ms: BaselineDelta,
+    /// Maximum latency (ms)
+    pub max_latency_ms: BaselineDelta,
+    /// Latency jitter (ms)
+    pub jitter_ms: BaselineDelta,
+    /// Samples lost per minute
+    pub loss_rate_per_min: BaselineDelta,
+}
+
+impl Baseline {
+    /// Compare `current` against this baseline
+    pub fn diff(&self, current: &Baseline) -> BaselineDiff {
+        BaselineDiff {
+            baseline_saved_at: self.saved_at,
+            avg_latency_ms: BaselineDelta::new(self.avg_latency_ms, current.avg_latency_ms),
+            p95_latency_ms: BaselineDelta::new(self.p95_latency_ms, current.p95_latency_ms),
+            max_latency_ms: BaselineDelta::new(self.max_latency_ms, current.max_latency_ms),
+            jitter_ms: BaselineDelta::new(self.jitter_ms, current.jitter_ms),
+            loss_rate_per_min: BaselineDelta::new(
+                self.loss_rate_per_min,
+                current.loss_rate_per_min,
+            ),
+        }
+    }
+
+    /// Write the baseline to `path` as JSON
+    ///
+    /// Writes to a temporary file first and renames it into place, so a
+    /// crash mid-write never leaves a truncated baseline behind.
+    pub fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize baseline")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Load a baseline written by [`Baseline::save_to_path`]
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+        let data =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        serde_json::from_slice(&data).context("Failed to parse baseline file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::store::StatsStore;
+
+    #[test]
+    fn test_diff_against_stored_baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.json");
+
+        // Commissioning: 5ms, 10 samples lost over 10 minutes
+        let mut store = StatsStore::new();
+        for _ in 0..10 {
+            store.record_latency(5.0);
+        }
+        store.record_loss(10);
+        store.tick_availability(std::time::Duration::from_secs(600), true);
+        let saved = store.baseline();
+        saved.save_to_path(&path).unwrap();
+        assert_eq!(saved.loss_rate_per_min, 1.0);
+
+        // After a driver update: 7ms, 40 samples lost over 10 minutes
+        let mut store = StatsStore::new();
+        for _ in 0..10 {
+            store.record_latency(7.0);
+        }
+        store.record_loss(40);
+        store.tick_availability(std::time::Duration::from_secs(600), true);
+
+        let baseline = Baseline::load_from_path(&path).unwrap();
+        assert_eq!(baseline, saved);
+        let diff = baseline.diff(&store.baseline());
+        assert_eq!(diff.baseline_saved_at, saved.saved_at);
+        assert_eq!(
+            diff.avg_latency_ms,
+            BaselineDelta {
+                baseline: 5.0,
+                current: 7.0,
+                delta: 2.0,
+            }
+        );
+        assert_eq!(diff.max_latency_ms.delta, 2.0);
+        assert_eq!(diff.loss_rate_per_min.current, 4.0);
+        assert_eq!(diff.loss_rate_per_min.delta, 3.0);
+    }
+
+    #[test]
+    fn test_missing_baseline_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(Baseline::load_from_path(&dir.path().join("baseline.json")).is_err());
+    }
+}
@@ -3,4 +3,5 @@
 //! Stores time-series data for latency measurements, sample loss events,
 //! and other metrics for display in the statistics window.
 
+pub mod baseline;
 pub mod store;
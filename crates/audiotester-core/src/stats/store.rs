@@ -2,8 +2,15 @@
 //!
 //! Stores historical measurements with automatic cleanup of old data.
 
+use crate::audio::fallback::LatencyMethod;
+use crate::stats::baseline::Baseline;
+use anyhow::Context;
 use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::VecDeque;
+use std::path::Path;
+use std::time::Duration;
 
 /// Maximum number of data points to keep in recent history (full resolution)
 const MAX_HISTORY_SIZE: usize = 3600; // 1 hour at 1 sample/sec
@@ -11,8 +18,11 @@ const MAX_HISTORY_SIZE: usize = 3600; // 1 hour at 1 sample/sec
 /// Maximum number of data points in archive (down-sampled)
 const MAX_ARCHIVE_SIZE: usize = 120960; // 14 days at 10-second intervals
 
-/// Duration of each loss archive bucket in seconds
-const LOSS_BUCKET_DURATION_SECS: i64 = 10;
+/// Default duration of each loss/latency archive bucket in seconds
+pub const LOSS_BUCKET_DURATION_SECS: i64 = 10;
+
+/// Default archive down-sampling ratio (archive every Nth measurement)
+pub const DEFAULT_ARCHIVE_RATIO: u64 = 10;
 
 /// Maximum number of loss archive buckets (14 days at 10s = 120960)
 const MAX_LOSS_ARCHIVE_SIZE: usize = 120960;
@@ -20,8 +30,59 @@ const MAX_LOSS_ARCHIVE_SIZE: usize = 120960;
 /// Maximum number of latency bucket archive entries (14 days at 10s = 120960)
 const MAX_LATENCY_BUCKET_ARCHIVE_SIZE: usize = 120960;
 
+/// Width of each latency histogram bin (ms)
+pub const LATENCY_HISTOGRAM_BIN_MS: f64 = 1.0;
+
+/// Number of latency histogram bins (last bin collects everything >= 99ms)
+pub const LATENCY_HISTOGRAM_BINS: usize = 100;
+
+/// Measurements required before the histogram is trusted for anomaly detection
+const ANOMALY_MIN_SAMPLES: u64 = 100;
+
+/// A measurement is anomalous when its bin and both neighbours together
+/// hold less than this fraction of all historical measurements
+const ANOMALY_MAX_FRACTION: f64 = 0.001;
+
+/// Maximum number of anomaly events kept for timeline markers
+const MAX_ANOMALY_EVENTS: usize = 1000;
+
+/// Default number of jitter standard deviations above the average that
+/// makes a measurement a latency spike
+pub const DEFAULT_SPIKE_FACTOR: f64 = 5.0;
+
+/// Lower bound for the jitter used in spike detection, so a perfectly
+/// stable path does not turn sub-sample noise into spikes (ms)
+const SPIKE_MIN_JITTER_MS: f64 = 0.1;
+
+/// Measurements required before spikes are detected
+const SPIKE_MIN_SAMPLES: usize = 10;
+
+/// Maximum number of latency spike events kept
+const MAX_SPIKE_EVENTS: usize = 1000;
+
+/// Maximum number of ASIO driver events kept for the timeline
+const MAX_ASIO_EVENTS: usize = 1000;
+
+/// Maximum number of loss events kept (oldest are dropped)
+pub const MAX_LOSS_EVENTS: usize = 10_000;
+
+/// Default window within which consecutive losses merge into one event (ms)
+pub const DEFAULT_LOSS_COALESCE_MS: u64 = 1000;
+
+/// Number of most recent latency measurements used for jitter (matches the plot window)
+pub const JITTER_WINDOW: usize = 300;
+
+/// Number of consecutive-measurement deltas kept for the jitter histogram
+pub const JITTER_DELTA_WINDOW: usize = 3000;
+
+/// Upper bound on jitter histogram buckets; larger deltas land in the last one
+pub const MAX_JITTER_BUCKETS: usize = 1000;
+
+/// Window for the rolling loss rate (seconds)
+const LOSS_RATE_WINDOW_SECS: u64 = 60;
+
 /// A single measurement point
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Measurement {
     /// Timestamp of the measurement
     pub timestamp: DateTime<Utc>,
@@ -30,7 +91,7 @@ pub struct Measurement {
 }
 
 /// A disconnection event with timestamp and duration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DisconnectionEvent {
     /// When the disconnection was detected
     pub timestamp: DateTime<Utc>,
@@ -40,8 +101,83 @@ pub struct DisconnectionEvent {
     pub reconnected: bool,
 }
 
+/// Kind of ASIO driver event seen by the monitoring loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AsioEventKind {
+    /// Driver sent kAsioResetRequest and the stream was invalidated
+    Reset,
+    /// Streams stayed alive but went silent until the engine was restarted
+    /// (e.g. VBMatrix "Restart Audio Engine" or a buffer size change)
+    VirtualRestart,
+}
+
+/// An ASIO driver event with timestamp
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsioEvent {
+    /// When the event was detected
+    pub timestamp: DateTime<Utc>,
+    /// What the driver did
+    pub kind: AsioEventKind,
+}
+
+/// A latency measurement well above the recent baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpikeEvent {
+    /// When the spike was measured
+    pub timestamp: DateTime<Utc>,
+    /// Measured latency (ms)
+    pub latency_ms: f64,
+    /// Average latency before the spike (ms)
+    pub baseline_ms: f64,
+}
+
+/// One-shot summary of a monitoring session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionSummary {
+    /// Seconds monitored with the engine running
+    pub duration_secs: u64,
+    /// Minimum latency (ms, 0 without measurements)
+    pub min_latency_ms: f64,
+    /// Average latency (ms)
+    pub avg_latency_ms: f64,
+    /// Maximum latency (ms)
+    pub max_latency_ms: f64,
+    /// 95th percentile of the recent latency history (ms)
+    pub p95_latency_ms: f64,
+    /// Samples lost
+    pub total_lost: u64,
+    /// Samples corrupted
+    pub total_corrupted: u64,
+    /// Disconnections recorded
+    pub disconnection_count: usize,
+    /// Share of monitored time with the signal present (%, None before monitoring)
+    pub availability_percent: Option<f64>,
+}
+
+impl std::fmt::Display for SessionSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}s, latency min/avg/max/p95 {:.2}/{:.2}/{:.2}/{:.2} ms, lost {}, corrupted {}, disconnections {}",
+            self.duration_secs,
+            self.min_latency_ms,
+            self.avg_latency_ms,
+            self.max_latency_ms,
+            self.p95_latency_ms,
+            self.total_lost,
+            self.total_corrupted,
+            self.disconnection_count
+        )?;
+        if let Some(availability) = self.availability_percent {
+            write!(f, ", availability {:.2}%", availability)?;
+        }
+        Ok(())
+    }
+}
+
 /// A loss event with timestamp and count
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LossEvent {
     /// When the loss was detected
     pub timestamp: DateTime<Utc>,
@@ -50,9 +186,9 @@ pub struct LossEvent {
 }
 
 /// Aggregated loss over a fixed time window (10 seconds)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LossBucket {
-    /// Start of this bucket (truncated to LOSS_BUCKET_DURATION_SECS boundary)
+    /// Start of this bucket (truncated to the store's bucket duration)
     pub timestamp: DateTime<Utc>,
     /// Total samples lost in this bucket
     pub total_loss: u64,
@@ -61,9 +197,9 @@ pub struct LossBucket {
 }
 
 /// Aggregated latency over a fixed time window (10 seconds)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LatencyBucket {
-    /// Start of this bucket (truncated to LOSS_BUCKET_DURATION_SECS boundary)
+    /// Start of this bucket (truncated to the store's bucket duration)
     pub timestamp: DateTime<Utc>,
     /// Average latency in this bucket (ms)
     pub avg_latency: f64,
@@ -88,10 +224,12 @@ pub struct StatsStore {
     loss_history: VecDeque<Measurement>,
     /// Corruption events over time
     corruption_history: VecDeque<Measurement>,
+    /// Measurement confidence (0.0 to 1.0) over time
+    confidence_history: VecDeque<Measurement>,
     /// Disconnection events
     disconnection_events: Vec<DisconnectionEvent>,
-    /// Loss events with timestamps
-    loss_events: Vec<LossEvent>,
+    /// Loss events with timestamps, oldest first
+    loss_events: VecDeque<LossEvent>,
     /// Loss archive: 10-second buckets for 14d timeline
     loss_archive: VecDeque<LossBucket>,
     /// Latency bucket archive: 10-second buckets for 14d timeline
@@ -104,6 +242,46 @@ pub struct StatsStore {
     stats: RunningStats,
     /// Counter for archive down-sampling (archive every N measurements)
     archive_counter: u64,
+    /// Archive every `archive_ratio`-th measurement
+    archive_ratio: u64,
+    /// Duration of each loss/latency archive bucket (seconds)
+    bucket_secs: i64,
+    /// Learned latency distribution (counts per LATENCY_HISTOGRAM_BIN_MS bin)
+    latency_histogram: Vec<u64>,
+    /// Total measurements in latency_histogram
+    histogram_total: u64,
+    /// Measurements flagged as anomalous (for timeline markers)
+    anomaly_events: VecDeque<Measurement>,
+    /// Measurements above `avg + spike_factor * jitter`, oldest first
+    spike_events: VecDeque<SpikeEvent>,
+    /// Jitter multiple above the average that counts as a spike
+    spike_factor: f64,
+    /// Losses within this many ms of the last loss event are added to it
+    loss_coalesce_ms: u64,
+    /// When the store was last written to disk
+    last_saved: Option<DateTime<Utc>>,
+    /// Absolute differences between consecutive latency measurements (ms)
+    latency_deltas: VecDeque<f64>,
+    /// ASIO driver resets and restarts, oldest first
+    asio_events: VecDeque<AsioEvent>,
+    /// Monitored seconds with the engine running and the signal present
+    healthy_secs: f64,
+    /// Total seconds since monitoring first started, including downtime
+    monitored_secs: f64,
+}
+
+/// On-disk form of the long-term history kept in a [`StatsStore`]
+#[derive(Serialize, Deserialize)]
+struct PersistedStats<'a> {
+    latency_archive: Cow<'a, VecDeque<Measurement>>,
+    loss_archive: Cow<'a, VecDeque<LossBucket>>,
+    latency_bucket_archive: Cow<'a, VecDeque<LatencyBucket>>,
+    disconnection_events: Cow<'a, [DisconnectionEvent]>,
+    loss_events: Cow<'a, VecDeque<LossEvent>>,
+    #[serde(default)]
+    healthy_secs: f64,
+    #[serde(default)]
+    monitored_secs: f64,
 }
 
 /// Running statistics calculated from measurements
@@ -117,6 +295,10 @@ pub struct RunningStats {
     pub max_latency: f64,
     /// Average latency (ms)
     pub avg_latency: f64,
+    /// Latency jitter: standard deviation over the last JITTER_WINDOW measurements (ms)
+    pub jitter_ms: f64,
+    /// Largest difference between two consecutive measurements since reset (ms)
+    pub max_jitter_ms: f64,
     /// Total samples lost
     pub total_lost: u64,
     /// Total samples corrupted
@@ -131,6 +313,8 @@ pub struct RunningStats {
     pub sample_rate: u32,
     /// Current buffer size (cached from engine)
     pub buffer_size: u32,
+    /// True when the device rejected the requested sample rate (cached from engine)
+    pub rate_fallback: bool,
     /// Total samples sent since reset
     pub samples_sent: u64,
     /// Total samples received since reset
@@ -143,18 +327,62 @@ pub struct RunningStats {
     pub estimated_loss: u64,
     /// True when ch1 counter signal is currently absent (muted loopback)
     pub counter_silent: bool,
+    /// True when ch1 counter is frozen on a constant value (repeating buffer)
+    pub counter_stuck: bool,
+    /// Peak input level per channel (linear 0.0-1.0), first few channels only
+    pub input_levels: Vec<f32>,
+    /// True when the current latency is unusual relative to the learned distribution
+    pub anomaly: bool,
+    /// True when the current latency is outside the configured expected range
+    pub latency_out_of_range: bool,
+    /// Driver-reported round-trip hardware latency (ms, 0 when unknown)
+    pub hardware_latency_ms: f64,
+    /// Estimated input/output clock drift (ppm, None until enough data)
+    pub clock_drift_ppm: Option<f64>,
+    /// How the current latency was measured
+    pub latency_method: LatencyMethod,
+}
+
+impl RunningStats {
+    /// Current latency in whole microseconds, for sub-millisecond links
+    pub fn current_latency_us(&self) -> u64 {
+        (self.current_latency * 1000.0).round().max(0.0) as u64
+    }
+
+    /// Measured latency in excess of the driver-reported hardware latency (ms)
+    ///
+    /// This is the extra latency added by routing/processing on the path.
+    /// Returns 0 when the hardware latency is unknown.
+    pub fn excess_latency_ms(&self) -> f64 {
+        if self.hardware_latency_ms > 0.0 {
+            (self.current_latency - self.hardware_latency_ms).max(0.0)
+        } else {
+            0.0
+        }
+    }
 }
 
 impl StatsStore {
     /// Create a new statistics store
     pub fn new() -> Self {
+        Self::with_config(DEFAULT_ARCHIVE_RATIO, LOSS_BUCKET_DURATION_SECS)
+    }
+
+    /// Create a store with custom archive resolution
+    ///
+    /// Every `archive_ratio`-th latency measurement is archived and loss and
+    /// latency are bucketed into `bucket_secs` buckets. Coarser values fit
+    /// more history into the fixed-size archives for week-long monitoring.
+    /// Both are clamped to at least 1.
+    pub fn with_config(archive_ratio: u64, bucket_secs: i64) -> Self {
         Self {
             latency_history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
             latency_archive: VecDeque::with_capacity(MAX_ARCHIVE_SIZE),
             loss_history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
             corruption_history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
+            confidence_history: VecDeque::with_capacity(MAX_HISTORY_SIZE),
             disconnection_events: Vec::new(),
-            loss_events: Vec::new(),
+            loss_events: VecDeque::new(),
             loss_archive: VecDeque::with_capacity(MAX_LOSS_ARCHIVE_SIZE),
             latency_bucket_archive: VecDeque::with_capacity(MAX_LATENCY_BUCKET_ARCHIVE_SIZE),
             max_size: MAX_HISTORY_SIZE,
@@ -164,6 +392,19 @@ impl StatsStore {
                 ..Default::default()
             },
             archive_counter: 0,
+            archive_ratio: archive_ratio.max(1),
+            bucket_secs: bucket_secs.max(1),
+            latency_histogram: vec![0; LATENCY_HISTOGRAM_BINS],
+            histogram_total: 0,
+            anomaly_events: VecDeque::new(),
+            spike_events: VecDeque::new(),
+            spike_factor: DEFAULT_SPIKE_FACTOR,
+            loss_coalesce_ms: DEFAULT_LOSS_COALESCE_MS,
+            asio_events: VecDeque::new(),
+            healthy_secs: 0.0,
+            monitored_secs: 0.0,
+            last_saved: None,
+            latency_deltas: VecDeque::with_capacity(JITTER_DELTA_WINDOW),
         }
     }
 
@@ -178,15 +419,41 @@ impl StatsStore {
             value: latency_ms,
         };
 
+        // Compare against the baseline before this measurement is included
+        if self.latency_history.len() >= SPIKE_MIN_SAMPLES {
+            let baseline_ms = self.stats.avg_latency;
+            let jitter = self.stats.jitter_ms.max(SPIKE_MIN_JITTER_MS);
+            if latency_ms > baseline_ms + self.spike_factor * jitter {
+                if self.spike_events.len() >= MAX_SPIKE_EVENTS {
+                    self.spike_events.pop_front();
+                }
+                self.spike_events.push_back(SpikeEvent {
+                    timestamp: now,
+                    latency_ms,
+                    baseline_ms,
+                });
+            }
+        }
+
+        // Delta to the previous measurement, for the jitter distribution
+        if let Some(prev) = self.latency_history.back() {
+            let delta = (latency_ms - prev.value).abs();
+            if self.latency_deltas.len() >= JITTER_DELTA_WINDOW {
+                self.latency_deltas.pop_front();
+            }
+            self.latency_deltas.push_back(delta);
+            self.stats.max_jitter_ms = self.stats.max_jitter_ms.max(delta);
+        }
+
         // Update history
         if self.latency_history.len() >= self.max_size {
             self.latency_history.pop_front();
         }
         self.latency_history.push_back(measurement.clone());
 
-        // Archive down-sampled data (every 10 measurements)
+        // Archive down-sampled data (every archive_ratio measurements)
         self.archive_counter += 1;
-        if self.archive_counter.is_multiple_of(10) {
+        if self.archive_counter.is_multiple_of(self.archive_ratio) {
             if self.latency_archive.len() >= self.max_archive_size {
                 self.latency_archive.pop_front();
             }
@@ -194,7 +461,7 @@ impl StatsStore {
         }
 
         // Aggregate into latency_bucket_archive (10s buckets)
-        let bucket_ts = Self::truncate_to_bucket(now);
+        let bucket_ts = self.truncate_to_bucket(now);
         if let Some(last) = self.latency_bucket_archive.back_mut() {
             if last.timestamp == bucket_ts {
                 // Same bucket — update running stats
@@ -229,6 +496,20 @@ impl StatsStore {
             });
         }
 
+        // Check against the learned distribution before adding this measurement
+        self.stats.anomaly = self.is_latency_anomalous(latency_ms);
+        if self.stats.anomaly {
+            if self.anomaly_events.len() >= MAX_ANOMALY_EVENTS {
+                self.anomaly_events.pop_front();
+            }
+            self.anomaly_events.push_back(Measurement {
+                timestamp: now,
+                value: latency_ms,
+            });
+        }
+        self.latency_histogram[Self::histogram_bin(latency_ms)] += 1;
+        self.histogram_total += 1;
+
         // Update running stats
         self.stats.current_latency = latency_ms;
         self.stats.min_latency = self.stats.min_latency.min(latency_ms);
@@ -238,6 +519,160 @@ impl StatsStore {
         // Recalculate average
         let sum: f64 = self.latency_history.iter().map(|m| m.value).sum();
         self.stats.avg_latency = sum / self.latency_history.len() as f64;
+
+        self.stats.jitter_ms = self.latency_jitter();
+    }
+
+    /// Histogram of absolute differences between consecutive measurements
+    ///
+    /// Returns `(bucket_start_ms, count)` for every bucket from 0 up to the
+    /// largest delta, so a stable link shows one tall bar near zero and a
+    /// glitchy one a long tail. At most [`MAX_JITTER_BUCKETS`] buckets are
+    /// returned; larger deltas are counted in the last. Empty when
+    /// `bucket_ms` is not positive or no deltas are recorded.
+    pub fn jitter_histogram(&self, bucket_ms: f64) -> Vec<(f64, u64)> {
+        if !bucket_ms.is_finite() || bucket_ms <= 0.0 || self.latency_deltas.is_empty() {
+            return Vec::new();
+        }
+        let bin = |delta: f64| ((delta / bucket_ms) as usize).min(MAX_JITTER_BUCKETS - 1);
+        let max_bin = self
+            .latency_deltas
+            .iter()
+            .map(|&d| bin(d))
+            .max()
+            .unwrap_or(0);
+
+        let mut counts = vec![0u64; max_bin + 1];
+        for &delta in &self.latency_deltas {
+            counts[bin(delta)] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (i as f64 * bucket_ms, count))
+            .collect()
+    }
+
+    /// Standard deviation of the last JITTER_WINDOW latency measurements
+    ///
+    /// Returns 0.0 with fewer than 2 measurements.
+    fn latency_jitter(&self) -> f64 {
+        let n = self.latency_history.len().min(JITTER_WINDOW);
+        if n < 2 {
+            return 0.0;
+        }
+        let window = || self.latency_history.iter().rev().take(n).map(|m| m.value);
+        let mean = window().sum::<f64>() / n as f64;
+        let variance = window().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+        variance.sqrt()
+    }
+
+    /// Average latency over the last `secs` seconds of recent history
+    ///
+    /// Like a load average, comparing short and long windows shows slow drift
+    /// that the lifetime average hides. Returns 0.0 when no measurement falls
+    /// in the window.
+    pub fn avg_latency_window(&self, secs: i64) -> f64 {
+        let cutoff = Utc::now() - chrono::Duration::seconds(secs);
+        let (sum, count) = self
+            .latency_history
+            .iter()
+            .rev()
+            .take_while(|m| m.timestamp >= cutoff)
+            .fold((0.0, 0usize), |(sum, count), m| (sum + m.value, count + 1));
+        if count == 0 {
+            0.0
+        } else {
+            sum / count as f64
+        }
+    }
+
+    /// Interpolated latency percentiles over the recent history
+    ///
+    /// `pcts` are in percent (0-100). Uses linear interpolation between the
+    /// closest ranks. Returns zeros when there is no history.
+    pub fn latency_percentiles(&self, pcts: &[f64]) -> Vec<f64> {
+        let mut values: Vec<f64> = self.latency_history.iter().map(|m| m.value).collect();
+        if values.is_empty() {
+            return vec![0.0; pcts.len()];
+        }
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        let last = (values.len() - 1) as f64;
+        pcts.iter()
+            .map(|p| {
+                let rank = (p.clamp(0.0, 100.0) / 100.0) * last;
+                let lo = rank.floor() as usize;
+                let hi = rank.ceil() as usize;
+                values[lo] + (values[hi] - values[lo]) * (rank - lo as f64)
+            })
+            .collect()
+    }
+
+    /// Histogram bin index for a latency value
+    fn histogram_bin(latency_ms: f64) -> usize {
+        ((latency_ms.max(0.0) / LATENCY_HISTOGRAM_BIN_MS) as usize).min(LATENCY_HISTOGRAM_BINS - 1)
+    }
+
+    /// Check whether a latency falls into an essentially empty part of the
+    /// learned distribution
+    ///
+    /// Adapts to each path's normal range instead of using a fixed threshold:
+    /// a jump to 40ms on a path that lives at 4ms is flagged, while a path that
+    /// normally sits at 40ms is not. Returns false until enough measurements
+    /// have been collected.
+    pub fn is_latency_anomalous(&self, latency_ms: f64) -> bool {
+        if self.histogram_total < ANOMALY_MIN_SAMPLES {
+            return false;
+        }
+        let bin = Self::histogram_bin(latency_ms);
+        let lo = bin.saturating_sub(1);
+        let hi = (bin + 1).min(LATENCY_HISTOGRAM_BINS - 1);
+        let nearby: u64 = self.latency_histogram[lo..=hi].iter().sum();
+        (nearby as f64 / self.histogram_total as f64) < ANOMALY_MAX_FRACTION
+    }
+
+    /// Get the learned latency histogram as (bin start ms, count) pairs
+    pub fn latency_histogram(&self) -> Vec<(f64, u64)> {
+        self.latency_histogram
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| (i as f64 * LATENCY_HISTOGRAM_BIN_MS, count))
+            .collect()
+    }
+
+    /// Get measurements flagged as latency anomalies
+    pub fn anomaly_events(&self) -> &VecDeque<Measurement> {
+        &self.anomaly_events
+    }
+
+    /// Set how many jitter standard deviations above the average make a
+    /// measurement a spike (default [`DEFAULT_SPIKE_FACTOR`])
+    ///
+    /// Non-positive or non-finite factors are ignored.
+    pub fn set_spike_factor(&mut self, factor: f64) {
+        if factor.is_finite() && factor > 0.0 {
+            self.spike_factor = factor;
+        }
+    }
+
+    /// Get recorded latency spikes, oldest first
+    pub fn spike_events(&self) -> &VecDeque<SpikeEvent> {
+        &self.spike_events
+    }
+
+    /// Set the window within which consecutive losses merge into one loss
+    /// event (default [`DEFAULT_LOSS_COALESCE_MS`], 0 keeps every loss separate)
+    ///
+    /// Only the event list is coalesced; totals, history and archive buckets
+    /// still count every loss.
+    pub fn set_loss_coalesce_ms(&mut self, ms: u64) {
+        self.loss_coalesce_ms = ms;
+    }
+
+    /// Get the loss event coalescing window (ms)
+    pub fn loss_coalesce_ms(&self) -> u64 {
+        self.loss_coalesce_ms
     }
 
     /// Record sample loss
@@ -245,9 +680,20 @@ impl StatsStore {
     /// # Arguments
     /// * `count` - Number of samples lost
     pub fn record_loss(&mut self, count: u64) {
-        let now = Utc::now();
+        self.record_loss_at(count, Utc::now());
+    }
+
+    /// Record sample loss that happened at `timestamp`
+    ///
+    /// The engine stamps loss with the approximate capture time of the gap,
+    /// which precedes the analysis by up to the ring buffer depth.
+    ///
+    /// # Arguments
+    /// * `count` - Number of samples lost
+    /// * `timestamp` - When the first sample was lost
+    pub fn record_loss_at(&mut self, count: u64, timestamp: DateTime<Utc>) {
         let measurement = Measurement {
-            timestamp: now,
+            timestamp,
             value: count as f64,
         };
 
@@ -256,17 +702,25 @@ impl StatsStore {
         }
         self.loss_history.push_back(measurement);
 
-        // Record as a loss event
-        self.loss_events.push(LossEvent {
-            timestamp: now,
-            count,
-        });
+        // Record as a loss event, merged into the last one when close to it
+        let window = chrono::Duration::milliseconds(self.loss_coalesce_ms as i64);
+        match self.loss_events.back_mut() {
+            Some(last) if self.loss_coalesce_ms > 0 && timestamp - last.timestamp <= window => {
+                last.count += count;
+            }
+            _ => {
+                if self.loss_events.len() >= MAX_LOSS_EVENTS {
+                    self.loss_events.pop_front();
+                }
+                self.loss_events.push_back(LossEvent { timestamp, count });
+            }
+        }
 
         // Aggregate into loss_archive bucket
-        let bucket_ts = Self::truncate_to_bucket(now);
+        let bucket_ts = self.truncate_to_bucket(timestamp);
         if let Some(last) = self.loss_archive.back_mut() {
-            if last.timestamp == bucket_ts {
-                // Same bucket — aggregate
+            if last.timestamp >= bucket_ts {
+                // Same bucket (or a backdated loss from the previous one) — aggregate
                 last.total_loss += count;
                 last.event_count += 1;
             } else {
@@ -325,6 +779,11 @@ impl StatsStore {
         &self.corruption_history
     }
 
+    /// Get confidence history
+    pub fn confidence_history(&self) -> &VecDeque<Measurement> {
+        &self.confidence_history
+    }
+
     /// Get running statistics
     pub fn stats(&self) -> &RunningStats {
         &self.stats
@@ -336,11 +795,20 @@ impl StatsStore {
         self.latency_archive.clear();
         self.loss_history.clear();
         self.corruption_history.clear();
+        self.confidence_history.clear();
         self.disconnection_events.clear();
         self.loss_events.clear();
         self.loss_archive.clear();
         self.latency_bucket_archive.clear();
         self.archive_counter = 0;
+        self.latency_histogram = vec![0; LATENCY_HISTOGRAM_BINS];
+        self.histogram_total = 0;
+        self.anomaly_events.clear();
+        self.spike_events.clear();
+        self.latency_deltas.clear();
+        self.asio_events.clear();
+        self.healthy_secs = 0.0;
+        self.monitored_secs = 0.0;
         self.stats = RunningStats {
             min_latency: f64::MAX,
             ..Default::default()
@@ -352,16 +820,7 @@ impl StatsStore {
     /// # Returns
     /// Vector of (time_offset_seconds, latency_ms) pairs
     pub fn latency_plot_data(&self, count: usize) -> Vec<(f64, f64)> {
-        let now = Utc::now();
-        self.latency_history
-            .iter()
-            .rev()
-            .take(count)
-            .map(|m| {
-                let time_offset = (now - m.timestamp).num_milliseconds() as f64 / 1000.0;
-                (-time_offset, m.value)
-            })
-            .collect()
+        Self::plot_data(&self.latency_history, count)
     }
 
     /// Get loss values for plotting (last N points)
@@ -369,8 +828,21 @@ impl StatsStore {
     /// # Returns
     /// Vector of (time_offset_seconds, loss_count) pairs
     pub fn loss_plot_data(&self, count: usize) -> Vec<(f64, f64)> {
+        Self::plot_data(&self.loss_history, count)
+    }
+
+    /// Get confidence values for plotting (last N points)
+    ///
+    /// # Returns
+    /// Vector of (time_offset_seconds, confidence) pairs
+    pub fn confidence_plot_data(&self, count: usize) -> Vec<(f64, f64)> {
+        Self::plot_data(&self.confidence_history, count)
+    }
+
+    /// Last `count` measurements of a history as (seconds before now, value), newest first
+    fn plot_data(history: &VecDeque<Measurement>, count: usize) -> Vec<(f64, f64)> {
         let now = Utc::now();
-        self.loss_history
+        history
             .iter()
             .rev()
             .take(count)
@@ -397,23 +869,31 @@ impl StatsStore {
         self.stats.samples_received = 0;
         self.stats.estimated_loss = 0;
         self.stats.counter_silent = false;
+        // Relearn the distribution (the path may have changed)
+        self.latency_histogram = vec![0; LATENCY_HISTOGRAM_BINS];
+        self.histogram_total = 0;
+        self.stats.anomaly = false;
+        self.latency_deltas.clear();
+        self.stats.max_jitter_ms = 0.0;
+        self.healthy_secs = 0.0;
+        self.monitored_secs = 0.0;
     }
 
-    /// Truncate a timestamp to the nearest LOSS_BUCKET_DURATION_SECS boundary
-    fn truncate_to_bucket(ts: DateTime<Utc>) -> DateTime<Utc> {
+    /// Truncate a timestamp to the nearest bucket boundary
+    fn truncate_to_bucket(&self, ts: DateTime<Utc>) -> DateTime<Utc> {
         let secs = ts.timestamp();
-        let truncated = secs - (secs % LOSS_BUCKET_DURATION_SECS);
+        let truncated = secs - (secs % self.bucket_secs);
         DateTime::from_timestamp(truncated, 0).unwrap_or(ts)
     }
 
     /// Called every 10 seconds from the monitoring loop.
     ///
     /// Ensures continuous timeline coverage by appending a zero-loss bucket
-    /// when the most recent bucket is older than LOSS_BUCKET_DURATION_SECS.
+    /// when the most recent bucket is older than the bucket duration.
     /// This lets the chart display the full monitored timespan with empty gaps.
     pub fn loss_archive_tick(&mut self) {
         let now = Utc::now();
-        let bucket_ts = Self::truncate_to_bucket(now);
+        let bucket_ts = self.truncate_to_bucket(now);
 
         let should_push = match self.loss_archive.back() {
             Some(last) => last.timestamp < bucket_ts,
@@ -436,7 +916,7 @@ impl StatsStore {
     ///
     /// # Arguments
     /// * `range_secs` - How far back to look (e.g. 3600 for 1h, 86400 for 24h)
-    /// * `bucket_size_secs` - Desired output bucket size in seconds (must be >= 10)
+    /// * `bucket_size_secs` - Desired output bucket size in seconds (at least the store's bucket duration)
     ///
     /// # Returns
     /// Vector of (unix_timestamp, total_loss, event_count) tuples, sorted by time
@@ -447,7 +927,7 @@ impl StatsStore {
     ) -> Vec<(i64, u64, u32)> {
         let now = Utc::now();
         let cutoff = now - chrono::Duration::seconds(range_secs);
-        let bucket_size = bucket_size_secs.max(LOSS_BUCKET_DURATION_SECS);
+        let bucket_size = bucket_size_secs.max(self.bucket_secs);
 
         // Filter to range
         let in_range: Vec<&LossBucket> = self
@@ -484,7 +964,7 @@ impl StatsStore {
     ///
     /// # Arguments
     /// * `range_secs` - How far back to look (e.g. 3600 for 1h, 1209600 for 14d)
-    /// * `bucket_size_secs` - Desired output bucket size in seconds (must be >= 10)
+    /// * `bucket_size_secs` - Desired output bucket size in seconds (at least the store's bucket duration)
     ///
     /// # Returns
     /// Vector of (unix_timestamp, avg_latency, min_latency, max_latency) tuples, sorted by time
@@ -495,7 +975,7 @@ impl StatsStore {
     ) -> Vec<(i64, f64, f64, f64)> {
         let now = Utc::now();
         let cutoff = now - chrono::Duration::seconds(range_secs);
-        let bucket_size = bucket_size_secs.max(LOSS_BUCKET_DURATION_SECS);
+        let bucket_size = bucket_size_secs.max(self.bucket_secs);
 
         // Filter to range
         let in_range: Vec<&LatencyBucket> = self
@@ -557,16 +1037,111 @@ impl StatsStore {
         });
     }
 
+    /// Record an ASIO driver event for the timeline
+    pub fn record_asio_event(&mut self, kind: AsioEventKind) {
+        if self.asio_events.len() >= MAX_ASIO_EVENTS {
+            self.asio_events.pop_front();
+        }
+        self.asio_events.push_back(AsioEvent {
+            timestamp: Utc::now(),
+            kind,
+        });
+    }
+
+    /// Count `elapsed` wall-clock time towards availability
+    ///
+    /// Called by the monitoring loop on every pass with the measured time
+    /// since the previous pass. Nothing is counted until the engine first
+    /// runs; after that, time spent stopped counts as downtime, and running
+    /// time is healthy unless the signal is lost.
+    pub fn tick_availability(&mut self, elapsed: Duration, running: bool) {
+        if !running && self.monitored_secs == 0.0 {
+            return;
+        }
+        let secs = elapsed.as_secs_f64();
+        self.monitored_secs += secs;
+        if running && !self.stats.signal_lost {
+            self.healthy_secs += secs;
+        }
+    }
+
+    /// Share of monitored time with the signal present (None before any monitoring)
+    pub fn availability_percent(&self) -> Option<f64> {
+        (self.monitored_secs > 0.0).then(|| self.healthy_secs / self.monitored_secs * 100.0)
+    }
+
+    /// Summarize the session: monitored time, latency range and losses
+    pub fn session_summary(&self) -> SessionSummary {
+        let stats = &self.stats;
+        SessionSummary {
+            duration_secs: self.monitored_secs.round() as u64,
+            min_latency_ms: if stats.measurement_count > 0 {
+                stats.min_latency
+            } else {
+                0.0
+            },
+            avg_latency_ms: stats.avg_latency,
+            max_latency_ms: stats.max_latency,
+            p95_latency_ms: self.latency_percentiles(&[95.0])[0],
+            total_lost: stats.total_lost,
+            total_corrupted: stats.total_corrupted,
+            disconnection_count: self.disconnection_events.len(),
+            availability_percent: self.availability_percent(),
+        }
+    }
+
+    /// Take the current latency/loss profile as a [`Baseline`]
+    ///
+    /// The loss rate is averaged over the monitored time (0 before any
+    /// monitoring).
+    pub fn baseline(&self) -> Baseline {
+        let minutes = self.monitored_secs / 60.0;
+        Baseline {
+            saved_at: Utc::now(),
+            avg_latency_ms: self.stats.avg_latency,
+            p95_latency_ms: self.latency_percentiles(&[95.0])[0],
+            max_latency_ms: self.stats.max_latency,
+            jitter_ms: self.stats.jitter_ms,
+            loss_rate_per_min: if minutes > 0.0 {
+                self.stats.total_lost as f64 / minutes
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Get ASIO driver events, oldest first
+    pub fn asio_events(&self) -> &VecDeque<AsioEvent> {
+        &self.asio_events
+    }
+
     /// Get disconnection events
     pub fn disconnection_events(&self) -> &[DisconnectionEvent] {
         &self.disconnection_events
     }
 
-    /// Get loss events
-    pub fn loss_events(&self) -> &[LossEvent] {
+    /// Get loss events, oldest first
+    pub fn loss_events(&self) -> &VecDeque<LossEvent> {
         &self.loss_events
     }
 
+    /// Samples lost per minute over the last 60 seconds
+    ///
+    /// The window is shortened to the uptime early in a session so the
+    /// rate is not diluted by time that was never monitored.
+    pub fn loss_rate_per_min(&self) -> f64 {
+        let cutoff = Utc::now() - chrono::Duration::seconds(LOSS_RATE_WINDOW_SECS as i64);
+        let recent: u64 = self
+            .loss_events
+            .iter()
+            .rev()
+            .take_while(|e| e.timestamp >= cutoff)
+            .map(|e| e.count)
+            .sum();
+        let window_secs = self.stats.uptime_seconds.clamp(1, LOSS_RATE_WINDOW_SECS);
+        recent as f64 * 60.0 / window_secs as f64
+    }
+
     /// Get latency archive for extended history
     pub fn latency_archive(&self) -> &VecDeque<Measurement> {
         &self.latency_archive
@@ -610,6 +1185,32 @@ impl StatsStore {
         data
     }
 
+    /// Get the whole retained latency series reduced to at most `target_points`
+    ///
+    /// Combines the archive (up to where the recent history starts) with the
+    /// full-resolution history and applies Largest-Triangle-Three-Buckets, which
+    /// keeps visual peaks while cutting the point count. The first and last
+    /// points are always kept.
+    ///
+    /// # Returns
+    /// Vector of (time_offset_seconds, latency_ms) pairs, oldest first
+    pub fn latency_downsampled(&self, target_points: usize) -> Vec<(f64, f64)> {
+        let now = Utc::now();
+        let history_start = self.latency_history.front().map(|m| m.timestamp);
+        let series: Vec<(f64, f64)> = self
+            .latency_archive
+            .iter()
+            .filter(|m| history_start.is_none_or(|start| m.timestamp < start))
+            .chain(self.latency_history.iter())
+            .map(|m| {
+                let time_offset = (now - m.timestamp).num_milliseconds() as f64 / 1000.0;
+                (-time_offset, m.value)
+            })
+            .collect();
+
+        lttb(&series, target_points)
+    }
+
     /// Set uptime seconds
     pub fn set_uptime(&mut self, seconds: u64) {
         self.stats.uptime_seconds = seconds;
@@ -672,6 +1273,23 @@ impl StatsStore {
         self.stats.last_confidence = confidence;
     }
 
+    /// Record a measurement's confidence into the history and set it as the last value
+    pub fn record_confidence(&mut self, confidence: f32) {
+        self.set_confidence(confidence);
+        if self.confidence_history.len() >= self.max_size {
+            self.confidence_history.pop_front();
+        }
+        self.confidence_history.push_back(Measurement {
+            timestamp: Utc::now(),
+            value: confidence as f64,
+        });
+    }
+
+    /// Set how the current latency was measured
+    pub fn set_latency_method(&mut self, method: LatencyMethod) {
+        self.stats.latency_method = method;
+    }
+
     /// Get last confidence value
     pub fn confidence(&self) -> f32 {
         self.stats.last_confidence
@@ -682,11 +1300,50 @@ impl StatsStore {
         self.stats.counter_silent = silent;
     }
 
+    /// Set counter stuck state (frozen audio buffer)
+    pub fn set_counter_stuck(&mut self, stuck: bool) {
+        self.stats.counter_stuck = stuck;
+    }
+
+    /// Set whether the current latency is outside the expected range
+    pub fn set_latency_out_of_range(&mut self, out_of_range: bool) {
+        self.stats.latency_out_of_range = out_of_range;
+    }
+
+    /// Account for samples that cannot be verified while the counter is silent
+    ///
+    /// Marks the counter silent and sets the estimate to everything sent since
+    /// the silence began (`sample_rate * silent_for`).
+    pub fn estimate_silent_loss(&mut self, silent_for: std::time::Duration, sample_rate: u32) {
+        self.stats.counter_silent = true;
+        self.stats.estimated_loss = (silent_for.as_secs_f64() * sample_rate as f64) as u64;
+    }
+
     /// Set estimated loss during counter silence
     pub fn set_estimated_loss(&mut self, estimated: u64) {
         self.stats.estimated_loss = estimated;
     }
 
+    /// Set whether the engine fell back from the requested sample rate
+    pub fn set_rate_fallback(&mut self, fallback: bool) {
+        self.stats.rate_fallback = fallback;
+    }
+
+    /// Set driver-reported round-trip hardware latency (cached from engine)
+    pub fn set_hardware_latency(&mut self, latency_ms: f64) {
+        self.stats.hardware_latency_ms = latency_ms;
+    }
+
+    /// Set the estimated input/output clock drift (cached from engine)
+    pub fn set_clock_drift(&mut self, ppm: Option<f64>) {
+        self.stats.clock_drift_ppm = ppm;
+    }
+
+    /// Set per-channel input peak levels (cached from engine)
+    pub fn set_input_levels(&mut self, levels: Vec<f32>) {
+        self.stats.input_levels = levels;
+    }
+
     /// Reset estimated loss (called on recovery from silence or engine restart)
     pub fn reset_estimated_loss(&mut self) {
         self.stats.estimated_loss = 0;
@@ -694,6 +1351,144 @@ impl StatsStore {
     }
 }
 
+impl StatsStore {
+    /// Write the long-term history (archives and events) to `path` as JSON
+    ///
+    /// Holds `&mut self` for the file I/O; callers sharing the store behind a
+    /// lock should use [`StatsStore::persisted_json`] and
+    /// [`StatsStore::write_persisted`] instead, so readers are not blocked.
+    pub fn save_to_path(&mut self, path: &Path) -> anyhow::Result<()> {
+        let json = self.persisted_json()?;
+        Self::write_persisted(&json, path)?;
+        self.mark_saved();
+        Ok(())
+    }
+
+    /// Serialize the long-term history (archives and events) as JSON
+    pub fn persisted_json(&self) -> anyhow::Result<Vec<u8>> {
+        let persisted = PersistedStats {
+            latency_archive: Cow::Borrowed(&self.latency_archive),
+            loss_archive: Cow::Borrowed(&self.loss_archive),
+            latency_bucket_archive: Cow::Borrowed(&self.latency_bucket_archive),
+            disconnection_events: Cow::Borrowed(&self.disconnection_events[..]),
+            loss_events: Cow::Borrowed(&self.loss_events),
+            healthy_secs: self.healthy_secs,
+            monitored_secs: self.monitored_secs,
+        };
+        serde_json::to_vec(&persisted).context("Failed to serialize stats")
+    }
+
+    /// Write JSON from [`StatsStore::persisted_json`] to `path`
+    ///
+    /// The file is written to a temporary sibling first and renamed into
+    /// place, so an interrupted save never leaves a truncated file behind.
+    pub fn write_persisted(json: &[u8], path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Record that the history was just saved (see [`StatsStore::last_saved`])
+    pub fn mark_saved(&mut self) {
+        self.last_saved = Some(Utc::now());
+    }
+
+    /// Load a store previously written by [`StatsStore::save_to_path`]
+    ///
+    /// Only the long-term history is restored; running statistics start fresh.
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+        let data =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let persisted: PersistedStats =
+            serde_json::from_slice(&data).context("Failed to parse stats file")?;
+
+        let mut store = Self::new();
+        store.latency_archive = persisted.latency_archive.into_owned();
+        store.loss_archive = persisted.loss_archive.into_owned();
+        store.latency_bucket_archive = persisted.latency_bucket_archive.into_owned();
+        store.disconnection_events = persisted.disconnection_events.into_owned();
+        store.loss_events = persisted.loss_events.into_owned();
+        while store.loss_events.len() > MAX_LOSS_EVENTS {
+            store.loss_events.pop_front();
+        }
+        store.healthy_secs = persisted.healthy_secs;
+        store.monitored_secs = persisted.monitored_secs;
+
+        while store.latency_archive.len() > store.max_archive_size {
+            store.latency_archive.pop_front();
+        }
+        while store.loss_archive.len() > MAX_LOSS_ARCHIVE_SIZE {
+            store.loss_archive.pop_front();
+        }
+        while store.latency_bucket_archive.len() > MAX_LATENCY_BUCKET_ARCHIVE_SIZE {
+            store.latency_bucket_archive.pop_front();
+        }
+        Ok(store)
+    }
+
+    /// When the store was last saved to disk, if ever
+    pub fn last_saved(&self) -> Option<DateTime<Utc>> {
+        self.last_saved
+    }
+}
+
+/// Largest-Triangle-Three-Buckets downsampling of an x-ordered series
+///
+/// Keeps the first and last points and, from each bucket in between, the
+/// point forming the largest triangle with the previously kept point and the
+/// average of the next bucket.
+fn lttb(data: &[(f64, f64)], threshold: usize) -> Vec<(f64, f64)> {
+    let n = data.len();
+    if threshold >= n {
+        return data.to_vec();
+    }
+    if threshold < 3 {
+        let mut ends = vec![data[0], data[n - 1]];
+        ends.truncate(threshold);
+        return ends;
+    }
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(data[0]);
+
+    // Interior points are split into threshold - 2 buckets
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+    let mut a = 0;
+    for i in 0..threshold - 2 {
+        // Average of the next bucket (the last point for the final bucket)
+        let avg_start = ((i + 1) as f64 * every) as usize + 1;
+        let avg_end = (((i + 2) as f64 * every) as usize + 1).min(n);
+        let next = &data[avg_start..avg_end];
+        let avg_x = next.iter().map(|p| p.0).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.1).sum::<f64>() / next.len() as f64;
+
+        let range_start = (i as f64 * every) as usize + 1;
+        let range_end = avg_start;
+        let (ax, ay) = data[a];
+        let mut max_area = -1.0;
+        let mut chosen = range_start;
+        for (j, &(x, y)) in data.iter().enumerate().take(range_end).skip(range_start) {
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            if area > max_area {
+                max_area = area;
+                chosen = j;
+            }
+        }
+
+        sampled.push(data[chosen]);
+        a = chosen;
+    }
+
+    sampled.push(data[n - 1]);
+    sampled
+}
+
 impl Default for StatsStore {
     fn default() -> Self {
         Self::new()
@@ -724,6 +1519,10 @@ mod tests {
         assert_eq!(store.stats().min_latency, 5.0);
         assert_eq!(store.stats().max_latency, 10.0);
         assert_eq!(store.stats().avg_latency, 7.5);
+
+        // 50 samples at 192kHz
+        store.record_latency(50.0 / 192.0);
+        assert_eq!(store.stats().current_latency_us(), 260);
     }
 
     #[test]
@@ -737,6 +1536,74 @@ mod tests {
         assert_eq!(store.stats().total_lost, 15);
     }
 
+    #[test]
+    fn test_record_loss_at_keeps_capture_time() {
+        let mut store = StatsStore::new();
+        let captured = Utc::now() - chrono::Duration::milliseconds(150);
+
+        store.record_loss_at(4, captured);
+        assert_eq!(store.loss_events()[0].timestamp, captured);
+        assert_eq!(store.loss_archive.len(), 1);
+        assert_eq!(store.stats().total_lost, 4);
+    }
+
+    #[test]
+    fn test_latency_anomaly_outlier() {
+        let mut store = StatsStore::new();
+
+        // Stable path around 4ms
+        for i in 0..500 {
+            store.record_latency(4.0 + (i % 5) as f64 * 0.1);
+            assert!(!store.stats().anomaly, "Stable measurement flagged");
+        }
+
+        // Sudden jump into the 40ms band
+        store.record_latency(42.0);
+        assert!(store.stats().anomaly);
+        assert_eq!(store.anomaly_events().len(), 1);
+
+        // Back to normal
+        store.record_latency(4.2);
+        assert!(!store.stats().anomaly);
+    }
+
+    #[test]
+    fn test_latency_spike_event() {
+        let mut store = StatsStore::new();
+        for i in 0..50 {
+            store.record_latency(if i % 2 == 0 { 5.0 } else { 5.2 });
+        }
+        assert!(store.spike_events().is_empty());
+
+        store.record_latency(25.0);
+        for i in 0..20 {
+            store.record_latency(if i % 2 == 0 { 5.0 } else { 5.2 });
+        }
+
+        assert_eq!(store.spike_events().len(), 1);
+        let spike = &store.spike_events()[0];
+        assert_eq!(spike.latency_ms, 25.0);
+        assert!((spike.baseline_ms - 5.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_latency_anomaly_needs_history() {
+        let mut store = StatsStore::new();
+        store.record_latency(4.0);
+        store.record_latency(40.0);
+        assert!(!store.stats().anomaly);
+    }
+
+    #[test]
+    fn test_excess_latency() {
+        let mut store = StatsStore::new();
+        store.record_latency(4.0);
+        assert_eq!(store.stats().excess_latency_ms(), 0.0);
+
+        store.set_hardware_latency(2.7);
+        assert!((store.stats().excess_latency_ms() - 1.3).abs() < 1e-9);
+    }
+
     #[test]
     fn test_clear() {
         let mut store = StatsStore::new();
@@ -762,6 +1629,57 @@ mod tests {
         assert_eq!(store.latency_history().len(), MAX_HISTORY_SIZE);
     }
 
+    #[test]
+    fn test_confidence_history() {
+        let mut store = StatsStore::new();
+        for i in 0..MAX_HISTORY_SIZE + 10 {
+            store.record_confidence(if i % 2 == 0 { 0.9 } else { 0.25 });
+        }
+        assert_eq!(store.confidence_history().len(), MAX_HISTORY_SIZE);
+        assert_eq!(store.confidence(), 0.25);
+
+        // Newest first, at or before now, limited to the requested count
+        let plot = store.confidence_plot_data(3);
+        assert_eq!(plot.len(), 3);
+        assert_eq!(plot[0].1, 0.25);
+        assert!((plot[1].1 - 0.9).abs() < 1e-6);
+        assert!(plot.iter().all(|&(t, _)| t <= 0.0));
+
+        store.clear();
+        assert!(store.confidence_plot_data(300).is_empty());
+    }
+
+    #[test]
+    fn test_archive_ratio() {
+        let mut store = StatsStore::with_config(60, LOSS_BUCKET_DURATION_SECS);
+        for i in 1..=180 {
+            store.record_latency(i as f64);
+        }
+        let archived: Vec<f64> = store.latency_archive().iter().map(|m| m.value).collect();
+        assert_eq!(archived, [60.0, 120.0, 180.0]);
+
+        // Default keeps every 10th measurement
+        let mut store = StatsStore::new();
+        for i in 1..=180 {
+            store.record_latency(i as f64);
+        }
+        assert_eq!(store.latency_archive().len(), 18);
+    }
+
+    #[test]
+    fn test_loss_timeline_honors_store_bucket() {
+        let mut store = StatsStore::with_config(DEFAULT_ARCHIVE_RATIO, 60);
+        store.record_loss(5);
+        store.record_loss(7);
+
+        // A finer requested bucket is widened to the store's 60s buckets
+        let timeline = store.loss_timeline_data(3600, 10);
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].0 % 60, 0);
+        assert_eq!(timeline[0].1, 12);
+        assert_eq!(timeline[0].2, 2);
+    }
+
     #[test]
     fn test_set_sample_counters() {
         let mut store = StatsStore::new();
@@ -780,4 +1698,392 @@ mod tests {
         assert_eq!(store.samples_sent(), 2500);
         assert_eq!(store.samples_received(), 2490);
     }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+
+        let mut store = StatsStore::new();
+        for _ in 0..10 {
+            store.record_latency(5.0);
+        }
+        store.record_loss(42);
+        store.loss_archive_tick();
+        store.record_disconnection(1500, true);
+        store.tick_availability(Duration::from_secs(3), true);
+        store.set_signal_lost(true);
+        store.tick_availability(Duration::from_secs(1), true);
+        assert!(store.last_saved().is_none());
+
+        store.save_to_path(&path).unwrap();
+        assert!(store.last_saved().is_some());
+
+        let loaded = StatsStore::load_from_path(&path).unwrap();
+        assert_eq!(
+            loaded.latency_archive().len(),
+            store.latency_archive().len()
+        );
+        assert_eq!(loaded.loss_events().len(), 1);
+        assert_eq!(loaded.loss_events()[0].count, 42);
+        assert_eq!(loaded.disconnection_events().len(), 1);
+        assert_eq!(loaded.disconnection_events()[0].duration_ms, 1500);
+        assert_eq!(loaded.stats().measurement_count, 0);
+        assert_eq!(loaded.availability_percent(), Some(75.0));
+    }
+
+    #[test]
+    fn test_availability_percent() {
+        let mut store = StatsStore::new();
+        assert_eq!(store.availability_percent(), None);
+
+        // Stopped before monitoring ever started: not counted
+        store.tick_availability(Duration::from_secs(30), false);
+        assert_eq!(store.availability_percent(), None);
+
+        // Alternate healthy and lost seconds: 6 healthy out of 10
+        for tick in 0..10 {
+            store.set_signal_lost(tick % 5 >= 3);
+            store.tick_availability(Duration::from_secs(1), true);
+        }
+        assert_eq!(store.availability_percent(), Some(60.0));
+
+        store.set_signal_lost(false);
+        store.tick_availability(Duration::from_secs(10), true);
+        assert_eq!(store.availability_percent(), Some(80.0));
+
+        // Engine stopped after monitoring started: downtime
+        store.tick_availability(Duration::from_secs(5), false);
+        assert_eq!(store.availability_percent(), Some(64.0));
+
+        // Uneven pass lengths are counted as measured
+        store.tick_availability(Duration::from_millis(2500), true);
+        store.tick_availability(Duration::from_millis(2500), true);
+        assert_eq!(store.availability_percent(), Some(70.0));
+
+        store.reset_counters();
+        assert_eq!(store.availability_percent(), None);
+    }
+
+    #[test]
+    fn test_session_summary() {
+        let mut store = StatsStore::new();
+        assert_eq!(store.session_summary().min_latency_ms, 0.0);
+
+        for i in 0..=100 {
+            store.record_latency(5.0 + i as f64 * 0.01);
+        }
+        store.record_loss(12);
+        store.record_corruption(3);
+        store.record_disconnection(1500, true);
+        for tick in 0..9 {
+            store.set_signal_lost(tick == 0);
+            store.tick_availability(Duration::from_secs(1), true);
+        }
+        // Stopped for a second before the summary: still part of the session
+        store.tick_availability(Duration::from_millis(1000), false);
+
+        let summary = store.session_summary();
+        assert_eq!(summary.duration_secs, 10);
+        assert!((summary.min_latency_ms - 5.0).abs() < 1e-9);
+        assert!((summary.avg_latency_ms - 5.5).abs() < 1e-9);
+        assert!((summary.max_latency_ms - 6.0).abs() < 1e-9);
+        assert!((summary.p95_latency_ms - 5.95).abs() < 1e-9);
+        assert_eq!(summary.total_lost, 12);
+        assert_eq!(summary.total_corrupted, 3);
+        assert_eq!(summary.disconnection_count, 1);
+        assert_eq!(summary.availability_percent, Some(80.0));
+        assert!(summary.to_string().contains("lost 12"));
+    }
+
+    #[test]
+    fn test_load_corrupt_file_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+
+        std::fs::write(&path, b"not json at all").unwrap();
+        assert!(StatsStore::load_from_path(&path).is_err());
+
+        // Partial write: valid prefix of a real save, cut off mid-way
+        let mut store = StatsStore::new();
+        store.record_loss(7);
+        store.record_disconnection(100, false);
+        store.save_to_path(&path).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &data[..data.len() / 2]).unwrap();
+        assert!(StatsStore::load_from_path(&path).is_err());
+    }
+
+    #[test]
+    fn test_save_leaves_no_temp_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats.json");
+        StatsStore::new().save_to_path(&path).unwrap();
+        assert!(path.exists());
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_load_missing_file_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(StatsStore::load_from_path(&dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_record_asio_events_in_order() {
+        let mut store = StatsStore::new();
+        store.record_asio_event(AsioEventKind::Reset);
+        store.record_asio_event(AsioEventKind::VirtualRestart);
+        store.record_asio_event(AsioEventKind::Reset);
+
+        let kinds: Vec<AsioEventKind> = store.asio_events().iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AsioEventKind::Reset,
+                AsioEventKind::VirtualRestart,
+                AsioEventKind::Reset
+            ]
+        );
+        assert!(store
+            .asio_events()
+            .iter()
+            .zip(store.asio_events().iter().skip(1))
+            .all(|(a, b)| a.timestamp <= b.timestamp));
+
+        store.clear();
+        assert!(store.asio_events().is_empty());
+    }
+
+    #[test]
+    fn test_jitter_std_dev() {
+        let mut store = StatsStore::new();
+        assert_eq!(store.stats().jitter_ms, 0.0);
+
+        // Fewer than 2 samples: no jitter
+        store.record_latency(5.0);
+        assert_eq!(store.stats().jitter_ms, 0.0);
+
+        // 2, 4, 4, 4, 5, 5, 7, 9: mean 5, population std-dev exactly 2
+        let mut store = StatsStore::new();
+        for v in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            store.record_latency(v);
+        }
+        assert!((store.stats().jitter_ms - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_jitter_uses_recent_window() {
+        let mut store = StatsStore::new();
+        for i in 0..100 {
+            store.record_latency(i as f64);
+        }
+        // A full window of constant values pushes the ramp out entirely
+        for _ in 0..JITTER_WINDOW {
+            store.record_latency(5.0);
+        }
+        assert_eq!(store.stats().jitter_ms, 0.0);
+    }
+
+    #[test]
+    fn test_latency_downsampled_keeps_ends_and_peaks() {
+        let mut store = StatsStore::new();
+        assert!(store.latency_downsampled(500).is_empty());
+
+        // One sample per second with a single spike in an otherwise flat series
+        let start = Utc::now() - chrono::Duration::seconds(2000);
+        for i in 0..2000 {
+            store.latency_history.push_back(Measurement {
+                timestamp: start + chrono::Duration::seconds(i),
+                value: if i == 1234 {
+                    50.0
+                } else {
+                    5.0 + i as f64 * 1e-4
+                },
+            });
+        }
+        let full = store.latency_downsampled(usize::MAX);
+        assert_eq!(full.len(), 2000);
+
+        let reduced = store.latency_downsampled(500);
+        assert!(reduced.len() <= 500);
+        assert_eq!(reduced.first().unwrap().1, full.first().unwrap().1);
+        assert_eq!(reduced.last().unwrap().1, full.last().unwrap().1);
+        assert!(reduced.iter().any(|&(_, v)| v == 50.0));
+        assert!(reduced.windows(2).all(|w| w[0].0 <= w[1].0));
+
+        assert_eq!(store.latency_downsampled(2).len(), 2);
+        assert!(store.latency_downsampled(0).is_empty());
+    }
+
+    #[test]
+    fn test_estimated_loss_during_silence() {
+        let mut store = StatsStore::new();
+        store.estimate_silent_loss(std::time::Duration::from_millis(500), 48000);
+        assert!(store.stats().counter_silent);
+        assert_eq!(store.stats().estimated_loss, 24000);
+
+        // Grows with the silence duration
+        store.estimate_silent_loss(std::time::Duration::from_secs(2), 48000);
+        assert_eq!(store.stats().estimated_loss, 96000);
+        assert_eq!(
+            store.stats().total_lost,
+            0,
+            "Estimate is not confirmed loss"
+        );
+
+        store.reset_estimated_loss();
+        assert!(!store.stats().counter_silent);
+        assert_eq!(store.stats().estimated_loss, 0);
+    }
+
+    #[test]
+    fn test_jitter_histogram_buckets() {
+        let mut store = StatsStore::new();
+        assert!(store.jitter_histogram(0.1).is_empty());
+
+        // Deltas: 0.05, 0.05, 0.15, 0.0, 0.35
+        for v in [5.0, 5.05, 5.0, 5.15, 5.15, 5.5] {
+            store.record_latency(v);
+        }
+        let hist = store.jitter_histogram(0.1);
+        let counts: Vec<u64> = hist.iter().map(|&(_, c)| c).collect();
+        assert_eq!(counts, vec![3, 1, 0, 1]);
+        assert!((hist[3].0 - 0.3).abs() < 1e-9);
+        assert!((store.stats().max_jitter_ms - 0.35).abs() < 1e-9);
+
+        assert!(store.jitter_histogram(0.0).is_empty());
+        assert!(store.jitter_histogram(f64::NAN).is_empty());
+
+        store.reset_counters();
+        assert!(store.jitter_histogram(0.1).is_empty());
+        assert_eq!(store.stats().max_jitter_ms, 0.0);
+    }
+
+    #[test]
+    fn test_jitter_histogram_caps_bucket_count() {
+        let mut store = StatsStore::new();
+        store.record_latency(1.0);
+        store.record_latency(500.0);
+        let hist = store.jitter_histogram(0.01);
+        assert_eq!(hist.len(), MAX_JITTER_BUCKETS);
+        assert_eq!(hist.last().unwrap().1, 1);
+    }
+
+    #[test]
+    fn test_avg_latency_window() {
+        let mut store = StatsStore::new();
+        assert_eq!(store.avg_latency_window(60), 0.0);
+
+        // 10ms twelve minutes ago, 6ms four minutes ago, 2ms and 4ms in the last minute
+        let now = Utc::now();
+        for (secs_ago, value) in [(720, 10.0), (240, 6.0), (30, 2.0), (10, 4.0)] {
+            store.latency_history.push_back(Measurement {
+                timestamp: now - chrono::Duration::seconds(secs_ago),
+                value,
+            });
+        }
+
+        assert!((store.avg_latency_window(60) - 3.0).abs() < 1e-9);
+        assert!((store.avg_latency_window(300) - 4.0).abs() < 1e-9);
+        assert!((store.avg_latency_window(900) - 5.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_percentiles_empty() {
+        let store = StatsStore::new();
+        assert_eq!(
+            store.latency_percentiles(&[50.0, 95.0, 99.0]),
+            vec![0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_percentiles_single_value() {
+        let mut store = StatsStore::new();
+        store.record_latency(4.2);
+        assert_eq!(
+            store.latency_percentiles(&[50.0, 95.0, 99.0]),
+            vec![4.2, 4.2, 4.2]
+        );
+    }
+
+    #[test]
+    fn test_percentiles_uniform_ramp() {
+        let mut store = StatsStore::new();
+        // Insert out of order to exercise sorting: 0..=100
+        for i in (0..=100).rev() {
+            store.record_latency(i as f64);
+        }
+        let p = store.latency_percentiles(&[0.0, 50.0, 95.0, 99.0, 100.0]);
+        assert_eq!(p, vec![0.0, 50.0, 95.0, 99.0, 100.0]);
+
+        // Interpolates between ranks
+        let mut store = StatsStore::new();
+        for v in [1.0, 2.0, 3.0, 4.0] {
+            store.record_latency(v);
+        }
+        assert!((store.latency_percentiles(&[50.0])[0] - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_loss_events_bounded_and_coalesced() {
+        let mut store = StatsStore::new();
+        let start = Utc::now() - chrono::Duration::days(2);
+
+        // Losses 500ms apart merge into the event they follow
+        store.record_loss_at(3, start);
+        store.record_loss_at(4, start + chrono::Duration::milliseconds(500));
+        store.record_loss_at(5, start + chrono::Duration::milliseconds(2000));
+        let counts: Vec<u64> = store.loss_events().iter().map(|e| e.count).collect();
+        assert_eq!(counts, [7, 5]);
+        assert_eq!(store.loss_events()[0].timestamp, start);
+        assert_eq!(store.stats().total_lost, 12);
+
+        // A flaky link losing samples every 2 seconds for days stays bounded
+        store.clear();
+        for i in 0..100_000 {
+            store.record_loss_at(1, start + chrono::Duration::seconds(2 * i));
+        }
+        assert_eq!(store.loss_events().len(), MAX_LOSS_EVENTS);
+        assert_eq!(
+            store.loss_events().back().unwrap().timestamp,
+            start + chrono::Duration::seconds(2 * 99_999)
+        );
+        assert_eq!(store.stats().total_lost, 100_000);
+
+        // Without coalescing every loss is its own event
+        store.clear();
+        store.set_loss_coalesce_ms(0);
+        store.record_loss_at(1, start);
+        store.record_loss_at(1, start);
+        assert_eq!(store.loss_events().len(), 2);
+    }
+
+    #[test]
+    fn test_loss_rate_counts_recent_window_only() {
+        let mut store = StatsStore::new();
+        store.set_uptime(3600);
+
+        // Old loss far outside the window
+        store.loss_events.push_back(LossEvent {
+            timestamp: Utc::now() - chrono::Duration::hours(2),
+            count: 5000,
+        });
+        assert_eq!(store.loss_rate_per_min(), 0.0);
+
+        store.record_loss(30);
+        store.record_loss(12);
+        assert_eq!(store.loss_rate_per_min(), 42.0);
+        assert_eq!(store.stats().total_lost, 42);
+    }
+
+    #[test]
+    fn test_loss_rate_scales_by_short_uptime() {
+        let mut store = StatsStore::new();
+        store.set_uptime(30);
+        store.record_loss(10);
+        // 10 samples in 30s of monitoring = 20 per minute
+        assert_eq!(store.loss_rate_per_min(), 20.0);
+    }
 }
@@ -0,0 +1,231 @@
+//! Webhook alerts for disconnections, reconnections and latency drift
+//!
+//! Posts a small JSON document to an incoming webhook (Slack, Teams, or any
+//! HTTP endpoint) so unattended installs can page someone. Delivery is
+//! best-effort: failures are logged and never affect monitoring.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two alerts of the same kind
+pub const ALERT_MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Timeout for a single webhook request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Kind of alert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEvent {
+    /// Reconnection gave up; the device is disconnected
+    Disconnect,
+    /// The device was reconnected after an outage
+    Reconnect,
+    /// Latency left the expected range (e.g. a routing change added buffering)
+    LatencyOutOfRange,
+}
+
+/// JSON body posted to the webhook
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertPayload {
+    pub event: AlertEvent,
+    pub device: Option<String>,
+    /// Outage duration in milliseconds
+    pub duration_ms: u64,
+    /// Whether the reconnection succeeded
+    pub success: bool,
+    /// Measured latency in milliseconds (latency alerts only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<f64>,
+}
+
+impl AlertPayload {
+    /// Build the payload for a recorded disconnection
+    pub fn from_disconnection(device: Option<&str>, duration_ms: u64, success: bool) -> Self {
+        Self {
+            event: if success {
+                AlertEvent::Reconnect
+            } else {
+                AlertEvent::Disconnect
+            },
+            device: device.map(str::to_string),
+            duration_ms,
+            success,
+            latency_ms: None,
+        }
+    }
+
+    /// Build the payload for a latency outside the expected range
+    pub fn latency_out_of_range(device: Option<&str>, latency_ms: f64) -> Self {
+        Self {
+            event: AlertEvent::LatencyOutOfRange,
+            device: device.map(str::to_string),
+            duration_ms: 0,
+            success: false,
+            latency_ms: Some(latency_ms),
+        }
+    }
+}
+
+/// Rate-limited webhook sender
+pub struct WebhookAlerter {
+    url: String,
+    client: reqwest::Client,
+    min_interval: Duration,
+    last_disconnect: Option<Instant>,
+    last_reconnect: Option<Instant>,
+    last_latency: Option<Instant>,
+}
+
+impl WebhookAlerter {
+    /// Create an alerter posting to `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            url: url.into(),
+            client,
+            min_interval: ALERT_MIN_INTERVAL,
+            last_disconnect: None,
+            last_reconnect: None,
+            last_latency: None,
+        }
+    }
+
+    /// Override the minimum interval between alerts of the same kind
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Send an alert in the background unless one of the same kind was
+    /// sent recently; returns whether it was dispatched
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn notify(&mut self, payload: AlertPayload) -> bool {
+        let last = match payload.event {
+            AlertEvent::Disconnect => &mut self.last_disconnect,
+            AlertEvent::Reconnect => &mut self.last_reconnect,
+            AlertEvent::LatencyOutOfRange => &mut self.last_latency,
+        };
+        if last.is_some_and(|t| t.elapsed() < self.min_interval) {
+            tracing::debug!(event = ?payload.event, "Webhook alert rate-limited");
+            return false;
+        }
+        *last = Some(Instant::now());
+
+        let client = self.client.clone();
+        let url = self.url.clone();
+        tokio::spawn(async move {
+            if let Err(e) = post(&client, &url, &payload).await {
+                tracing::warn!(event = ?payload.event, "Webhook alert failed: {}", e);
+            }
+        });
+        true
+    }
+
+    /// Post an alert immediately, bypassing the rate limit
+    pub async fn send(&self, payload: &AlertPayload) -> anyhow::Result<()> {
+        post(&self.client, &self.url, payload).await
+    }
+}
+
+async fn post(client: &reqwest::Client, url: &str, payload: &AlertPayload) -> anyhow::Result<()> {
+    client
+        .post(url)
+        .json(payload)
+        .send()
+        .await?
+        .error_for_status()?;
+    tracing::info!(event = ?payload.event, "Webhook alert sent");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::State;
+    use axum::Json;
+    use tokio::sync::mpsc;
+
+    /// Start a webhook receiver that forwards posted bodies to a channel
+    async fn mock_webhook() -> (String, mpsc::UnboundedReceiver<serde_json::Value>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = axum::Router::new()
+            .route(
+                "/hook",
+                axum::routing::post(
+                    |State(tx): State<mpsc::UnboundedSender<serde_json::Value>>,
+                     Json(body): Json<serde_json::Value>| async move {
+                        let _ = tx.send(body);
+                    },
+                ),
+            )
+            .with_state(tx);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{}/hook", addr), rx)
+    }
+
+    #[tokio::test]
+    async fn test_webhook_payload_shape() {
+        let (url, mut rx) = mock_webhook().await;
+        let alerter = WebhookAlerter::new(url);
+
+        alerter
+            .send(&AlertPayload::from_disconnection(
+                Some("VASIO-8"),
+                1234,
+                false,
+            ))
+            .await
+            .unwrap();
+
+        let body = rx.recv().await.unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "event": "disconnect",
+                "device": "VASIO-8",
+                "duration_ms": 1234,
+                "success": false,
+            })
+        );
+
+        alerter
+            .send(&AlertPayload::latency_out_of_range(Some("VASIO-8"), 9.5))
+            .await
+            .unwrap();
+        let body = rx.recv().await.unwrap();
+        assert_eq!(body["event"], "latency_out_of_range");
+        assert_eq!(body["latency_ms"], 9.5);
+    }
+
+    #[tokio::test]
+    async fn test_notify_rate_limits_per_event() {
+        let (url, mut rx) = mock_webhook().await;
+        let mut alerter = WebhookAlerter::new(url);
+
+        assert!(alerter.notify(AlertPayload::from_disconnection(None, 10, true)));
+        assert!(!alerter.notify(AlertPayload::from_disconnection(None, 20, true)));
+        assert!(alerter.notify(AlertPayload::from_disconnection(None, 30, false)));
+
+        let mut events = [rx.recv().await.unwrap(), rx.recv().await.unwrap()];
+        events.sort_by_key(|b| b["duration_ms"].as_u64());
+        assert_eq!(events[0]["event"], "reconnect");
+        assert_eq!(events[0]["device"], serde_json::Value::Null);
+        assert_eq!(events[1]["event"], "disconnect");
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_webhook_is_an_error() {
+        let alerter = WebhookAlerter::new("http://127.0.0.1:1/hook");
+        let payload = AlertPayload::from_disconnection(None, 0, true);
+        assert!(alerter.send(&payload).await.is_err());
+    }
+}
@@ -2,11 +2,18 @@
 //!
 //! All endpoints are under /api/v1/ and return JSON.
 
-use crate::AppState;
-use audiotester_core::audio::engine::EngineState;
+use crate::event_log::LogEvent;
+use crate::preferences::Preferences;
+use crate::{AppState, EngineStatus, LatencyThresholds, ReconnectStrategy, SignalLossTimeouts};
+use audiotester_core::audio::burst::BurstWaveform;
+use audiotester_core::audio::capture::MAX_CAPTURE_SECS;
+use audiotester_core::audio::engine::{
+    DeviceInfo, EngineState, MeasurementMethod, MAX_BUFFER_SIZE, MIN_BUFFER_SIZE,
+};
+use audiotester_core::audio::fallback::LatencyMethod;
 use axum::extract::State;
-use axum::http::StatusCode;
-use axum::response::Json;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 
 /// Application status response
@@ -16,28 +23,72 @@ pub struct StatusResponse {
     pub build_date: String,
     pub state: String,
     pub device: Option<String>,
+    /// Separate input device (None when input and output share `device`)
+    pub input_device: Option<String>,
+    /// Latency method: "frame_counter" (one device) or "timestamp" (two devices)
+    pub measurement_method: MeasurementMethod,
     pub sample_rate: u32,
+    /// Sample rate requested by the configuration
+    pub requested_sample_rate: u32,
+    /// Sample rate the streams run at (None while stopped)
+    pub effective_sample_rate: Option<u32>,
+    /// True when the device rejected the requested rate and the default was used
+    pub rate_fallback: bool,
     pub monitoring: bool,
+    /// Driver-reported input latency (ms), once streams are running
+    pub input_latency_ms: Option<f64>,
+    /// Driver-reported output latency (ms), once streams are running
+    pub output_latency_ms: Option<f64>,
+    /// When statistics were last persisted to disk (RFC 3339), if ever
+    pub stats_last_saved: Option<String>,
+    /// A test tone is replacing the burst output
+    pub test_tone_active: bool,
+    /// Port the web server is listening on
+    pub port: u16,
 }
 
 /// Statistics response
 #[derive(Serialize, Clone)]
 pub struct StatsResponse {
     pub current_latency: f64,
+    /// Current latency in whole microseconds
+    pub latency_us: u64,
     pub min_latency: f64,
     pub max_latency: f64,
     pub avg_latency: f64,
+    /// Latency standard deviation over the recent window (ms)
+    pub jitter_ms: f64,
+    /// Largest difference between consecutive measurements since reset (ms)
+    pub max_jitter_ms: f64,
+    /// Average latency over the last minute (ms)
+    pub avg_1m: f64,
+    /// Average latency over the last 5 minutes (ms)
+    pub avg_5m: f64,
+    /// Average latency over the last 15 minutes (ms)
+    pub avg_15m: f64,
+    /// Median latency over the recent history (ms)
+    pub p50_latency: f64,
+    /// 95th percentile latency over the recent history (ms)
+    pub p95_latency: f64,
+    /// 99th percentile latency over the recent history (ms)
+    pub p99_latency: f64,
     pub total_lost: u64,
+    /// Samples lost per minute over the last 60 seconds
+    pub loss_rate_per_min: f64,
     pub total_corrupted: u64,
     pub measurement_count: u64,
     pub latency_history: Vec<(f64, f64)>,
     pub loss_history: Vec<(f64, f64)>,
+    /// Measurement confidence over time, as (seconds before now, confidence)
+    pub confidence_history: Vec<(f64, f64)>,
     /// Active device name (if any)
     pub device_name: Option<String>,
     /// Current buffer size
     pub buffer_size: u32,
     /// Current sample rate
     pub sample_rate: u32,
+    /// True when the device rejected the requested sample rate
+    pub rate_fallback: bool,
     /// Uptime in seconds
     pub uptime_seconds: u64,
     /// Loss events with timestamps for visualization
@@ -54,6 +105,26 @@ pub struct StatsResponse {
     pub estimated_loss: u64,
     /// True when ch1 counter signal is currently absent (muted loopback)
     pub counter_silent: bool,
+    /// True when ch1 counter is frozen on a constant value (repeating buffer)
+    pub counter_stuck: bool,
+    /// Peak input level per channel (linear 0.0-1.0), ch0 burst, ch1 counter, ...
+    pub input_levels: Vec<f32>,
+    /// True when the current latency is unusual for this path (learned histogram)
+    pub anomaly: bool,
+    /// True when the current latency is outside `expected_latency_ms` ± tolerance
+    pub latency_out_of_range: bool,
+    /// Driver-reported round-trip hardware latency (ms, 0 when unknown)
+    pub hardware_latency_ms: f64,
+    /// Measured latency in excess of the hardware latency (ms)
+    pub excess_latency_ms: f64,
+    /// Input/output clock drift (ppm); positive when latency grows
+    pub clock_drift_ppm: Option<f64>,
+    /// Share of monitored time with the signal present (%, None before monitoring)
+    pub availability_percent: Option<f64>,
+    /// True while recording is paused via `POST /api/v1/recording`
+    pub recording_paused: bool,
+    /// How the current latency was measured ("burst" or "mls_fallback")
+    pub latency_method: LatencyMethod,
 }
 
 /// Loss event response for API
@@ -69,6 +140,8 @@ pub struct LossEventResponse {
 #[derive(Serialize)]
 pub struct DeviceResponse {
     pub name: String,
+    /// Host (driver API) the device belongs to, e.g. "ASIO" or "WASAPI"
+    pub host: String,
     pub is_default: bool,
     pub sample_rates: Vec<u32>,
     pub input_channels: u16,
@@ -81,6 +154,38 @@ pub struct ConfigResponse {
     pub device: Option<String>,
     pub sample_rate: u32,
     pub monitoring: bool,
+    /// Counter silence threshold for loss analysis (ms)
+    pub silence_threshold_ms: u32,
+    /// Requested ASIO buffer size in frames (null = driver default)
+    pub buffer_size: Option<u32>,
+    /// Channel index carrying the burst signal
+    pub burst_channel: usize,
+    /// Channel index carrying the frame counter
+    pub counter_channel: usize,
+    /// Time without a valid analysis before declaring signal loss (ms)
+    pub signal_timeout_ms: u64,
+    /// Time the signal must stay lost before reconnecting (ms)
+    pub reconnect_after_loss_ms: u64,
+    /// Latency above which the tray shows a warning (ms)
+    pub latency_warning_ms: f64,
+    /// Latency above which the tray shows an error (ms)
+    pub latency_error_ms: f64,
+    /// Reconnection attempts after an engine error (0 = unlimited)
+    pub max_reconnect_attempts: u32,
+    /// Burst amplitude (0.0 to 1.0)
+    pub burst_amplitude: f32,
+    /// Burst waveform: "noise", "chirp" or "impulse"
+    pub burst_waveform: BurstWaveform,
+    /// Lowest confidence accepted as a valid signal
+    pub min_confidence: f32,
+    /// Latency below which a measurement is not a valid signal (ms)
+    pub min_latency_ms: f64,
+    /// Latency at or above which a measurement is not a valid signal (ms)
+    pub max_latency_ms: f64,
+    /// Latency the path is expected to have (ms, null = range check disabled)
+    pub expected_latency_ms: Option<f64>,
+    /// Deviation from the expected latency still considered in range (ms)
+    pub latency_tolerance_ms: f64,
 }
 
 /// Configuration update request
@@ -88,12 +193,75 @@ pub struct ConfigResponse {
 pub struct ConfigUpdate {
     pub device: Option<String>,
     pub sample_rate: Option<u32>,
+    pub silence_threshold_ms: Option<u32>,
+    /// Buffer size in frames, applied on next start (0 = driver default)
+    pub buffer_size: Option<u32>,
+    /// Burst channel index, applied on next start
+    pub burst_channel: Option<usize>,
+    /// Counter channel index, applied on next start
+    pub counter_channel: Option<usize>,
+    /// Burst amplitude (0.0 to 1.0), applied on next start
+    pub burst_amplitude: Option<f32>,
+    /// Burst waveform, applied on next start
+    pub burst_waveform: Option<BurstWaveform>,
+    /// Signal-loss timeout (ms); must not exceed `reconnect_after_loss_ms`
+    pub signal_timeout_ms: Option<u64>,
+    /// Reconnect delay after signal loss (ms)
+    pub reconnect_after_loss_ms: Option<u64>,
+    /// Latency warning threshold (ms); must not exceed `latency_error_ms`
+    pub latency_warning_ms: Option<f64>,
+    /// Latency error threshold (ms)
+    pub latency_error_ms: Option<f64>,
+}
+
+/// Device selection request
+#[derive(Deserialize)]
+pub struct DeviceSelectRequest {
+    pub name: String,
+    /// Receive on this device instead (timestamp latency); omit for loopback on `name`
+    pub input_name: Option<String>,
 }
 
 /// Remote URL response
 #[derive(Serialize)]
 pub struct RemoteUrlResponse {
+    /// First candidate URL (kept for older clients)
     pub url: String,
+    /// Candidate URLs: IPv4, hostname, then IPv6
+    pub urls: Vec<String>,
+}
+
+/// Startup self-diagnostic report
+#[derive(Debug, Serialize, PartialEq)]
+pub struct DiagnosticsResponse {
+    /// Device enumeration succeeded and found at least one ASIO device
+    pub asio_available: bool,
+    /// Number of devices on the selected audio hosts
+    pub device_count: usize,
+    /// Name of the host's default device, if any
+    pub default_device: Option<String>,
+    /// Device the engine is configured to use
+    pub configured_device: Option<String>,
+    /// Whether the configured device is currently listed
+    pub configured_device_found: bool,
+    /// Whether a file can be created in the log directory
+    pub log_dir_writable: bool,
+    /// LAN address for remote dashboard access
+    pub local_ip: Option<String>,
+    /// Device enumeration error, if it failed
+    pub error: Option<String>,
+}
+
+/// Recording pause request
+#[derive(Deserialize)]
+pub struct RecordingRequest {
+    pub paused: bool,
+}
+
+/// Recording pause state
+#[derive(Serialize)]
+pub struct RecordingResponse {
+    pub paused: bool,
 }
 
 /// Monitoring toggle request
@@ -102,6 +270,52 @@ pub struct MonitoringRequest {
     pub enabled: bool,
 }
 
+/// Health check response
+#[derive(Serialize, Debug, PartialEq)]
+pub struct HealthResponse {
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Longest the health check waits for the engine thread
+const HEALTH_ENGINE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Decide health from the engine state (None = engine unresponsive) and signal
+fn health_from(engine_state: Option<EngineState>, signal_lost: bool) -> HealthResponse {
+    let reason = match engine_state {
+        None => Some("engine not responding".to_string()),
+        Some(EngineState::Running) if signal_lost => Some("signal lost".to_string()),
+        Some(EngineState::Running) => None,
+        Some(other) => Some(format!("engine {:?}", other).to_lowercase()),
+    };
+    HealthResponse {
+        ok: reason.is_none(),
+        reason,
+    }
+}
+
+/// GET /api/v1/health
+///
+/// Lightweight liveness check for load balancers and uptime monitors:
+/// 200 when monitoring with a signal, 503 otherwise.
+pub async fn get_health(State(state): State<AppState>) -> (StatusCode, Json<HealthResponse>) {
+    let signal_lost = state.stats_lock().stats().signal_lost;
+    let engine_state = tokio::time::timeout(HEALTH_ENGINE_TIMEOUT, state.engine.get_status())
+        .await
+        .ok()
+        .and_then(|status| status.ok())
+        .map(|status| status.state);
+
+    let health = health_from(engine_state, signal_lost);
+    let code = if health.ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(health))
+}
+
 /// GET /api/v1/status
 pub async fn get_status(
     State(state): State<AppState>,
@@ -112,24 +326,67 @@ pub async fn get_status(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(StatusResponse {
+    Ok(Json(status_response(
+        status,
+        stats_last_saved(&state),
+        state.port(),
+    )))
+}
+
+/// Build the status response from an engine status snapshot
+fn status_response(
+    status: EngineStatus,
+    stats_last_saved: Option<String>,
+    port: u16,
+) -> StatusResponse {
+    StatusResponse {
         version: audiotester_core::VERSION.to_string(),
         build_date: audiotester_core::BUILD_DATE.to_string(),
         state: format!("{:?}", status.state),
         device: status.device_name,
+        input_device: status.input_device_name,
+        measurement_method: status.measurement_method,
         sample_rate: status.sample_rate,
+        requested_sample_rate: status.requested_sample_rate,
+        effective_sample_rate: status.effective_sample_rate,
+        rate_fallback: status.rate_fallback,
         monitoring: status.state == EngineState::Running,
-    }))
+        input_latency_ms: status.hardware_latency_ms.map(|(i, _)| i),
+        output_latency_ms: status.hardware_latency_ms.map(|(_, o)| o),
+        stats_last_saved,
+        test_tone_active: status.test_tone_active,
+        port,
+    }
+}
+
+/// Last stats save time as RFC 3339, for status reporting
+fn stats_last_saved(state: &AppState) -> Option<String> {
+    state.stats_lock().last_saved().map(|t| t.to_rfc3339())
 }
 
 /// GET /api/v1/stats
 pub async fn get_stats(State(state): State<AppState>) -> Json<StatsResponse> {
     // Extract stats from lock in a block so MutexGuard is dropped before .await
-    let (stats, latency_history, loss_history, loss_events) = {
-        let store = state.stats.lock().unwrap();
+    let (
+        stats,
+        latency_history,
+        loss_history,
+        confidence_history,
+        loss_events,
+        percentiles,
+        rolling_avg,
+        loss_rate_per_min,
+        availability_percent,
+    ) = {
+        let store = state.stats_lock();
         let stats = store.stats().clone();
+        let percentiles = store.latency_percentiles(&[50.0, 95.0, 99.0]);
+        let rolling_avg = [60, 300, 900].map(|secs| store.avg_latency_window(secs));
+        let loss_rate_per_min = store.loss_rate_per_min();
+        let availability_percent = store.availability_percent();
         let latency_history = store.latency_plot_data(300);
         let loss_history = store.loss_plot_data(300);
+        let confidence_history = store.confidence_plot_data(300);
         let loss_events: Vec<LossEventResponse> = store
             .loss_events()
             .iter()
@@ -140,17 +397,29 @@ pub async fn get_stats(State(state): State<AppState>) -> Json<StatsResponse> {
                 count: e.count,
             })
             .collect();
-        (stats, latency_history, loss_history, loss_events)
+        (
+            stats,
+            latency_history,
+            loss_history,
+            confidence_history,
+            loss_events,
+            percentiles,
+            rolling_avg,
+            loss_rate_per_min,
+            availability_percent,
+        )
     };
 
     // Get device info from engine (safe to await now, no lock held)
-    let (device_name, sample_rate) = match state.engine.get_status().await {
-        Ok(status) => (status.device_name, status.sample_rate),
-        Err(_) => (None, 0),
+    let (device_name, sample_rate, rate_fallback) = match state.engine.get_status().await {
+        Ok(status) => (status.device_name, status.sample_rate, status.rate_fallback),
+        Err(_) => (None, 0, false),
     };
 
+    let excess_latency_ms = stats.excess_latency_ms();
     Json(StatsResponse {
         current_latency: stats.current_latency,
+        latency_us: stats.current_latency_us(),
         min_latency: if stats.min_latency == f64::MAX {
             0.0
         } else {
@@ -158,14 +427,25 @@ pub async fn get_stats(State(state): State<AppState>) -> Json<StatsResponse> {
         },
         max_latency: stats.max_latency,
         avg_latency: stats.avg_latency,
+        jitter_ms: stats.jitter_ms,
+        max_jitter_ms: stats.max_jitter_ms,
+        avg_1m: rolling_avg[0],
+        avg_5m: rolling_avg[1],
+        avg_15m: rolling_avg[2],
+        loss_rate_per_min,
+        p50_latency: percentiles[0],
+        p95_latency: percentiles[1],
+        p99_latency: percentiles[2],
         total_lost: stats.total_lost,
         total_corrupted: stats.total_corrupted,
         measurement_count: stats.measurement_count,
         latency_history,
         loss_history,
+        confidence_history,
         device_name,
         buffer_size: stats.buffer_size,
         sample_rate,
+        rate_fallback,
         uptime_seconds: stats.uptime_seconds,
         loss_events,
         samples_sent: stats.samples_sent,
@@ -174,24 +454,50 @@ pub async fn get_stats(State(state): State<AppState>) -> Json<StatsResponse> {
         confidence: stats.last_confidence,
         estimated_loss: stats.estimated_loss,
         counter_silent: stats.counter_silent,
+        counter_stuck: stats.counter_stuck,
+        input_levels: stats.input_levels,
+        anomaly: stats.anomaly,
+        latency_out_of_range: stats.latency_out_of_range,
+        hardware_latency_ms: stats.hardware_latency_ms,
+        excess_latency_ms,
+        clock_drift_ppm: stats.clock_drift_ppm,
+        availability_percent,
+        recording_paused: state.is_recording_paused(),
+        latency_method: stats.latency_method,
     })
 }
 
+/// Query parameters for POST /api/v1/reset
+#[derive(Deserialize, Default)]
+pub struct ResetQuery {
+    /// Also wipe graph history, archives and disconnection events
+    pub hard: Option<bool>,
+}
+
 /// POST /api/v1/reset
 ///
-/// Resets statistics counters (min/max/avg/totals) without clearing graph history.
+/// Soft reset (default) clears statistics counters (min/max/avg/totals) and
+/// keeps graph history. `?hard=true` wipes all latency, loss, archive and
+/// disconnection data and resets the engine's latency analyzer.
 pub async fn reset_stats(
     State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<ResetQuery>,
 ) -> Result<StatusCode, (StatusCode, String)> {
-    if let Ok(mut store) = state.stats.lock() {
-        store.reset_counters();
-        Ok(StatusCode::NO_CONTENT)
-    } else {
-        Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "Failed to acquire lock on stats store".to_string(),
-        ))
+    let hard = query.hard.unwrap_or(false);
+    {
+        let mut store = state.stats_lock();
+        if hard {
+            store.clear();
+        } else {
+            store.reset_counters();
+        }
     }
+
+    if hard {
+        state.engine.reset_analyzer().await;
+        tracing::info!("Statistics and history cleared (hard reset)");
+    }
+    Ok(StatusCode::NO_CONTENT)
 }
 
 /// GET /api/v1/devices
@@ -204,6 +510,7 @@ pub async fn list_devices(
                 .into_iter()
                 .map(|d| DeviceResponse {
                     name: d.name,
+                    host: d.host,
                     is_default: d.is_default,
                     sample_rates: d.sample_rates,
                     input_channels: d.input_channels,
@@ -231,12 +538,52 @@ pub async fn get_config(
 
     Ok(Json(ConfigResponse {
         device: status.device_name,
-        sample_rate: status.sample_rate,
+        sample_rate: status.requested_sample_rate,
         monitoring: status.state == EngineState::Running,
+        silence_threshold_ms: status.silence_threshold_ms,
+        buffer_size: status.buffer_size,
+        burst_channel: status.signal_channels.0,
+        counter_channel: status.signal_channels.1,
+        signal_timeout_ms: state.loss_timeouts.signal_timeout_ms(),
+        reconnect_after_loss_ms: state.loss_timeouts.reconnect_after_loss_ms(),
+        latency_warning_ms: state.latency_thresholds.warning_ms(),
+        latency_error_ms: state.latency_thresholds.error_ms(),
+        max_reconnect_attempts: state.config.max_reconnect_attempts,
+        burst_amplitude: status.burst_amplitude,
+        burst_waveform: status.burst_waveform,
+        min_confidence: state.config.min_confidence,
+        min_latency_ms: state.config.min_latency_ms,
+        max_latency_ms: state.config.max_latency_ms,
+        expected_latency_ms: state.config.expected_latency_ms,
+        latency_tolerance_ms: state.config.latency_tolerance_ms,
     }))
 }
 
+/// Reject a device name the engine does not list
+///
+/// Runs before anything is stopped or changed, so a typo leaves the current
+/// device (and a running measurement) untouched.
+async fn ensure_device_listed(state: &AppState, name: &str) -> Result<(), (StatusCode, String)> {
+    let devices = state.engine.list_devices().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to list devices: {}", e),
+        )
+    })?;
+    if devices.iter().any(|d| d.name == name) {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::BAD_REQUEST,
+            format!("Failed to select device: unknown device '{}'", name),
+        ))
+    }
+}
+
 /// PATCH /api/v1/config
+///
+/// The whole update is validated before any field is applied, so a rejected
+/// request leaves the configuration exactly as it was.
 pub async fn update_config(
     State(state): State<AppState>,
     Json(update): Json<ConfigUpdate>,
@@ -248,17 +595,128 @@ pub async fn update_config(
                 format!("Invalid sample rate: {} (must be 8000-384000 Hz)", rate),
             ));
         }
-        state.engine.set_sample_rate(rate).await;
+    }
+
+    let timeouts = &state.loss_timeouts;
+    let new_timeouts =
+        if update.signal_timeout_ms.is_some() || update.reconnect_after_loss_ms.is_some() {
+            let signal = update
+                .signal_timeout_ms
+                .unwrap_or_else(|| timeouts.signal_timeout_ms());
+            let reconnect = update
+                .reconnect_after_loss_ms
+                .unwrap_or_else(|| timeouts.reconnect_after_loss_ms());
+            SignalLossTimeouts::validate(signal, reconnect)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+            Some((signal, reconnect))
+        } else {
+            None
+        };
+
+    let thresholds = &state.latency_thresholds;
+    let new_thresholds = if update.latency_warning_ms.is_some() || update.latency_error_ms.is_some()
+    {
+        let warning = update
+            .latency_warning_ms
+            .unwrap_or_else(|| thresholds.warning_ms());
+        let error = update
+            .latency_error_ms
+            .unwrap_or_else(|| thresholds.error_ms());
+        LatencyThresholds::validate(warning, error).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        Some((warning, error))
+    } else {
+        None
+    };
+
+    if let Some(ms) = update.silence_threshold_ms {
+        if !(10..=5000).contains(&ms) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid silence threshold: {} (must be 10-5000 ms)", ms),
+            ));
+        }
+    }
+
+    if let Some(frames) = update.buffer_size {
+        if frames != 0 && !(MIN_BUFFER_SIZE..=MAX_BUFFER_SIZE).contains(&frames) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Invalid buffer size: {} (must be 0 or {}-{} frames)",
+                    frames, MIN_BUFFER_SIZE, MAX_BUFFER_SIZE
+                ),
+            ));
+        }
+    }
+
+    let status = state
+        .engine
+        .get_status()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let new_channels = if update.burst_channel.is_some() || update.counter_channel.is_some() {
+        let (burst, counter) = status.signal_channels;
+        let burst = update.burst_channel.unwrap_or(burst);
+        let counter = update.counter_channel.unwrap_or(counter);
+        if burst == counter {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Burst and counter must use different channels (both set to {})",
+                    burst
+                ),
+            ));
+        }
+        Some((burst, counter))
+    } else {
+        None
+    };
+
+    if let Some(amplitude) = update.burst_amplitude {
+        if !(0.0..=1.0).contains(&amplitude) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid burst amplitude: {} (must be 0.0-1.0)", amplitude),
+            ));
+        }
     }
 
     if let Some(ref device) = update.device {
-        // Stop if running
-        let status = state
-            .engine
-            .get_status()
-            .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        ensure_device_listed(&state, device).await?;
+    }
+
+    // Everything is valid: apply
+    if let Some(rate) = update.sample_rate {
+        state.engine.set_sample_rate(rate).await;
+    }
+    if let Some((signal, reconnect)) = new_timeouts {
+        timeouts
+            .set(signal, reconnect)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    }
+    if let Some((warning, error)) = new_thresholds {
+        thresholds
+            .set(warning, error)
+            .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    }
+    if let Some(ms) = update.silence_threshold_ms {
+        state.engine.set_silence_threshold_ms(ms).await;
+    }
+    if let Some(frames) = update.buffer_size {
+        state.engine.set_buffer_size(frames).await;
+    }
+    if let Some((burst, counter)) = new_channels {
+        state.engine.set_signal_channels(burst, counter).await;
+    }
+    if let Some(amplitude) = update.burst_amplitude {
+        state.engine.set_burst_amplitude(amplitude).await;
+    }
+    if let Some(waveform) = update.burst_waveform {
+        state.engine.set_burst_waveform(waveform).await;
+    }
 
+    if let Some(device) = update.device {
         if status.state == EngineState::Running {
             state.engine.stop().await.map_err(|e| {
                 (
@@ -268,16 +726,12 @@ pub async fn update_config(
             })?;
         }
 
-        state
-            .engine
-            .select_device(device.clone())
-            .await
-            .map_err(|e| {
-                (
-                    StatusCode::BAD_REQUEST,
-                    format!("Failed to select device: {}", e),
-                )
-            })?;
+        state.engine.select_device(device).await.map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to select device: {}", e),
+            )
+        })?;
     }
 
     let status = state
@@ -288,21 +742,498 @@ pub async fn update_config(
 
     Ok(Json(ConfigResponse {
         device: status.device_name,
-        sample_rate: status.sample_rate,
+        sample_rate: status.requested_sample_rate,
         monitoring: status.state == EngineState::Running,
+        silence_threshold_ms: status.silence_threshold_ms,
+        buffer_size: status.buffer_size,
+        burst_channel: status.signal_channels.0,
+        counter_channel: status.signal_channels.1,
+        signal_timeout_ms: state.loss_timeouts.signal_timeout_ms(),
+        reconnect_after_loss_ms: state.loss_timeouts.reconnect_after_loss_ms(),
+        latency_warning_ms: state.latency_thresholds.warning_ms(),
+        latency_error_ms: state.latency_thresholds.error_ms(),
+        max_reconnect_attempts: state.config.max_reconnect_attempts,
+        burst_amplitude: status.burst_amplitude,
+        burst_waveform: status.burst_waveform,
+        min_confidence: state.config.min_confidence,
+        min_latency_ms: state.config.min_latency_ms,
+        max_latency_ms: state.config.max_latency_ms,
+        expected_latency_ms: state.config.expected_latency_ms,
+        latency_tolerance_ms: state.config.latency_tolerance_ms,
+    }))
+}
+
+/// Placeholder shown instead of secret values
+pub const REDACTED: &str = "***";
+
+/// Environment variables whose values are secrets
+const SECRET_ENV_VARS: &[&str] = &["AUDIOTESTER_TOKEN", "AUDIOTESTER_ALERT_WEBHOOK"];
+
+/// Fully resolved runtime configuration for remote support
+///
+/// Combines defaults, environment variables and runtime changes (PATCH
+/// /api/v1/config) into what is actually in effect. Secrets are redacted.
+#[derive(Serialize)]
+pub struct EffectiveConfigResponse {
+    /// Port the server is listening on
+    pub port: u16,
+    pub bind_addr: String,
+    pub device: Option<String>,
+    pub input_device: Option<String>,
+    /// Requested sample rate
+    pub sample_rate: u32,
+    /// Sample rate the streams run at (null while stopped)
+    pub effective_sample_rate: Option<u32>,
+    /// Requested buffer size in frames (null = driver default)
+    pub buffer_size: Option<u32>,
+    /// Buffer size delivered by the driver (0 until streams run)
+    pub actual_buffer_size: u32,
+    pub burst_channel: usize,
+    pub counter_channel: usize,
+    pub burst_amplitude: f32,
+    pub burst_waveform: BurstWaveform,
+    pub silence_threshold_ms: u32,
+    pub signal_timeout_ms: u64,
+    pub reconnect_after_loss_ms: u64,
+    pub latency_warning_ms: f64,
+    pub latency_error_ms: f64,
+    pub min_confidence: f32,
+    pub min_latency_ms: f64,
+    pub max_latency_ms: f64,
+    pub expected_latency_ms: Option<f64>,
+    pub latency_tolerance_ms: f64,
+    pub startup_grace_ms: u64,
+    pub reconnect_strategy: ReconnectStrategy,
+    pub max_reconnect_attempts: u32,
+    pub stats_save_interval_secs: u64,
+    pub incident_min_loss: u64,
+    pub notifications_enabled: bool,
+    /// `***` when a token is set
+    pub auth_token: Option<String>,
+    pub auth_all: bool,
+    /// Origins allowed to call the API cross-site (empty = any)
+    pub allowed_origins: Vec<String>,
+    /// `***` when a webhook is set (the URL embeds its credentials)
+    pub alert_webhook: Option<String>,
+    pub osc_target: Option<String>,
+    pub midi_port: Option<String>,
+    pub autoconfig: Option<crate::AutoConfigInfo>,
+    pub data_paths: DataPaths,
+    /// `AUDIOTESTER_*` variables set for this process (secrets redacted)
+    pub env: std::collections::BTreeMap<String, String>,
+}
+
+/// Files and directories the app writes to
+#[derive(Serialize)]
+pub struct DataPaths {
+    pub stats: Option<String>,
+    pub baseline: Option<String>,
+    pub preferences: Option<String>,
+    pub incidents: Option<String>,
+    pub logs: Option<String>,
+}
+
+/// Pick the `AUDIOTESTER_*` variables from `vars`, redacting secret values
+fn audiotester_env(
+    vars: impl Iterator<Item = (String, String)>,
+) -> std::collections::BTreeMap<String, String> {
+    vars.filter(|(name, _)| name.starts_with("AUDIOTESTER_"))
+        .map(|(name, value)| {
+            let value = if SECRET_ENV_VARS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                value
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+/// GET /api/v1/config/effective
+pub async fn get_effective_config(
+    State(state): State<AppState>,
+) -> Result<Json<EffectiveConfigResponse>, (StatusCode, String)> {
+    let status = state
+        .engine
+        .get_status()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(effective_config(&state, status, std::env::vars())))
+}
+
+/// Assemble the effective configuration from the state, engine status and environment
+fn effective_config(
+    state: &AppState,
+    status: EngineStatus,
+    vars: impl Iterator<Item = (String, String)>,
+) -> EffectiveConfigResponse {
+    let config = &state.config;
+    let redact = |value: &Option<String>| value.as_ref().map(|_| REDACTED.to_string());
+    let path = |path: &Option<std::path::PathBuf>| path.as_ref().map(|p| p.display().to_string());
+    EffectiveConfigResponse {
+        port: state.port(),
+        bind_addr: config.bind_addr.clone(),
+        device: status.device_name,
+        input_device: status.input_device_name,
+        sample_rate: status.requested_sample_rate,
+        effective_sample_rate: status.effective_sample_rate,
+        buffer_size: status.buffer_size,
+        actual_buffer_size: status.actual_buffer_size,
+        burst_channel: status.signal_channels.0,
+        counter_channel: status.signal_channels.1,
+        burst_amplitude: status.burst_amplitude,
+        burst_waveform: status.burst_waveform,
+        silence_threshold_ms: status.silence_threshold_ms,
+        signal_timeout_ms: state.loss_timeouts.signal_timeout_ms(),
+        reconnect_after_loss_ms: state.loss_timeouts.reconnect_after_loss_ms(),
+        latency_warning_ms: state.latency_thresholds.warning_ms(),
+        latency_error_ms: state.latency_thresholds.error_ms(),
+        min_confidence: config.min_confidence,
+        min_latency_ms: config.min_latency_ms,
+        max_latency_ms: config.max_latency_ms,
+        expected_latency_ms: config.expected_latency_ms,
+        latency_tolerance_ms: config.latency_tolerance_ms,
+        startup_grace_ms: config.startup_grace_ms,
+        reconnect_strategy: config.reconnect_strategy,
+        max_reconnect_attempts: config.max_reconnect_attempts,
+        stats_save_interval_secs: config.stats_save_interval_secs,
+        incident_min_loss: config.incident_min_loss,
+        notifications_enabled: config.notifications_enabled,
+        auth_token: redact(&config.auth_token),
+        auth_all: config.auth_all,
+        allowed_origins: config.allowed_origins.clone(),
+        alert_webhook: redact(&config.alert_webhook),
+        osc_target: config.osc_target.map(|target| target.to_string()),
+        midi_port: config.midi_port.clone(),
+        autoconfig: config.autoconfig,
+        data_paths: DataPaths {
+            stats: path(&config.stats_path),
+            baseline: path(&config.baseline_path),
+            preferences: path(&config.preferences_path),
+            incidents: path(&config.incident_dir),
+            logs: path(&state.log_dir),
+        },
+        env: audiotester_env(vars),
+    }
+}
+
+/// PUT /api/v1/device
+///
+/// Selects a device without starting monitoring. Stops the engine first if
+/// running; unknown names are rejected before anything is stopped.
+pub async fn select_device(
+    State(state): State<AppState>,
+    Json(req): Json<DeviceSelectRequest>,
+) -> Result<Json<ConfigResponse>, (StatusCode, String)> {
+    let input = req.input_name.unwrap_or_else(|| req.name.clone());
+    ensure_device_listed(&state, &req.name).await?;
+    if input != req.name {
+        ensure_device_listed(&state, &input).await?;
+    }
+
+    let status = state
+        .engine
+        .get_status()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if status.state == EngineState::Running {
+        state.engine.stop().await.map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to stop: {}", e),
+            )
+        })?;
+    }
+
+    state
+        .engine
+        .select_devices(req.name, input)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to select device: {}", e),
+            )
+        })?;
+
+    get_config(State(state)).await
+}
+
+/// DELETE /api/v1/device
+///
+/// Deselects the current device, stopping monitoring if running, so the
+/// ASIO driver is released for other applications.
+pub async fn clear_device(
+    State(state): State<AppState>,
+) -> Result<Json<ConfigResponse>, (StatusCode, String)> {
+    state.engine.deselect_device().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to deselect device: {}", e),
+        )
+    })?;
+
+    get_config(State(state)).await
+}
+
+/// WAV capture request
+#[derive(Deserialize)]
+pub struct CaptureRequest {
+    /// Length of the recording in seconds
+    pub duration_secs: u32,
+}
+
+/// WAV capture response
+#[derive(Serialize)]
+pub struct CaptureResponse {
+    /// File the capture is being written to
+    pub path: String,
+    pub duration_secs: u32,
+}
+
+/// POST /api/v1/capture
+///
+/// Starts recording input channel 0 to a WAV file next to the logs and
+/// returns its path. The file is complete once the duration has elapsed.
+pub async fn start_capture(
+    State(state): State<AppState>,
+    Json(req): Json<CaptureRequest>,
+) -> Result<Json<CaptureResponse>, (StatusCode, String)> {
+    if !(1..=MAX_CAPTURE_SECS).contains(&req.duration_secs) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Invalid duration: {} (must be 1-{} s)",
+                req.duration_secs, MAX_CAPTURE_SECS
+            ),
+        ));
+    }
+
+    let dir = state
+        .log_dir
+        .as_ref()
+        .and_then(|d| d.parent())
+        .map(|d| d.join("captures"))
+        .unwrap_or_else(std::env::temp_dir);
+    let path = dir.join(format!(
+        "audiotester-capture-{}.wav",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    state
+        .engine
+        .start_capture(path.clone(), req.duration_secs)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::CONFLICT,
+                format!("Failed to start capture: {}", e),
+            )
+        })?;
+
+    Ok(Json(CaptureResponse {
+        path: path.display().to_string(),
+        duration_secs: req.duration_secs,
+    }))
+}
+
+/// Default test tone frequency (Hz)
+const DEFAULT_TONE_FREQ_HZ: f32 = 1000.0;
+
+/// Default test tone duration (ms)
+const DEFAULT_TONE_DURATION_MS: u32 = 1000;
+
+/// Test tone request (all fields optional)
+#[derive(Deserialize)]
+pub struct TestToneRequest {
+    /// Tone frequency in Hz (default 1000)
+    pub freq_hz: Option<f32>,
+    /// Output channel (default: the burst channel)
+    pub channel: Option<usize>,
+    /// Tone length in milliseconds (default 1000)
+    pub duration_ms: Option<u32>,
+}
+
+/// Test tone response
+#[derive(Serialize)]
+pub struct TestToneResponse {
+    pub freq_hz: f32,
+    pub channel: usize,
+    pub duration_ms: u32,
+}
+
+/// POST /api/v1/test-tone
+///
+/// Replaces the burst with a sine on one output channel for a moment to
+/// confirm audio is physically flowing. Latency is not measured while the
+/// tone plays.
+pub async fn play_test_tone(
+    State(state): State<AppState>,
+    Json(req): Json<TestToneRequest>,
+) -> Result<Json<TestToneResponse>, (StatusCode, String)> {
+    let status = state.engine.get_status().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Engine error: {}", e),
+        )
+    })?;
+    if status.state != EngineState::Running {
+        return Err((
+            StatusCode::CONFLICT,
+            "Engine not running; start monitoring first".to_string(),
+        ));
+    }
+
+    let freq_hz = req.freq_hz.unwrap_or(DEFAULT_TONE_FREQ_HZ);
+    let channel = req.channel.unwrap_or(status.signal_channels.0);
+    let duration_ms = req.duration_ms.unwrap_or(DEFAULT_TONE_DURATION_MS);
+    state
+        .engine
+        .play_test_tone(freq_hz, channel, duration_ms)
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("Failed to play test tone: {}", e),
+            )
+        })?;
+
+    Ok(Json(TestToneResponse {
+        freq_hz,
+        channel,
+        duration_ms,
     }))
 }
 
 /// GET /api/v1/remote-url
 ///
-/// Returns the remote access URL for accessing the dashboard from other devices.
+/// Returns the remote access URLs for accessing the dashboard from other
+/// devices: the LAN IPv4 address, the machine hostname and an IPv6 address
+/// where available.
 pub async fn get_remote_url(State(state): State<AppState>) -> Json<RemoteUrlResponse> {
-    let ip = local_ip_address::local_ip()
-        .map(|ip| ip.to_string())
-        .unwrap_or_else(|_| "localhost".to_string());
-    Json(RemoteUrlResponse {
-        url: format!("http://{}:{}", ip, state.config.port),
-    })
+    let ipv4 = local_ip_address::local_ip().ok();
+    let ipv6 = local_ip_address::local_ipv6().ok();
+    let hostname = hostname::get().ok().and_then(|h| h.into_string().ok());
+    Json(remote_urls(ipv4, ipv6, hostname.as_deref(), state.port()))
+}
+
+/// Build the candidate remote URLs from the resolved addresses
+///
+/// Link-local and loopback IPv6 addresses are skipped since browsers on
+/// other machines cannot use them. Falls back to localhost when nothing
+/// else is known.
+fn remote_urls(
+    ipv4: Option<std::net::IpAddr>,
+    ipv6: Option<std::net::IpAddr>,
+    hostname: Option<&str>,
+    port: u16,
+) -> RemoteUrlResponse {
+    let mut urls = Vec::new();
+    if let Some(ip) = ipv4 {
+        urls.push(format!("http://{}:{}", ip, port));
+    }
+    if let Some(name) = hostname.map(str::trim).filter(|name| !name.is_empty()) {
+        urls.push(format!("http://{}:{}", name, port));
+    }
+    if let Some(std::net::IpAddr::V6(ip)) = ipv6 {
+        let link_local = (ip.segments()[0] & 0xffc0) == 0xfe80;
+        if !link_local && !ip.is_loopback() {
+            urls.push(format!("http://[{}]:{}", ip, port));
+        }
+    }
+    if urls.is_empty() {
+        urls.push(format!("http://localhost:{}", port));
+    }
+    RemoteUrlResponse {
+        url: urls[0].clone(),
+        urls,
+    }
+}
+
+/// LAN address of this machine, if one can be determined
+fn local_ip() -> Option<String> {
+    local_ip_address::local_ip().ok().map(|ip| ip.to_string())
+}
+
+/// GET /api/v1/diagnostics
+///
+/// First-launch health checklist: audio host and devices, configured
+/// device presence, log directory and remote access address.
+pub async fn get_diagnostics(State(state): State<AppState>) -> Json<DiagnosticsResponse> {
+    let devices = state.engine.list_devices().await;
+    let configured = state
+        .engine
+        .get_status()
+        .await
+        .ok()
+        .and_then(|status| status.device_name);
+    let log_dir_writable = state.log_dir.as_deref().is_some_and(dir_writable);
+
+    Json(diagnostics_report(
+        devices.as_deref().map_err(|e| e.to_string()),
+        configured,
+        log_dir_writable,
+        local_ip(),
+    ))
+}
+
+/// Assemble the diagnostics report from the gathered checks
+fn diagnostics_report(
+    devices: Result<&[DeviceInfo], String>,
+    configured_device: Option<String>,
+    log_dir_writable: bool,
+    local_ip: Option<String>,
+) -> DiagnosticsResponse {
+    let (devices, error) = match devices {
+        Ok(devices) => (devices, None),
+        Err(e) => (&[][..], Some(e)),
+    };
+    let configured_device_found = configured_device
+        .as_deref()
+        .is_some_and(|name| devices.iter().any(|d| d.name == name));
+
+    DiagnosticsResponse {
+        asio_available: devices.iter().any(|d| d.host == "ASIO"),
+        device_count: devices.len(),
+        default_device: devices
+            .iter()
+            .find(|d| d.is_default)
+            .map(|d| d.name.clone()),
+        configured_device,
+        configured_device_found,
+        log_dir_writable,
+        local_ip,
+        error,
+    }
+}
+
+/// Whether a probe file can be created (and removed) in `dir`
+fn dir_writable(dir: &std::path::Path) -> bool {
+    let probe = dir.join(".audiotester-write-test");
+    let writable = std::fs::write(&probe, b"").is_ok();
+    let _ = std::fs::remove_file(&probe);
+    writable
+}
+
+/// POST /api/v1/recording
+///
+/// Pauses or resumes recording measurements into the statistics store.
+/// Audio keeps running and analysis continues, so resuming needs no device
+/// restart; the graphs simply freeze while paused.
+pub async fn set_recording(
+    State(state): State<AppState>,
+    Json(req): Json<RecordingRequest>,
+) -> Json<RecordingResponse> {
+    if state.is_recording_paused() != req.paused {
+        state.set_recording_paused(req.paused);
+        if req.paused {
+            tracing::info!("Stats recording paused");
+        } else {
+            tracing::info!("Stats recording resumed");
+        }
+    }
+    Json(RecordingResponse { paused: req.paused })
 }
 
 /// POST /api/v1/monitoring
@@ -388,16 +1319,138 @@ pub async fn toggle_monitoring(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(StatusResponse {
-        version: audiotester_core::VERSION.to_string(),
-        build_date: audiotester_core::BUILD_DATE.to_string(),
-        state: format!("{:?}", status.state),
-        device: status.device_name,
-        sample_rate: status.sample_rate,
-        monitoring: status.state == EngineState::Running,
+    Ok(Json(status_response(
+        status,
+        stats_last_saved(&state),
+        state.port(),
+    )))
+}
+
+/// Default time to wait for a valid measurement when probing (ms)
+const DEFAULT_PROBE_TIMEOUT_MS: u64 = 5000;
+
+/// Longest accepted probe timeout (ms)
+const MAX_PROBE_TIMEOUT_MS: u64 = 30000;
+
+/// Query parameters for POST /api/v1/probe
+#[derive(Deserialize, Default)]
+pub struct ProbeQuery {
+    /// How long to wait for a valid measurement (default: 5000, max: 30000)
+    pub timeout_ms: Option<u64>,
+}
+
+/// Single latency measurement returned by POST /api/v1/probe
+#[derive(Serialize, Debug)]
+pub struct ProbeResponse {
+    pub latency_ms: f64,
+    pub confidence: f32,
+    pub sample_rate: u32,
+}
+
+/// POST /api/v1/probe
+///
+/// Takes one latency measurement for a quick commissioning check. A stopped
+/// engine is started, probed until the first valid measurement and stopped
+/// again; while monitoring is running the latest valid measurement is
+/// returned. Responds 504 if no valid signal arrives within the timeout.
+pub async fn probe_latency(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<ProbeQuery>,
+) -> Result<Json<ProbeResponse>, (StatusCode, String)> {
+    let timeout_ms = query
+        .timeout_ms
+        .unwrap_or(DEFAULT_PROBE_TIMEOUT_MS)
+        .min(MAX_PROBE_TIMEOUT_MS);
+    let no_signal = || {
+        (
+            StatusCode::GATEWAY_TIMEOUT,
+            format!("No valid signal within {} ms", timeout_ms),
+        )
+    };
+
+    let status = state
+        .engine
+        .get_status()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if status.state == EngineState::Running {
+        let stats = state.stats_lock().stats().clone();
+        if stats.signal_lost
+            || !state
+                .config
+                .has_valid_signal(stats.current_latency, stats.last_confidence)
+        {
+            return Err(no_signal());
+        }
+        return Ok(Json(ProbeResponse {
+            latency_ms: stats.current_latency,
+            confidence: stats.last_confidence,
+            sample_rate: status.sample_rate,
+        }));
+    }
+
+    let (result, sample_rate) = state
+        .engine
+        .probe(std::time::Duration::from_millis(timeout_ms), |result| {
+            state
+                .config
+                .has_valid_signal(result.latency_ms, result.confidence)
+        })
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Probe failed: {}", e),
+            )
+        })?
+        .ok_or_else(no_signal)?;
+
+    Ok(Json(ProbeResponse {
+        latency_ms: result.latency_ms,
+        confidence: result.confidence,
+        sample_rate,
     }))
 }
 
+/// Sample rate used for the synthetic self-test
+const SELFTEST_SAMPLE_RATE: u32 = 48000;
+
+/// Loopback delay injected by the self-test (5ms at 48kHz)
+const SELFTEST_DELAY_FRAMES: usize = 240;
+
+/// Self-test result returned by POST /api/v1/selftest
+#[derive(Serialize, Debug)]
+pub struct SelfTestResponse {
+    pub ok: bool,
+    pub measured_ms: f64,
+    pub expected_ms: f64,
+}
+
+/// POST /api/v1/selftest
+///
+/// Runs a synthetic burst through a fresh detector and latency analyzer and
+/// checks the measured latency against the injected delay. Needs no audio
+/// hardware and does not touch the running engine.
+pub async fn run_selftest() -> Json<SelfTestResponse> {
+    let result = audiotester_core::audio::selftest::run_selftest(
+        SELFTEST_SAMPLE_RATE,
+        SELFTEST_DELAY_FRAMES,
+    );
+    if !result.ok {
+        tracing::warn!(
+            measured_ms = result.measured_ms,
+            expected_ms = result.expected_ms,
+            "Self-test latency mismatch"
+        );
+    }
+    Json(SelfTestResponse {
+        ok: result.ok,
+        measured_ms: result.measured_ms,
+        expected_ms: result.expected_ms,
+    })
+}
+
 /// Query parameters for GET /api/v1/loss-timeline
 #[derive(Deserialize)]
 pub struct LossTimelineQuery {
@@ -461,10 +1514,9 @@ pub async fn get_loss_timeline(
         })
         .max(10); // Clamp to minimum 10s (archive resolution)
 
-    let buckets = match state.stats.lock() {
-        Ok(store) => store.loss_timeline_data(range_secs, bucket_size),
-        Err(_) => Vec::new(),
-    };
+    let buckets = state
+        .stats_lock()
+        .loss_timeline_data(range_secs, bucket_size);
 
     let response_buckets: Vec<LossTimelineBucket> = buckets
         .into_iter()
@@ -477,6 +1529,477 @@ pub async fn get_loss_timeline(
     })
 }
 
+/// Number of worst buckets listed in the loss report
+const LOSS_REPORT_TOP_BUCKETS: usize = 5;
+
+/// Bucket size used for loss report aggregation (archive resolution)
+const LOSS_REPORT_BUCKET_SECS: i64 = 10;
+
+/// Query parameters for GET /api/v1/loss-timeline/report
+#[derive(Deserialize)]
+pub struct LossReportQuery {
+    /// Time range: "1h", "6h", "12h", "24h", "3d", "7d", "14d" (default: "24h")
+    pub range: Option<String>,
+}
+
+/// A bucket listed in the loss report
+#[derive(Serialize, Debug, PartialEq)]
+pub struct LossReportBucket {
+    /// Start of bucket (ISO 8601)
+    pub timestamp: String,
+    /// Total samples lost in this bucket
+    pub loss: u64,
+    /// Number of discrete loss events
+    pub events: u32,
+}
+
+/// Longest stretch of consecutive loss-free buckets
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CleanStretch {
+    /// Start of the stretch (ISO 8601)
+    pub start: String,
+    /// End of the stretch (ISO 8601)
+    pub end: String,
+    /// Length in seconds
+    pub duration_secs: i64,
+}
+
+/// Structured loss report for operations reporting
+#[derive(Serialize, Debug)]
+pub struct LossReportResponse {
+    /// Requested range label (e.g. "24h")
+    pub range: String,
+    /// Seconds of monitored time covered by archived buckets
+    pub monitored_secs: i64,
+    /// Total samples lost in range
+    pub total_loss: u64,
+    /// Total discrete loss events in range
+    pub total_events: u32,
+    /// Number of buckets with any loss
+    pub active_buckets: usize,
+    /// Worst buckets by samples lost (descending)
+    pub worst_buckets: Vec<LossReportBucket>,
+    /// Longest loss-free stretch (None when no clean bucket exists)
+    pub longest_clean: Option<CleanStretch>,
+    /// One-paragraph human-readable summary
+    pub summary: String,
+}
+
+/// Format a unix timestamp as ISO 8601
+fn unix_to_rfc3339(t: i64) -> String {
+    chrono::DateTime::from_timestamp(t, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Aggregate (timestamp, loss, events) buckets into a loss report
+fn build_loss_report(
+    range: &str,
+    buckets: &[(i64, u64, u32)],
+    bucket_size: i64,
+) -> LossReportResponse {
+    let total_loss: u64 = buckets.iter().map(|b| b.1).sum();
+    let total_events: u32 = buckets.iter().map(|b| b.2).sum();
+    let active_buckets = buckets.iter().filter(|b| b.1 > 0).count();
+
+    let mut worst: Vec<&(i64, u64, u32)> = buckets.iter().filter(|b| b.1 > 0).collect();
+    worst.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let worst_buckets: Vec<LossReportBucket> = worst
+        .into_iter()
+        .take(LOSS_REPORT_TOP_BUCKETS)
+        .map(|&(t, loss, events)| LossReportBucket {
+            timestamp: unix_to_rfc3339(t),
+            loss,
+            events,
+        })
+        .collect();
+
+    // Longest run of contiguous clean buckets (a missing bucket breaks the run)
+    let mut best: Option<(i64, i64)> = None;
+    let mut current: Option<(i64, i64)> = None;
+    for &(t, loss, _) in buckets {
+        if loss > 0 {
+            current = None;
+            continue;
+        }
+        current = match current {
+            Some((start, end)) if end == t => Some((start, t + bucket_size)),
+            _ => Some((t, t + bucket_size)),
+        };
+        if let Some((start, end)) = current {
+            if best.is_none_or(|(bs, be)| end - start > be - bs) {
+                best = Some((start, end));
+            }
+        }
+    }
+    let longest_clean = best.map(|(start, end)| CleanStretch {
+        start: unix_to_rfc3339(start),
+        end: unix_to_rfc3339(end),
+        duration_secs: end - start,
+    });
+
+    let monitored_secs = buckets.len() as i64 * bucket_size;
+    let mut summary = format!(
+        "Over the last {} ({} monitored), {} samples were lost in {} events across {} of {} buckets.",
+        range,
+        format_duration_secs(monitored_secs),
+        total_loss,
+        total_events,
+        active_buckets,
+        buckets.len()
+    );
+    if let Some(first) = worst_buckets.first() {
+        summary.push_str(&format!(
+            " Worst bucket: {} samples at {}.",
+            first.loss, first.timestamp
+        ));
+    }
+    if let Some(ref clean) = longest_clean {
+        summary.push_str(&format!(
+            " Longest clean stretch: {} starting {}.",
+            format_duration_secs(clean.duration_secs),
+            clean.start
+        ));
+    }
+
+    LossReportResponse {
+        range: range.to_string(),
+        monitored_secs,
+        total_loss,
+        total_events,
+        active_buckets,
+        worst_buckets,
+        longest_clean,
+        summary,
+    }
+}
+
+/// Format seconds as a compact duration (e.g. "2h 5m", "45s")
+fn format_duration_secs(secs: i64) -> String {
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 {
+        format!("{}h {}m", h, m)
+    } else if m > 0 {
+        format!("{}m {}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+/// GET /api/v1/loss-timeline/report
+///
+/// Returns a structured summary of loss over the range: totals, the worst
+/// buckets and the longest clean stretch, for automated operations reports.
+pub async fn get_loss_report(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<LossReportQuery>,
+) -> Json<LossReportResponse> {
+    let range_str = query.range.as_deref().unwrap_or("24h");
+
+    let range_secs: i64 = match range_str {
+        "1h" => 3600,
+        "6h" => 21600,
+        "12h" => 43200,
+        "3d" => 259200,
+        "7d" => 604800,
+        "14d" => 1209600,
+        _ => 86400, // default 24h
+    };
+
+    let buckets = state
+        .stats_lock()
+        .loss_timeline_data(range_secs, LOSS_REPORT_BUCKET_SECS);
+
+    Json(build_loss_report(
+        range_str,
+        &buckets,
+        LOSS_REPORT_BUCKET_SECS,
+    ))
+}
+
+/// Query parameters for GET /api/v1/annotations
+#[derive(Deserialize)]
+pub struct AnnotationsQuery {
+    /// Time range: "1h", "6h", "12h", "24h", "3d", "7d", "14d" (default: "1h")
+    pub range: Option<String>,
+}
+
+/// A chart marker for a disconnection or loss event
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Annotation {
+    /// When the event happened (ISO 8601)
+    pub timestamp: String,
+    /// "disconnection" or "loss"
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Human-readable marker text
+    pub label: String,
+}
+
+/// Annotations response
+#[derive(Serialize)]
+pub struct AnnotationsResponse {
+    /// Events in the range, oldest first
+    pub annotations: Vec<Annotation>,
+}
+
+/// Merge disconnection and loss events at or after `cutoff` into chart annotations
+fn build_annotations(
+    disconnections: &[audiotester_core::stats::store::DisconnectionEvent],
+    losses: &std::collections::VecDeque<audiotester_core::stats::store::LossEvent>,
+    cutoff: chrono::DateTime<chrono::Utc>,
+) -> Vec<Annotation> {
+    let mut events: Vec<(chrono::DateTime<chrono::Utc>, &'static str, String)> = disconnections
+        .iter()
+        .filter(|e| e.timestamp >= cutoff)
+        .map(|e| {
+            let label = if e.reconnected {
+                format!(
+                    "Disconnected {:.1}s, reconnected",
+                    e.duration_ms as f64 / 1000.0
+                )
+            } else {
+                "Disconnected, reconnect failed".to_string()
+            };
+            (e.timestamp, "disconnection", label)
+        })
+        .chain(
+            losses
+                .iter()
+                .filter(|e| e.timestamp >= cutoff)
+                .map(|e| (e.timestamp, "loss", format!("Lost {} samples", e.count))),
+        )
+        .collect();
+    events.sort_by_key(|(timestamp, _, _)| *timestamp);
+
+    events
+        .into_iter()
+        .map(|(timestamp, kind, label)| Annotation {
+            timestamp: timestamp.to_rfc3339(),
+            kind,
+            label,
+        })
+        .collect()
+}
+
+/// GET /api/v1/annotations
+///
+/// Returns disconnection and loss events in the range as chart markers, so
+/// latency spikes can be correlated with loss visually.
+pub async fn get_annotations(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<AnnotationsQuery>,
+) -> Json<AnnotationsResponse> {
+    let range_secs: i64 = match query.range.as_deref().unwrap_or("1h") {
+        "6h" => 21600,
+        "12h" => 43200,
+        "24h" => 86400,
+        "3d" => 259200,
+        "7d" => 604800,
+        "14d" => 1209600,
+        _ => 3600, // default 1h
+    };
+    let cutoff = chrono::Utc::now() - chrono::Duration::seconds(range_secs);
+
+    let annotations = {
+        let store = state.stats_lock();
+        build_annotations(store.disconnection_events(), store.loss_events(), cutoff)
+    };
+
+    Json(AnnotationsResponse { annotations })
+}
+
+/// GET /api/v1/detector
+///
+/// Returns the burst detector's envelope, noise floor, peak SNR and
+/// detection threshold, to tell a weak signal from a noisy one.
+pub async fn get_detector(
+    State(state): State<AppState>,
+) -> Result<Json<audiotester_core::audio::detector::DetectorDiagnostics>, (StatusCode, String)> {
+    state
+        .engine
+        .get_detector_diagnostics()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .ok_or((StatusCode::CONFLICT, "Engine not running".to_string()))
+}
+
+/// GET /api/v1/levels
+///
+/// Returns linear peak and RMS of the burst (ch0) and counter (ch1) inputs
+/// over the last callback buffer, to diagnose "no signal".
+pub async fn get_levels(
+    State(state): State<AppState>,
+) -> Result<Json<audiotester_core::audio::levels::InputLevels>, (StatusCode, String)> {
+    state
+        .engine
+        .get_levels()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map(Json)
+        .ok_or((StatusCode::CONFLICT, "Engine not running".to_string()))
+}
+
+/// A single ASIO driver event
+#[derive(Serialize)]
+pub struct AsioEventResponse {
+    pub timestamp: String,
+    pub kind: audiotester_core::stats::store::AsioEventKind,
+}
+
+/// ASIO driver event list
+#[derive(Serialize)]
+pub struct AsioEventsResponse {
+    /// Events, oldest first
+    pub events: Vec<AsioEventResponse>,
+}
+
+/// GET /api/v1/asio-events
+///
+/// Returns driver resets and virtual driver restarts seen by the monitoring
+/// loop, to show how often e.g. VBMatrix restarts its engine.
+pub async fn get_asio_events(State(state): State<AppState>) -> Json<AsioEventsResponse> {
+    let events = state
+        .stats_lock()
+        .asio_events()
+        .iter()
+        .map(|e| AsioEventResponse {
+            timestamp: e.timestamp.to_rfc3339(),
+            kind: e.kind,
+        })
+        .collect();
+    Json(AsioEventsResponse { events })
+}
+
+/// A single latency spike
+#[derive(Serialize)]
+pub struct SpikeEventResponse {
+    pub timestamp: String,
+    pub latency_ms: f64,
+    pub baseline_ms: f64,
+}
+
+/// Latency spike list
+#[derive(Serialize)]
+pub struct SpikesResponse {
+    /// Spikes, oldest first
+    pub spikes: Vec<SpikeEventResponse>,
+}
+
+/// GET /api/v1/summary
+///
+/// One-shot summary of the monitoring session. The statistics are kept
+/// after monitoring stops, so this still reports the finished session.
+pub async fn get_summary(State(state): State<AppState>) -> Json<audiotester_core::SessionSummary> {
+    Json(state.stats_lock().session_summary())
+}
+
+/// Where the baseline is stored, or 404 when persistence is disabled
+fn baseline_path(state: &AppState) -> Result<&std::path::Path, (StatusCode, String)> {
+    state.config.baseline_path.as_deref().ok_or((
+        StatusCode::NOT_FOUND,
+        "Baseline storage not configured".to_string(),
+    ))
+}
+
+/// POST /api/v1/baseline
+///
+/// Saves the current latency/loss profile as the reference for
+/// `GET /api/v1/baseline/diff`, replacing any earlier baseline.
+pub async fn save_baseline(
+    State(state): State<AppState>,
+) -> Result<Json<audiotester_core::Baseline>, (StatusCode, String)> {
+    let path = baseline_path(&state)?;
+    let baseline = state.stats_lock().baseline();
+    baseline
+        .save_to_path(path)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    tracing::info!(
+        path = %path.display(),
+        avg_latency_ms = baseline.avg_latency_ms,
+        loss_rate_per_min = baseline.loss_rate_per_min,
+        "Baseline saved"
+    );
+    Ok(Json(baseline))
+}
+
+/// GET /api/v1/baseline/diff
+///
+/// Compares the current latency and loss rate against the saved baseline,
+/// to catch regressions after a firmware or driver update.
+pub async fn get_baseline_diff(
+    State(state): State<AppState>,
+) -> Result<Json<audiotester_core::BaselineDiff>, (StatusCode, String)> {
+    let path = baseline_path(&state)?;
+    if !path.exists() {
+        return Err((StatusCode::NOT_FOUND, "No baseline saved".to_string()));
+    }
+    let baseline = audiotester_core::Baseline::load_from_path(path)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let current = state.stats_lock().baseline();
+    Ok(Json(baseline.diff(&current)))
+}
+
+/// Preferences file from the config, or 404 when preferences are disabled
+fn preferences_path(state: &AppState) -> Result<&std::path::Path, (StatusCode, String)> {
+    state.config.preferences_path.as_deref().ok_or((
+        StatusCode::NOT_FOUND,
+        "Preferences storage not configured".to_string(),
+    ))
+}
+
+/// GET /api/v1/preferences
+///
+/// Returns the saved dashboard preferences (defaults if none were saved).
+pub async fn get_preferences(
+    State(state): State<AppState>,
+) -> Result<Json<Preferences>, (StatusCode, String)> {
+    let path = preferences_path(&state)?;
+    Preferences::load_from_path(path)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// PUT /api/v1/preferences
+///
+/// Replaces the dashboard preferences. Omitted or null fields reset to the
+/// default (e.g. auto-scaling for the latency chart bounds).
+pub async fn set_preferences(
+    State(state): State<AppState>,
+    Json(prefs): Json<Preferences>,
+) -> Result<Json<Preferences>, (StatusCode, String)> {
+    let path = preferences_path(&state)?;
+    prefs.validate().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    prefs
+        .save_to_path(path)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    tracing::info!(
+        latency_y_min = ?prefs.latency_y_min,
+        latency_y_max = ?prefs.latency_y_max,
+        "Preferences saved"
+    );
+    Ok(Json(prefs))
+}
+
+/// GET /api/v1/spikes
+///
+/// Returns measurements that jumped well above the recent average latency,
+/// to flag glitches that do not register as sample loss.
+pub async fn get_spikes(State(state): State<AppState>) -> Json<SpikesResponse> {
+    let spikes = state
+        .stats_lock()
+        .spike_events()
+        .iter()
+        .map(|e| SpikeEventResponse {
+            timestamp: e.timestamp.to_rfc3339(),
+            latency_ms: e.latency_ms,
+            baseline_ms: e.baseline_ms,
+        })
+        .collect();
+    Json(SpikesResponse { spikes })
+}
+
 /// Query parameters for GET /api/v1/latency-timeline
 #[derive(Deserialize)]
 pub struct LatencyTimelineQuery {
@@ -541,10 +2064,9 @@ pub async fn get_latency_timeline(
         })
         .max(10);
 
-    let buckets = match state.stats.lock() {
-        Ok(store) => store.latency_timeline_data(range_secs, bucket_size),
-        Err(_) => Vec::new(),
-    };
+    let buckets = state
+        .stats_lock()
+        .latency_timeline_data(range_secs, bucket_size);
 
     let response_buckets: Vec<LatencyTimelineBucket> = buckets
         .into_iter()
@@ -557,93 +2079,1452 @@ pub async fn get_latency_timeline(
     })
 }
 
-/// Query parameters for GET /api/v1/logs
+/// Default number of points returned by GET /api/v1/latency
+const DEFAULT_LATENCY_POINTS: usize = 500;
+
+/// Most points GET /api/v1/latency will return
+const MAX_LATENCY_POINTS: usize = 10000;
+
+/// Query parameters for GET /api/v1/latency
 #[derive(Deserialize)]
-pub struct LogsQuery {
-    /// Number of lines from end (default 200)
-    pub tail: Option<usize>,
-    /// Filter by keyword (optional)
-    pub filter: Option<String>,
+pub struct LatencyQuery {
+    /// Target number of points (default: 500, max: 10000)
+    pub points: Option<usize>,
 }
 
-/// GET /api/v1/logs
+/// Downsampled latency series response
+#[derive(Serialize)]
+pub struct LatencyResponse {
+    /// (time_offset_seconds, latency_ms) pairs, oldest first
+    pub points: Vec<(f64, f64)>,
+}
+
+/// GET /api/v1/latency
 ///
-/// Returns recent log file content for diagnostic analysis.
-pub async fn get_logs(
+/// Returns the archive plus recent history reduced with LTTB to at most
+/// `points` entries, so long ranges stay cheap to chart without losing peaks.
+pub async fn get_latency(
     State(state): State<AppState>,
-    axum::extract::Query(query): axum::extract::Query<LogsQuery>,
-) -> Result<String, (StatusCode, String)> {
-    let log_dir = state
-        .log_dir
-        .as_ref()
-        .ok_or((StatusCode::NOT_FOUND, "Logging not configured".to_string()))?;
-
-    // Find the most recent log file
-    let mut entries: Vec<_> = std::fs::read_dir(log_dir)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .to_str()
-                .map(|s| s.contains("audiotester.log"))
-                .unwrap_or(false)
-        })
-        .collect();
-    entries.sort_by_key(|e| std::cmp::Reverse(e.metadata().ok().and_then(|m| m.modified().ok())));
+    axum::extract::Query(query): axum::extract::Query<LatencyQuery>,
+) -> Json<LatencyResponse> {
+    let target = query
+        .points
+        .unwrap_or(DEFAULT_LATENCY_POINTS)
+        .min(MAX_LATENCY_POINTS);
 
-    let log_file = entries
-        .first()
-        .ok_or((StatusCode::NOT_FOUND, "No log files found".to_string()))?;
+    let points = state.stats_lock().latency_downsampled(target);
 
-    let content = std::fs::read_to_string(log_file.path())
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Json(LatencyResponse { points })
+}
 
-    let tail = query.tail.unwrap_or(200);
-    let lines: Vec<&str> = content.lines().collect();
-    let start = lines.len().saturating_sub(tail);
-    let mut result: Vec<&str> = lines[start..].to_vec();
+/// Default jitter histogram bucket width (ms)
+const DEFAULT_JITTER_BUCKET_MS: f64 = 0.1;
 
-    if let Some(ref filter) = query.filter {
-        result.retain(|line| line.contains(filter.as_str()));
-    }
+/// Query parameters for GET /api/v1/jitter-histogram
+#[derive(Deserialize)]
+pub struct JitterHistogramQuery {
+    /// Bucket width in milliseconds (default: 0.1)
+    pub bucket_ms: Option<f64>,
+}
 
-    Ok(result.join("\n"))
+/// A single bucket in the jitter histogram response
+#[derive(Serialize)]
+pub struct JitterHistogramBucket {
+    /// Lower bound of the bucket (ms)
+    pub start_ms: f64,
+    /// Number of consecutive-measurement deltas in this bucket
+    pub count: u64,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Jitter histogram response
+#[derive(Serialize)]
+pub struct JitterHistogramResponse {
+    /// Bucket width in milliseconds
+    pub bucket_ms: f64,
+    /// Largest difference between consecutive measurements since reset (ms)
+    pub max_jitter_ms: f64,
+    /// Buckets from 0 ms upwards
+    pub buckets: Vec<JitterHistogramBucket>,
+}
 
-    #[test]
-    fn test_status_response_serializes() {
-        let resp = StatusResponse {
-            version: "0.1.5".to_string(),
-            build_date: "2026-02-15".to_string(),
-            state: "Stopped".to_string(),
-            device: None,
-            sample_rate: 96000,
-            monitoring: false,
-        };
-        let json = serde_json::to_string(&resp).unwrap();
-        assert!(json.contains("\"version\":\"0.1.5\""));
-        assert!(json.contains("\"build_date\":\"2026-02-15\""));
+/// GET /api/v1/jitter-histogram
+///
+/// Returns the distribution of absolute differences between consecutive
+/// latency measurements, binned by `bucket_ms`.
+pub async fn get_jitter_histogram(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<JitterHistogramQuery>,
+) -> Result<Json<JitterHistogramResponse>, (StatusCode, String)> {
+    let bucket_ms = query.bucket_ms.unwrap_or(DEFAULT_JITTER_BUCKET_MS);
+    if !bucket_ms.is_finite() || bucket_ms <= 0.0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid bucket_ms: {} (must be > 0)", bucket_ms),
+        ));
     }
 
-    #[test]
-    fn test_stats_response_serializes() {
-        let resp = StatsResponse {
+    let (histogram, max_jitter_ms) = {
+        let store = state.stats_lock();
+        (
+            store.jitter_histogram(bucket_ms),
+            store.stats().max_jitter_ms,
+        )
+    };
+
+    Ok(Json(JitterHistogramResponse {
+        bucket_ms,
+        max_jitter_ms,
+        buckets: histogram
+            .into_iter()
+            .map(|(start_ms, count)| JitterHistogramBucket { start_ms, count })
+            .collect(),
+    }))
+}
+
+/// Query parameters for GET /api/v1/export/latency.csv
+#[derive(Deserialize)]
+pub struct LatencyExportQuery {
+    /// Time range: "1h", "6h", "24h" (default: everything retained)
+    pub range: Option<String>,
+}
+
+/// Parse a `1h`/`6h`/`24h` range of the latency export endpoints into seconds
+fn parse_export_range(range: &str) -> Result<i64, (StatusCode, String)> {
+    match range {
+        "1h" => Ok(3600),
+        "6h" => Ok(21600),
+        "24h" => Ok(86400),
+        other => Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid range: {} (must be 1h, 6h or 24h)", other),
+        )),
+    }
+}
+
+/// Latency archive followed by the recent history as `(timestamp, ms)`, oldest first
+///
+/// Only measurements at or after `cutoff` are returned (all when None).
+fn latency_rows(
+    state: &AppState,
+    cutoff: Option<chrono::DateTime<chrono::Utc>>,
+) -> Vec<(chrono::DateTime<chrono::Utc>, f64)> {
+    let store = state.stats_lock();
+    let history = store.latency_history();
+    // The archive down-samples the same measurements, so only use it before the history starts
+    let history_start = history.front().map(|m| m.timestamp);
+    store
+        .latency_archive()
+        .iter()
+        .filter(|m| history_start.is_none_or(|start| m.timestamp < start))
+        .chain(history.iter())
+        .filter(|m| cutoff.is_none_or(|c| m.timestamp >= c))
+        .map(|m| (m.timestamp, m.value))
+        .collect()
+}
+
+/// GET /api/v1/export/latency.csv
+///
+/// Downloads the latency archive followed by the full-resolution recent
+/// history as CSV (`timestamp_iso,latency_ms`), oldest first.
+pub async fn export_latency_csv(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<LatencyExportQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let range_secs = query.range.as_deref().map(parse_export_range).transpose()?;
+    let cutoff = range_secs.map(|secs| chrono::Utc::now() - chrono::Duration::seconds(secs));
+
+    // Copy the rows out so the lock is released before formatting
+    let rows = latency_rows(&state, cutoff);
+
+    let mut csv = String::with_capacity(32 * (rows.len() + 1));
+    csv.push_str("timestamp_iso,latency_ms\n");
+    for (timestamp, value) in rows {
+        csv.push_str(&format!("{},{:.3}\n", timestamp.to_rfc3339(), value));
+    }
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"audiotester-latency.csv\"",
+            ),
+        ],
+        csv,
+    ))
+}
+
+/// Query parameters for GET /api/v1/chart.png
+#[cfg(feature = "chart")]
+#[derive(Deserialize)]
+pub struct ChartQuery {
+    /// Time range: "1h", "6h", "24h" (default: "1h")
+    pub range: Option<String>,
+}
+
+/// GET /api/v1/chart.png
+///
+/// Renders the latency history over the range to a PNG, captioned with the
+/// device and time range, for embedding in status emails.
+#[cfg(feature = "chart")]
+pub async fn get_chart_png(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<ChartQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let range_secs = parse_export_range(query.range.as_deref().unwrap_or("1h"))?;
+    let to = chrono::Utc::now();
+    let from = to - chrono::Duration::seconds(range_secs);
+    let rows = latency_rows(&state, Some(from));
+    let device = state
+        .engine
+        .get_status()
+        .await
+        .ok()
+        .and_then(|status| status.device_name)
+        .unwrap_or_else(|| "No device".to_string());
+
+    // Rasterizing takes a few milliseconds; keep it off the async workers
+    let png = tokio::task::spawn_blocking(move || {
+        crate::chart::render_latency_png(&rows, &device, from, to)
+    })
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+    .map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to render chart: {:#}", e),
+        )
+    })?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "image/png"),
+            (header::CACHE_CONTROL, "no-store"),
+        ],
+        png,
+    ))
+}
+
+/// Query parameters for GET /api/v1/logs
+#[derive(Deserialize)]
+pub struct LogsQuery {
+    /// Number of lines from end (default 200)
+    pub tail: Option<usize>,
+    /// Filter by keyword (optional)
+    pub filter: Option<String>,
+    /// Log file name from `GET /api/v1/logs/files` (default: newest)
+    pub file: Option<String>,
+}
+
+/// One log file in the log directory
+#[derive(Serialize)]
+pub struct LogFileInfo {
+    /// File name, usable as `?file=` for `GET /api/v1/logs`
+    pub name: String,
+    /// Size in bytes
+    pub size_bytes: u64,
+    /// Last modification time (RFC 3339)
+    pub modified: Option<String>,
+}
+
+/// Response for GET /api/v1/logs/files
+#[derive(Serialize)]
+pub struct LogFilesResponse {
+    /// Log files, newest first
+    pub files: Vec<LogFileInfo>,
+}
+
+/// Prefix shared by the daily rotated log files
+const LOG_FILE_PREFIX: &str = "audiotester.log";
+
+/// List the `audiotester.log*` files in `log_dir`, newest first
+fn list_log_files(log_dir: &std::path::Path) -> std::io::Result<Vec<LogFileInfo>> {
+    let mut files: Vec<(Option<std::time::SystemTime>, LogFileInfo)> = std::fs::read_dir(log_dir)?
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let metadata = e.metadata().ok().filter(|m| m.is_file())?;
+            name.starts_with(LOG_FILE_PREFIX).then(|| {
+                let modified = metadata.modified().ok();
+                let info = LogFileInfo {
+                    name,
+                    size_bytes: metadata.len(),
+                    modified: modified
+                        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+                };
+                (modified, info)
+            })
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    Ok(files.into_iter().map(|(_, info)| info).collect())
+}
+
+/// Resolve a `?file=` log file name to a path inside `log_dir`
+///
+/// Only plain `audiotester.log*` file names are accepted, and the resolved
+/// path must stay inside the log directory, so requests such as
+/// `?file=../../secret` cannot read other files.
+fn resolve_log_file(
+    log_dir: &std::path::Path,
+    name: &str,
+) -> Result<std::path::PathBuf, (StatusCode, String)> {
+    let invalid = || {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid log file: {}", name),
+        )
+    };
+    let plain_name = std::path::Path::new(name)
+        .file_name()
+        .is_some_and(|file_name| file_name == name);
+    if !plain_name || !name.starts_with(LOG_FILE_PREFIX) {
+        return Err(invalid());
+    }
+
+    let path = log_dir.join(name);
+    let not_found = |_| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("Log file not found: {}", name),
+        )
+    };
+    let canonical_dir = log_dir.canonicalize().map_err(not_found)?;
+    let canonical = path.canonicalize().map_err(not_found)?;
+    if !canonical.starts_with(&canonical_dir) {
+        return Err(invalid());
+    }
+    Ok(canonical)
+}
+
+/// GET /api/v1/logs/files
+///
+/// Lists the rotated log files with sizes and dates, newest first.
+pub async fn get_log_files(
+    State(state): State<AppState>,
+) -> Result<Json<LogFilesResponse>, (StatusCode, String)> {
+    let log_dir = state
+        .log_dir
+        .as_ref()
+        .ok_or((StatusCode::NOT_FOUND, "Logging not configured".to_string()))?;
+    let files =
+        list_log_files(log_dir).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(LogFilesResponse { files }))
+}
+
+/// Response for GET /api/v1/incidents
+#[derive(Serialize)]
+pub struct IncidentsResponse {
+    /// Captured incident files, newest first
+    pub incidents: Vec<crate::incident::IncidentFileInfo>,
+}
+
+/// GET /api/v1/incidents
+///
+/// Lists the `incident-*.json` context captures written on loss spikes.
+pub async fn get_incidents(
+    State(state): State<AppState>,
+) -> Result<Json<IncidentsResponse>, (StatusCode, String)> {
+    let dir = state.config.incident_dir.as_ref().ok_or((
+        StatusCode::NOT_FOUND,
+        "Incident capture not configured".to_string(),
+    ))?;
+    let incidents = crate::incident::list_incidents(dir)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(IncidentsResponse { incidents }))
+}
+
+/// Maximum number of bytes read from the end of a log file
+///
+/// Daily log files can grow to tens of megabytes on a busy day; the
+/// endpoint only ever returns the last few hundred lines, so there is no
+/// need to load the whole file into memory.
+const MAX_LOG_TAIL_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Read up to `max_bytes` from the end of a log file
+///
+/// Invalid UTF-8 (e.g. from device names written by drivers) is replaced
+/// with U+FFFD instead of failing the whole request. When the read starts
+/// mid-file, the first (likely partial) line is dropped.
+fn read_log_tail(path: &std::path::Path, max_bytes: u64) -> std::io::Result<String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(max_bytes);
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut bytes = Vec::with_capacity((len - start) as usize);
+    file.read_to_end(&mut bytes)?;
+
+    let content = String::from_utf8_lossy(&bytes);
+    if start > 0 {
+        match content.find('\n') {
+            Some(pos) => Ok(content[pos + 1..].to_string()),
+            None => Ok(String::new()),
+        }
+    } else {
+        Ok(content.into_owned())
+    }
+}
+
+/// GET /api/v1/logs
+///
+/// Returns recent log file content for diagnostic analysis, from the newest
+/// log file or the one named by `?file=`.
+pub async fn get_logs(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<LogsQuery>,
+) -> Result<String, (StatusCode, String)> {
+    let log_dir = state
+        .log_dir
+        .as_ref()
+        .ok_or((StatusCode::NOT_FOUND, "Logging not configured".to_string()))?;
+
+    let log_file = match query.file.as_deref() {
+        Some(name) => resolve_log_file(log_dir, name)?,
+        None => {
+            // Most recent log file
+            let newest = list_log_files(log_dir)
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+                .into_iter()
+                .next()
+                .ok_or((StatusCode::NOT_FOUND, "No log files found".to_string()))?;
+            log_dir.join(newest.name)
+        }
+    };
+
+    let content = read_log_tail(&log_file, MAX_LOG_TAIL_BYTES)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let tail = query.tail.unwrap_or(200);
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(tail);
+    let mut result: Vec<&str> = lines[start..].to_vec();
+
+    if let Some(ref filter) = query.filter {
+        result.retain(|line| line.contains(filter.as_str()));
+    }
+
+    Ok(result.join("\n"))
+}
+
+/// Query parameters for GET /api/v1/events/log
+#[derive(Deserialize)]
+pub struct EventLogQuery {
+    /// Minimum level: "error", "warn", "info", "debug" or "trace" (default: all)
+    pub level: Option<String>,
+}
+
+/// Structured event log response
+#[derive(Serialize)]
+pub struct EventLogResponse {
+    /// Events, oldest first
+    pub events: Vec<LogEvent>,
+    /// Events dropped by the capture rate limit since start
+    pub dropped: u64,
+}
+
+/// GET /api/v1/events/log
+///
+/// Returns recent log events as JSON from memory, filtered to `level` and
+/// more severe.
+pub async fn get_event_log(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<EventLogQuery>,
+) -> Result<Json<EventLogResponse>, (StatusCode, String)> {
+    let min_level = query
+        .level
+        .map(|level| {
+            level
+                .parse::<tracing::Level>()
+                .map_err(|_| (StatusCode::BAD_REQUEST, format!("Invalid level: {}", level)))
+        })
+        .transpose()?;
+    Ok(Json(EventLogResponse {
+        events: state.event_log.query(min_level),
+        dropped: state.event_log.dropped(),
+    }))
+}
+
+/// Number of log lines included in a support snapshot
+const SNAPSHOT_LOG_LINES: usize = 500;
+
+/// Support snapshot bundling everything needed to diagnose remotely
+#[derive(Serialize)]
+pub struct SnapshotResponse {
+    pub version: String,
+    pub build_date: String,
+    pub session_id: String,
+    /// When the snapshot was taken (ISO 8601)
+    pub generated_at: String,
+    pub status: StatusResponse,
+    /// Effective configuration, as from `GET /api/v1/config/effective`
+    pub config: EffectiveConfigResponse,
+    /// Active log filter directives (if runtime log level is configured)
+    pub log_filter: Option<String>,
+    pub stats: StatsResponse,
+    /// Startup checklist, as from `GET /api/v1/diagnostics`
+    pub diagnostics: DiagnosticsResponse,
+    /// Structured log events, as from `GET /api/v1/events/log`
+    pub events: EventLogResponse,
+    /// Driver resets and restarts, as from `GET /api/v1/asio-events`
+    pub asio_events: AsioEventsResponse,
+    /// Recent log lines (None if logging is not configured)
+    pub logs: Option<String>,
+}
+
+/// GET /api/v1/snapshot
+///
+/// Bundles status, full stats, effective config, diagnostics, engine events
+/// and recent logs into a single JSON download for support handoff.
+pub async fn get_snapshot(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let status = get_status(State(state.clone())).await?.0;
+    let engine_status = state
+        .engine
+        .get_status()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let config = effective_config(&state, engine_status, std::env::vars());
+    let Json(stats) = get_stats(State(state.clone())).await;
+    let Json(diagnostics) = get_diagnostics(State(state.clone())).await;
+    let Json(asio_events) = get_asio_events(State(state.clone())).await;
+    let events = EventLogResponse {
+        events: state.event_log.query(None),
+        dropped: state.event_log.dropped(),
+    };
+    let logs = get_logs(
+        State(state.clone()),
+        axum::extract::Query(LogsQuery {
+            tail: Some(SNAPSHOT_LOG_LINES),
+            filter: None,
+            file: None,
+        }),
+    )
+    .await
+    .ok();
+
+    let log_filter = state
+        .log_filter
+        .as_ref()
+        .and_then(|h| h.with_current(|f| f.to_string()).ok());
+
+    let now = chrono::Utc::now();
+    let snapshot = SnapshotResponse {
+        version: audiotester_core::VERSION.to_string(),
+        build_date: audiotester_core::BUILD_DATE.to_string(),
+        session_id: state.session_id.clone(),
+        generated_at: now.to_rfc3339(),
+        status,
+        config,
+        log_filter,
+        stats,
+        diagnostics,
+        events,
+        asio_events,
+        logs,
+    };
+
+    let disposition = format!(
+        "attachment; filename=\"audiotester-snapshot-{}.json\"",
+        now.format("%Y%m%d-%H%M%S")
+    );
+    Ok(([(header::CONTENT_DISPOSITION, disposition)], Json(snapshot)))
+}
+
+/// Log level update request
+#[derive(Deserialize)]
+pub struct LogLevelRequest {
+    /// Target module (e.g. "audiotester_core"); omitted for the default level
+    pub target: Option<String>,
+    /// Level: trace, debug, info, warn, error or off
+    pub level: String,
+}
+
+/// Current log filter response
+#[derive(Serialize)]
+pub struct LogLevelResponse {
+    /// Active filter directives (EnvFilter syntax)
+    pub filter: String,
+}
+
+/// Replace (or add) the directive for `target` in a comma-separated filter
+///
+/// A `None` target sets the default level (a directive without `=`).
+pub(crate) fn merge_log_directive(current: &str, target: Option<&str>, level: &str) -> String {
+    let mut directives: Vec<String> = current
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .filter(|d| match target {
+            Some(t) => d.split('=').next() != Some(t),
+            None => d.contains('='),
+        })
+        .map(str::to_string)
+        .collect();
+
+    match target {
+        Some(t) => directives.push(format!("{}={}", t, level)),
+        None => directives.insert(0, level.to_string()),
+    }
+    directives.join(",")
+}
+
+/// GET /api/v1/log-level
+///
+/// Returns the active tracing filter directives.
+pub async fn get_log_level(
+    State(state): State<AppState>,
+) -> Result<Json<LogLevelResponse>, (StatusCode, String)> {
+    let handle = state.log_filter.as_ref().ok_or((
+        StatusCode::NOT_FOUND,
+        "Runtime log level not configured".to_string(),
+    ))?;
+
+    let filter = handle
+        .with_current(|f| f.to_string())
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(Json(LogLevelResponse { filter }))
+}
+
+/// POST /api/v1/log-level
+///
+/// Adjusts the tracing filter at runtime without restarting, so verbosity
+/// can be raised while reproducing an issue on a live measurement.
+pub async fn set_log_level(
+    State(state): State<AppState>,
+    Json(req): Json<LogLevelRequest>,
+) -> Result<Json<LogLevelResponse>, (StatusCode, String)> {
+    let handle = state.log_filter.as_ref().ok_or((
+        StatusCode::NOT_FOUND,
+        "Runtime log level not configured".to_string(),
+    ))?;
+
+    let level = req.level.trim().to_ascii_lowercase();
+    if !["trace", "debug", "info", "warn", "error", "off"].contains(&level.as_str()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Invalid level: {} (must be trace, debug, info, warn, error or off)",
+                req.level
+            ),
+        ));
+    }
+    let target = req
+        .target
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty());
+    if let Some(t) = target {
+        if t.contains([',', '=', '[', ']']) {
+            return Err((StatusCode::BAD_REQUEST, format!("Invalid target: {}", t)));
+        }
+    }
+
+    let current = handle
+        .with_current(|f| f.to_string())
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let merged = merge_log_directive(&current, target, &level);
+
+    let new_filter = tracing_subscriber::EnvFilter::try_new(&merged)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid filter: {}", e)))?;
+    handle
+        .reload(new_filter)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    tracing::info!(filter = %merged, "Log filter updated");
+    Ok(Json(LogLevelResponse { filter: merged }))
+}
+
+/// Longest accepted debug trace (seconds)
+pub const MAX_DEBUG_TRACE_SECS: u64 = 600;
+
+/// Debug trace request
+#[derive(Deserialize)]
+pub struct DebugTraceRequest {
+    /// How long to keep `audiotester_core` at TRACE (1-600 seconds)
+    pub duration_secs: u64,
+}
+
+/// Debug trace response
+#[derive(Serialize)]
+pub struct DebugTraceResponse {
+    /// Filter active during the trace (EnvFilter syntax)
+    pub filter: String,
+    /// When the previous filter is restored (RFC 3339)
+    pub until: String,
+}
+
+/// POST /api/v1/debug-trace
+///
+/// Raises `audiotester_core` to TRACE for `duration_secs`, then restores
+/// the previous filter, so remote users can capture a verbose trace (e.g.
+/// of issue #26 phase drift) without restarting with RUST_LOG. A request
+/// during a running trace extends it.
+pub async fn start_debug_trace(
+    State(state): State<AppState>,
+    Json(req): Json<DebugTraceRequest>,
+) -> Result<Json<DebugTraceResponse>, (StatusCode, String)> {
+    let handle = state.log_filter.clone().ok_or((
+        StatusCode::NOT_FOUND,
+        "Runtime log level not configured".to_string(),
+    ))?;
+    if !(1..=MAX_DEBUG_TRACE_SECS).contains(&req.duration_secs) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "duration_secs must be between 1 and {} (got {})",
+                MAX_DEBUG_TRACE_SECS, req.duration_secs
+            ),
+        ));
+    }
+
+    let (generation, filter) = state
+        .debug_trace
+        .start(&handle)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    tracing::info!(filter = %filter, duration_secs = req.duration_secs, "Debug trace started");
+
+    let trace = std::sync::Arc::clone(&state.debug_trace);
+    let duration = std::time::Duration::from_secs(req.duration_secs);
+    tokio::spawn(async move {
+        tokio::time::sleep(duration).await;
+        match trace.finish(&handle, generation) {
+            Ok(Some(filter)) => tracing::info!(filter = %filter, "Debug trace ended"),
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to restore log filter after trace"),
+        }
+    });
+
+    let until = chrono::Utc::now() + chrono::Duration::seconds(req.duration_secs as i64);
+    Ok(Json(DebugTraceResponse {
+        filter,
+        until: until.to_rfc3339(),
+    }))
+}
+
+/// Hand-written OpenAPI 3 description of this API
+///
+/// Update `openapi.json` together with the routes in [`crate::build_router`].
+pub const OPENAPI_JSON: &str = include_str!("openapi.json");
+
+/// GET /api/v1/openapi.json
+///
+/// Serves the OpenAPI document so clients can be generated and the API
+/// explored in Swagger UI or similar tools.
+pub async fn get_openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/json")], OPENAPI_JSON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_state(store: audiotester_core::stats::store::StatsStore) -> AppState {
+        AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(store)),
+            crate::ServerConfig::default(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rejected_config_update_changes_nothing() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let Json(before) = get_config(State(state.clone())).await.unwrap();
+        let before = serde_json::to_value(&before).unwrap();
+
+        // Valid fields first, then an inverted threshold pair
+        let update: ConfigUpdate = serde_json::from_value(serde_json::json!({
+            "sample_rate": 96000,
+            "silence_threshold_ms": 250,
+            "latency_warning_ms": 80.0,
+            "latency_error_ms": 40.0,
+        }))
+        .unwrap();
+        let err = update_config(State(state.clone()), Json(update))
+            .await
+            .err()
+            .expect("update should be rejected");
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+        // Unknown device: rejected before the sample rate is touched
+        let update: ConfigUpdate = serde_json::from_value(serde_json::json!({
+            "sample_rate": 96000,
+            "device": "No Such Device",
+        }))
+        .unwrap();
+        let err = update_config(State(state.clone()), Json(update))
+            .await
+            .err()
+            .expect("update should be rejected");
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+
+        let Json(after) = get_config(State(state)).await.unwrap();
+        assert_eq!(serde_json::to_value(&after).unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn test_select_unknown_device_keeps_current() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let Json(before) = get_config(State(state.clone())).await.unwrap();
+
+        let req: DeviceSelectRequest =
+            serde_json::from_value(serde_json::json!({ "name": "No Such Device" })).unwrap();
+        let err = select_device(State(state.clone()), Json(req))
+            .await
+            .err()
+            .expect("unknown device should be rejected");
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        assert!(err.1.contains("No Such Device"), "{}", err.1);
+
+        let Json(after) = get_config(State(state)).await.unwrap();
+        assert_eq!(after.device, before.device);
+        assert_eq!(after.monitoring, before.monitoring);
+    }
+
+    #[tokio::test]
+    async fn test_baseline_requires_storage() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = save_baseline(State(state.clone())).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+        let err = get_baseline_diff(State(state)).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_openapi_document() {
+        let doc: serde_json::Value = serde_json::from_str(OPENAPI_JSON).unwrap();
+        assert!(doc["openapi"].as_str().unwrap().starts_with("3."));
+        let paths = doc["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/api/v1/stats"));
+
+        // Every API route is documented
+        let lib = include_str!("lib.rs");
+        let router = &lib[lib.find("pub fn build_router").unwrap()..];
+        let router = &router[..router.find("\n}\n").unwrap()];
+        for route in router.split('"').filter(|s| s.starts_with("/api/v1/")) {
+            assert!(
+                paths.contains_key(route),
+                "{} missing from openapi.json",
+                route
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_preferences_endpoint() {
+        let dir =
+            std::env::temp_dir().join(format!("audiotester-prefs-api-{}", std::process::id()));
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(
+                audiotester_core::stats::store::StatsStore::new(),
+            )),
+            crate::ServerConfig {
+                preferences_path: Some(dir.join("preferences.json")),
+                ..Default::default()
+            },
+            None,
+        );
+
+        let Json(prefs) = get_preferences(State(state.clone())).await.unwrap();
+        assert_eq!(prefs, Preferences::default());
+
+        let pinned = Preferences {
+            latency_y_min: None,
+            latency_y_max: Some(15.0),
+        };
+        let Json(saved) = set_preferences(State(state.clone()), Json(pinned.clone()))
+            .await
+            .unwrap();
+        assert_eq!(saved, pinned);
+        let Json(prefs) = get_preferences(State(state.clone())).await.unwrap();
+        assert_eq!(prefs, pinned);
+
+        // min >= max is rejected and leaves the saved preferences alone
+        let inverted = Preferences {
+            latency_y_min: Some(20.0),
+            latency_y_max: Some(10.0),
+        };
+        let err = set_preferences(State(state.clone()), Json(inverted))
+            .await
+            .unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        let Json(prefs) = get_preferences(State(state)).await.unwrap();
+        assert_eq!(prefs, pinned);
+
+        let unconfigured = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = get_preferences(State(unconfigured)).await.unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_effective_config_redacts_secrets() {
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(
+                audiotester_core::stats::store::StatsStore::new(),
+            )),
+            crate::ServerConfig {
+                port: 9000,
+                auth_token: Some("s3cret".to_string()),
+                alert_webhook: Some("https://hooks.example.com/T000/B000/XXXX".to_string()),
+                ..Default::default()
+            },
+            None,
+        );
+        let status = state.engine.get_status().await.unwrap();
+        let vars = [
+            ("AUDIOTESTER_PORT", "9000"),
+            ("AUDIOTESTER_TOKEN", "s3cret"),
+            (
+                "AUDIOTESTER_ALERT_WEBHOOK",
+                "https://hooks.example.com/T000/B000/XXXX",
+            ),
+            ("PATH", "/usr/bin"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+
+        let config = effective_config(&state, status, vars.into_iter());
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.auth_token.as_deref(), Some("***"));
+        assert_eq!(config.alert_webhook.as_deref(), Some("***"));
+        assert_eq!(config.env.len(), 3);
+        assert_eq!(config.env["AUDIOTESTER_PORT"], "9000");
+        assert_eq!(config.env["AUDIOTESTER_TOKEN"], "***");
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("s3cret"));
+        assert!(!json.contains("hooks.example.com"));
+
+        // Unset secrets stay null rather than looking configured
+        let open = test_state(audiotester_core::stats::store::StatsStore::new());
+        let status = open.engine.get_status().await.unwrap();
+        let config = effective_config(&open, status, std::iter::empty());
+        assert_eq!(config.auth_token, None);
+        assert!(config.env.is_empty());
+    }
+
+    fn device(name: &str, host: &str, is_default: bool) -> DeviceInfo {
+        DeviceInfo {
+            name: name.to_string(),
+            host: host.to_string(),
+            is_default,
+            sample_rates: vec![48000],
+            input_channels: 2,
+            output_channels: 2,
+        }
+    }
+
+    #[test]
+    fn test_remote_urls_candidates() {
+        let ipv4 = Some("192.168.1.20".parse().unwrap());
+        let ipv6 = Some("2001:db8::20".parse().unwrap());
+        let resp = remote_urls(ipv4, ipv6, Some("studio-pc"), 8920);
+        assert_eq!(resp.url, "http://192.168.1.20:8920");
+        assert_eq!(
+            resp.urls,
+            [
+                "http://192.168.1.20:8920",
+                "http://studio-pc:8920",
+                "http://[2001:db8::20]:8920",
+            ]
+        );
+
+        // IPv6-only network with a link-local address and no hostname
+        let link_local = Some("fe80::1".parse().unwrap());
+        let resp = remote_urls(None, link_local, None, 8921);
+        assert_eq!(resp.urls, ["http://localhost:8921"]);
+        assert_eq!(resp.url, "http://localhost:8921");
+
+        let resp = remote_urls(None, ipv6, Some(""), 8920);
+        assert_eq!(resp.url, "http://[2001:db8::20]:8920");
+    }
+
+    #[test]
+    fn test_diagnostics_serialization() {
+        let devices = [
+            device("VASIO-8", "ASIO", true),
+            device("Dante", "ASIO", false),
+        ];
+        let report = diagnostics_report(
+            Ok(&devices),
+            Some("Dante".to_string()),
+            true,
+            Some("192.168.1.20".to_string()),
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["asio_available"], true);
+        assert_eq!(json["device_count"], 2);
+        assert_eq!(json["default_device"], "VASIO-8");
+        assert_eq!(json["configured_device"], "Dante");
+        assert_eq!(json["configured_device_found"], true);
+        assert_eq!(json["log_dir_writable"], true);
+        assert_eq!(json["local_ip"], "192.168.1.20");
+        assert!(json["error"].is_null());
+    }
+
+    #[test]
+    fn test_diagnostics_device_not_found() {
+        let devices = [device("Speakers", "WASAPI", true)];
+        let report = diagnostics_report(Ok(&devices), Some("VASIO-8".to_string()), false, None);
+        assert!(!report.asio_available);
+        assert!(!report.configured_device_found);
+        assert_eq!(report.configured_device.as_deref(), Some("VASIO-8"));
+
+        let failed = diagnostics_report(
+            Err("ASIO driver missing".to_string()),
+            Some("VASIO-8".to_string()),
+            true,
+            None,
+        );
+        assert_eq!(failed.device_count, 0);
+        assert!(!failed.configured_device_found);
+        assert_eq!(failed.error.as_deref(), Some("ASIO driver missing"));
+    }
+
+    #[test]
+    fn test_dir_writable() {
+        let dir = std::env::temp_dir().join("audiotester-diagnostics-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(dir_writable(&dir));
+        assert!(!dir_writable(&dir.join("missing")));
+    }
+
+    #[test]
+    fn test_status_reports_rate_fallback() {
+        // Device rejected 96kHz and the engine fell back to its 48kHz default
+        let status = EngineStatus {
+            state: EngineState::Running,
+            device_name: Some("VASIO-8".to_string()),
+            sample_rate: 48000,
+            requested_sample_rate: 96000,
+            effective_sample_rate: Some(48000),
+            rate_fallback: true,
+            silence_threshold_ms: 100,
+            buffer_size: None,
+            actual_buffer_size: 256,
+            signal_channels: (0, 1),
+            burst_amplitude: 0.5,
+            burst_waveform: BurstWaveform::Noise,
+            started_at: None,
+            hardware_latency_ms: None,
+            clock_drift_ppm: None,
+            input_device_name: None,
+            measurement_method: MeasurementMethod::FrameCounter,
+            test_tone_active: false,
+        };
+        let json = serde_json::to_value(status_response(status, None, 8921)).unwrap();
+        assert_eq!(json["sample_rate"], 48000);
+        assert_eq!(json["requested_sample_rate"], 96000);
+        assert_eq!(json["effective_sample_rate"], 48000);
+        assert_eq!(json["rate_fallback"], true);
+    }
+
+    #[tokio::test]
+    async fn test_soft_reset_keeps_history() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_latency(5.0);
+        store.record_latency(5.5);
+        store.record_loss(3);
+        let state = test_state(store);
+
+        let code = reset_stats(
+            State(state.clone()),
+            axum::extract::Query(ResetQuery::default()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(code, StatusCode::NO_CONTENT);
+
+        let store = state.stats.lock().unwrap();
+        assert_eq!(store.latency_plot_data(300).len(), 2);
+        assert_eq!(store.stats().total_lost, 0);
+        assert_eq!(store.stats().measurement_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_hard_reset_clears_history() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_latency(5.0);
+        store.record_latency(5.5);
+        store.record_loss(3);
+        store.record_disconnection(1500, true);
+        let state = test_state(store);
+        assert_eq!(state.stats.lock().unwrap().latency_plot_data(300).len(), 2);
+
+        let code = reset_stats(
+            State(state.clone()),
+            axum::extract::Query(ResetQuery { hard: Some(true) }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(code, StatusCode::NO_CONTENT);
+
+        let store = state.stats.lock().unwrap();
+        assert!(store.latency_plot_data(300).is_empty());
+        assert!(store.loss_plot_data(300).is_empty());
+        assert!(store.disconnection_events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_latency_respects_point_limit() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        for i in 0..100 {
+            store.record_latency(5.0 + (i % 7) as f64 * 0.1);
+        }
+        let state = test_state(store);
+
+        let Json(resp) = get_latency(
+            State(state.clone()),
+            axum::extract::Query(LatencyQuery { points: Some(20) }),
+        )
+        .await;
+        assert_eq!(resp.points.len(), 20);
+
+        let Json(resp) = get_latency(
+            State(state),
+            axum::extract::Query(LatencyQuery { points: None }),
+        )
+        .await;
+        assert_eq!(resp.points.len(), 100);
+    }
+
+    #[test]
+    fn test_annotations_filter_range_and_label() {
+        use audiotester_core::stats::store::{DisconnectionEvent, LossEvent};
+        let now = chrono::Utc::now();
+        let disconnections = vec![
+            DisconnectionEvent {
+                timestamp: now - chrono::Duration::hours(2),
+                duration_ms: 800,
+                reconnected: true,
+            },
+            DisconnectionEvent {
+                timestamp: now - chrono::Duration::minutes(10),
+                duration_ms: 1500,
+                reconnected: true,
+            },
+            DisconnectionEvent {
+                timestamp: now - chrono::Duration::minutes(5),
+                duration_ms: 0,
+                reconnected: false,
+            },
+        ];
+        let losses = std::collections::VecDeque::from([
+            LossEvent {
+                timestamp: now - chrono::Duration::hours(3),
+                count: 10,
+            },
+            LossEvent {
+                timestamp: now - chrono::Duration::minutes(20),
+                count: 42,
+            },
+        ]);
+
+        let annotations =
+            build_annotations(&disconnections, &losses, now - chrono::Duration::hours(1));
+        assert_eq!(annotations.len(), 3, "Events older than 1h are excluded");
+        assert_eq!(annotations[0].kind, "loss");
+        assert_eq!(annotations[0].label, "Lost 42 samples");
+        assert_eq!(annotations[1].kind, "disconnection");
+        assert_eq!(annotations[1].label, "Disconnected 1.5s, reconnected");
+        assert_eq!(annotations[2].label, "Disconnected, reconnect failed");
+
+        let json = serde_json::to_value(&annotations[0]).unwrap();
+        assert_eq!(json["type"], "loss");
+    }
+
+    #[tokio::test]
+    async fn test_selftest_passes() {
+        let Json(resp) = run_selftest().await;
+        assert!(resp.ok, "{:?}", resp);
+        assert_eq!(resp.expected_ms, 5.0);
+        assert!((resp.measured_ms - 5.0).abs() <= 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_test_tone_requires_running_engine() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = play_test_tone(
+            State(state),
+            Json(TestToneRequest {
+                freq_hz: Some(440.0),
+                channel: None,
+                duration_ms: None,
+            }),
+        )
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(err.0, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_asio_events_serialize_in_order() {
+        use audiotester_core::stats::store::AsioEventKind;
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_asio_event(AsioEventKind::Reset);
+        store.record_asio_event(AsioEventKind::VirtualRestart);
+
+        let Json(resp) = get_asio_events(State(test_state(store))).await;
+        let json = serde_json::to_value(resp).unwrap();
+        assert_eq!(json["events"][0]["kind"], "reset");
+        assert_eq!(json["events"][1]["kind"], "virtual_restart");
+    }
+
+    #[tokio::test]
+    async fn test_debug_trace_requires_log_filter() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = start_debug_trace(State(state), Json(DebugTraceRequest { duration_secs: 30 }))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_spikes_list_recorded_spike() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        for _ in 0..20 {
+            store.record_latency(5.0);
+        }
+        store.record_latency(30.0);
+
+        let Json(resp) = get_spikes(State(test_state(store))).await;
+        let json = serde_json::to_value(resp).unwrap();
+        assert_eq!(json["spikes"].as_array().unwrap().len(), 1);
+        assert_eq!(json["spikes"][0]["latency_ms"], 30.0);
+        assert_eq!(json["spikes"][0]["baseline_ms"], 5.0);
+    }
+
+    #[tokio::test]
+    async fn test_event_log_level_query() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let query = |level: &str| {
+            axum::extract::Query(EventLogQuery {
+                level: Some(level.to_string()),
+            })
+        };
+
+        let Json(resp) = get_event_log(State(state.clone()), query("warn"))
+            .await
+            .unwrap();
+        assert!(resp.events.is_empty());
+        assert_eq!(resp.dropped, 0);
+
+        let err = get_event_log(State(state), query("loud"))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_detector_requires_running_engine() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = get_detector(State(state)).await.err().unwrap();
+        assert_eq!(err.0, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_levels_require_running_engine() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = get_levels(State(state)).await.err().unwrap();
+        assert_eq!(err.0, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn test_probe_without_device_fails() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let err = probe_latency(
+            State(state),
+            axum::extract::Query(ProbeQuery {
+                timeout_ms: Some(100),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.0, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_health_decision() {
+        let healthy = health_from(Some(EngineState::Running), false);
+        assert_eq!(
+            serde_json::to_value(&healthy).unwrap(),
+            serde_json::json!({ "ok": true })
+        );
+
+        let lost = health_from(Some(EngineState::Running), true);
+        assert!(!lost.ok);
+        assert_eq!(lost.reason.as_deref(), Some("signal lost"));
+
+        let stopped = health_from(Some(EngineState::Stopped), false);
+        assert_eq!(stopped.reason.as_deref(), Some("engine stopped"));
+        let error = health_from(Some(EngineState::Error), false);
+        assert_eq!(error.reason.as_deref(), Some("engine error"));
+        assert!(!health_from(None, false).ok);
+    }
+
+    #[tokio::test]
+    async fn test_health_unavailable_when_stopped() {
+        let state = test_state(audiotester_core::stats::store::StatsStore::new());
+        let (code, Json(health)) = get_health(State(state)).await;
+        assert_eq!(code, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(health.reason.as_deref(), Some("engine stopped"));
+    }
+
+    #[tokio::test]
+    async fn test_export_latency_csv() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_latency(5.0);
+        store.record_latency(5.25);
+        let state = test_state(store);
+
+        let resp = export_latency_csv(
+            State(state.clone()),
+            axum::extract::Query(LatencyExportQuery {
+                range: Some("1h".to_string()),
+            }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "text/csv; charset=utf-8"
+        );
+        assert!(resp.headers()[header::CONTENT_DISPOSITION]
+            .to_str()
+            .unwrap()
+            .contains("audiotester-latency.csv"));
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(lines.next(), Some("timestamp_iso,latency_ms"));
+        let first = lines.next().expect("at least one data row");
+        assert!(first.ends_with(",5.000"), "unexpected row: {}", first);
+        assert_eq!(lines.count(), 1);
+
+        let bad = export_latency_csv(
+            State(state),
+            axum::extract::Query(LatencyExportQuery {
+                range: Some("3w".to_string()),
+            }),
+        )
+        .await;
+        assert!(matches!(bad, Err((StatusCode::BAD_REQUEST, _))));
+    }
+
+    #[cfg(feature = "chart")]
+    #[tokio::test]
+    async fn test_chart_png() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        for i in 0..50 {
+            store.record_latency(5.0 + (i % 5) as f64 * 0.25);
+        }
+        let state = test_state(store);
+
+        let resp = get_chart_png(
+            State(state.clone()),
+            axum::extract::Query(ChartQuery {
+                range: Some("1h".to_string()),
+            }),
+        )
+        .await
+        .unwrap()
+        .into_response();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers()[header::CONTENT_TYPE], "image/png");
+
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.starts_with(b"\x89PNG\r\n\x1a\n"), "not a PNG");
+        assert!(
+            body.len() > 2000,
+            "suspiciously small PNG: {} bytes",
+            body.len()
+        );
+
+        let bad = get_chart_png(
+            State(state),
+            axum::extract::Query(ChartQuery {
+                range: Some("3w".to_string()),
+            }),
+        )
+        .await;
+        assert!(matches!(bad, Err((StatusCode::BAD_REQUEST, _))));
+    }
+
+    #[test]
+    fn test_status_response_serializes() {
+        let resp = StatusResponse {
+            version: "0.1.5".to_string(),
+            build_date: "2026-02-15".to_string(),
+            state: "Stopped".to_string(),
+            device: None,
+            input_device: None,
+            measurement_method: MeasurementMethod::FrameCounter,
+            sample_rate: 96000,
+            requested_sample_rate: 96000,
+            effective_sample_rate: None,
+            rate_fallback: false,
+            monitoring: false,
+            input_latency_ms: Some(1.333),
+            output_latency_ms: None,
+            stats_last_saved: None,
+            test_tone_active: false,
+            port: 8920,
+        };
+        let json = serde_json::to_string(&resp).unwrap();
+        assert!(json.contains("\"version\":\"0.1.5\""));
+        assert!(json.contains("\"build_date\":\"2026-02-15\""));
+        assert!(json.contains("\"input_latency_ms\":1.333"));
+        assert!(json.contains("\"output_latency_ms\":null"));
+        assert!(json.contains("\"measurement_method\":\"frame_counter\""));
+    }
+
+    #[test]
+    fn test_stats_response_serializes() {
+        let resp = StatsResponse {
             current_latency: 5.0,
+            latency_us: 5000,
             min_latency: 4.0,
             max_latency: 6.0,
             avg_latency: 5.0,
+            jitter_ms: 0.4,
+            max_jitter_ms: 0.9,
+            avg_1m: 5.0,
+            avg_5m: 5.0,
+            avg_15m: 5.0,
+            loss_rate_per_min: 0.0,
+            p50_latency: 5.0,
+            p95_latency: 5.8,
+            p99_latency: 6.0,
             total_lost: 0,
             total_corrupted: 0,
             measurement_count: 100,
             latency_history: vec![(-1.0, 5.0), (-2.0, 5.1)],
             loss_history: vec![],
+            confidence_history: vec![(-1.0, 0.85)],
             device_name: Some("Test ASIO".to_string()),
             buffer_size: 256,
             sample_rate: 96000,
+            rate_fallback: false,
             uptime_seconds: 3600,
             loss_events: vec![],
             samples_sent: 1000000,
@@ -652,9 +3533,20 @@ mod tests {
             confidence: 0.85,
             estimated_loss: 0,
             counter_silent: false,
+            counter_stuck: false,
+            input_levels: vec![0.5, 0.99],
+            anomaly: false,
+            latency_out_of_range: false,
+            hardware_latency_ms: 2.7,
+            excess_latency_ms: 2.3,
+            clock_drift_ppm: Some(-12.5),
+            availability_percent: Some(99.5),
+            recording_paused: false,
+            latency_method: LatencyMethod::MlsFallback,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("\"current_latency\":5.0"));
+        assert!(json.contains("\"latency_us\":5000"));
         assert!(json.contains("\"device_name\":\"Test ASIO\""));
         assert!(json.contains("\"sample_rate\":96000"));
         assert!(json.contains("\"samples_sent\":1000000"));
@@ -663,6 +3555,9 @@ mod tests {
         assert!(json.contains("\"confidence\":0.85"));
         assert!(json.contains("\"estimated_loss\":0"));
         assert!(json.contains("\"counter_silent\":false"));
+        assert!(json.contains("\"input_levels\":[0.5,0.99]"));
+        assert!(json.contains("\"latency_method\":\"mls_fallback\""));
+        assert!(json.contains("\"clock_drift_ppm\":-12.5"));
     }
 
     #[test]
@@ -679,5 +3574,186 @@ mod tests {
         let update: ConfigUpdate = serde_json::from_str(json).unwrap();
         assert_eq!(update.device, None);
         assert_eq!(update.sample_rate, Some(48000));
+        assert_eq!(update.silence_threshold_ms, None);
+        assert_eq!(update.buffer_size, None);
+        assert_eq!(update.burst_channel, None);
+        assert_eq!(update.counter_channel, None);
+        assert_eq!(update.burst_amplitude, None);
+        assert_eq!(update.burst_waveform, None);
+        assert_eq!(update.signal_timeout_ms, None);
+        assert_eq!(update.latency_warning_ms, None);
+    }
+
+    #[test]
+    fn test_config_update_burst_waveform() {
+        let update: ConfigUpdate = serde_json::from_str(r#"{"burst_waveform": "chirp"}"#).unwrap();
+        assert_eq!(update.burst_waveform, Some(BurstWaveform::Chirp));
+        assert!(serde_json::from_str::<ConfigUpdate>(r#"{"burst_waveform": "square"}"#).is_err());
+    }
+
+    #[test]
+    fn test_device_select_request_deserializes() {
+        let json = r#"{"name": "VB-Matrix VASIO-8"}"#;
+        let req: DeviceSelectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.name, "VB-Matrix VASIO-8");
+        assert_eq!(req.input_name, None);
+
+        let json = r#"{"name": "Speakers", "input_name": "Microphone"}"#;
+        let req: DeviceSelectRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.input_name.as_deref(), Some("Microphone"));
+    }
+
+    #[test]
+    fn test_loss_report_aggregation() {
+        // 10 contiguous buckets: loss at 2 and 7, clean stretches 3-6 (4 buckets)
+        let buckets: Vec<(i64, u64, u32)> = (0..10)
+            .map(|i| {
+                let t = 1_700_000_000 + i * 10;
+                match i {
+                    2 => (t, 50, 1),
+                    7 => (t, 200, 2),
+                    _ => (t, 0, 0),
+                }
+            })
+            .collect();
+
+        let report = build_loss_report("1h", &buckets, 10);
+        assert_eq!(report.total_loss, 250);
+        assert_eq!(report.total_events, 3);
+        assert_eq!(report.active_buckets, 2);
+        assert_eq!(report.worst_buckets.len(), 2);
+        assert_eq!(report.worst_buckets[0].loss, 200);
+        let clean = report.longest_clean.unwrap();
+        assert_eq!(clean.duration_secs, 40);
+        assert_eq!(clean.start, unix_to_rfc3339(1_700_000_030));
+        assert!(report.summary.contains("250 samples"));
+    }
+
+    #[test]
+    fn test_loss_report_gap_breaks_clean_stretch() {
+        // Buckets at 0,10,20 then a gap (monitoring stopped) then 100,110
+        let buckets = vec![(0, 0, 0), (10, 0, 0), (20, 0, 0), (100, 0, 0), (110, 0, 0)];
+        let report = build_loss_report("1h", &buckets, 10);
+        assert_eq!(report.longest_clean.unwrap().duration_secs, 30);
+        assert!(report.worst_buckets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_bundles_diagnostics_and_events() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_asio_event(audiotester_core::stats::store::AsioEventKind::Reset);
+        let state = test_state(store);
+
+        let resp = get_snapshot(State(state)).await.unwrap().into_response();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let snapshot: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        // The full effective config, not a subset
+        assert_eq!(snapshot["config"]["reconnect_strategy"], "full");
+        assert!(snapshot["config"]["latency_error_ms"].is_number());
+        assert!(snapshot["config"]["env"].is_object());
+        assert!(snapshot["diagnostics"]["device_count"].is_number());
+        assert!(snapshot["events"]["events"].is_array());
+        assert_eq!(snapshot["asio_events"]["events"][0]["kind"], "reset");
+    }
+
+    #[test]
+    fn test_merge_log_directive_replaces_target() {
+        let merged = merge_log_directive(
+            "warn,audiotester=debug,audiotester_core=debug",
+            Some("audiotester_core"),
+            "trace",
+        );
+        assert_eq!(merged, "warn,audiotester=debug,audiotester_core=trace");
+    }
+
+    #[test]
+    fn test_merge_log_directive_default_level() {
+        let merged = merge_log_directive("warn,audiotester=debug", None, "info");
+        assert_eq!(merged, "info,audiotester=debug");
+    }
+
+    #[test]
+    fn test_read_log_tail_invalid_utf8() {
+        let path =
+            std::env::temp_dir().join(format!("audiotester-log-utf8-{}.log", std::process::id()));
+        std::fs::write(&path, b"first line\nDevice \xFF\xFE name\nlast line\n").unwrap();
+
+        let content = read_log_tail(&path, MAX_LOG_TAIL_BYTES).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("Device "));
+        assert!(lines[1].contains('\u{FFFD}'));
+    }
+
+    #[test]
+    fn test_read_log_tail_drops_partial_line() {
+        let path =
+            std::env::temp_dir().join(format!("audiotester-log-tail-{}.log", std::process::id()));
+        std::fs::write(&path, "aaaaaaaaaa\nbbbb\ncccc\n").unwrap();
+
+        // 12 bytes from the end starts inside the first line
+        let content = read_log_tail(&path, 12).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(content, "bbbb\ncccc\n");
+    }
+
+    #[tokio::test]
+    async fn test_log_file_selection() {
+        let dir = std::env::temp_dir().join(format!("audiotester-logs-{}", std::process::id()));
+        let log_dir = dir.join("logs");
+        std::fs::create_dir_all(&log_dir).unwrap();
+        std::fs::write(log_dir.join("audiotester.log.2026-10-15"), "yesterday\n").unwrap();
+        std::fs::write(log_dir.join("other.txt"), "not a log\n").unwrap();
+        std::fs::write(dir.join("secret"), "secret\n").unwrap();
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(
+                audiotester_core::stats::store::StatsStore::new(),
+            )),
+            crate::ServerConfig::default(),
+            Some(log_dir.clone()),
+        );
+        let query = |file: &str| {
+            axum::extract::Query(LogsQuery {
+                tail: None,
+                filter: None,
+                file: Some(file.to_string()),
+            })
+        };
+
+        let Json(listing) = get_log_files(State(state.clone())).await.unwrap();
+        let names: Vec<&str> = listing.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["audiotester.log.2026-10-15"]);
+        assert_eq!(listing.files[0].size_bytes, 10);
+
+        let content = get_logs(State(state.clone()), query("audiotester.log.2026-10-15"))
+            .await
+            .unwrap();
+        assert_eq!(content, "yesterday");
+
+        // Path traversal and non-log files are rejected
+        for file in ["../../secret", "../secret", "audiotester.log/../../secret"] {
+            let err = get_logs(State(state.clone()), query(file))
+                .await
+                .unwrap_err();
+            assert_eq!(err.0, StatusCode::BAD_REQUEST, "{}", file);
+        }
+        let err = get_logs(State(state.clone()), query("other.txt"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        let err = get_logs(State(state), query("audiotester.log.1999-01-01"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.0, StatusCode::NOT_FOUND);
+
+        std::fs::remove_dir_all(&dir).ok();
     }
 }
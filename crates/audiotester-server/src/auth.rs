@@ -0,0 +1,269 @@
+//! Optional bearer-token protection for the web server
+//!
+//! The server listens on all interfaces with permissive CORS, so without a
+//! token anyone on the LAN can reset statistics or switch devices. When
+//! [`ServerConfig::auth_token`](crate::ServerConfig::auth_token) is set,
+//! mutating `/api/v1/*` requests must carry `Authorization: Bearer <token>`.
+//! With `auth_all` every request is gated, including dashboard pages.
+//! Browsers cannot set headers on a WebSocket, so `/api/v1/ws` also accepts
+//! the token as a `?token=` query parameter; commands sent over the socket
+//! are only run when the connection presented it.
+//!
+//! Independently of the token, mutating requests sent by a browser page on
+//! an origin outside [`ServerConfig::allowed_origins`](crate::ServerConfig::allowed_origins)
+//! are refused, so other sites cannot change settings through a visitor's
+//! browser (CORS alone only stops them from reading the response).
+
+use crate::AppState;
+use axum::extract::{Query, Request, State};
+use axum::http::{header, HeaderMap, Method, StatusCode, Uri};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+
+/// Reject requests that need a token but do not present the configured one
+///
+/// Mutating requests from a browser origin that is not allowed are rejected
+/// first, whether or not a token is configured.
+pub async fn require_token(State(state): State<AppState>, req: Request, next: Next) -> Response {
+    if is_protected(req.method(), req.uri().path())
+        && !crate::request_origin_allowed(&state.config.allowed_origins, req.headers())
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(serde_json::json!({ "error": "Origin not allowed" })),
+        )
+            .into_response();
+    }
+
+    let Some(expected) = state.config.auth_token.as_deref() else {
+        return next.run(req).await;
+    };
+    if !state.config.auth_all && !is_protected(req.method(), req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let query_token = if req.uri().path() == WS_PATH {
+        ws_query_token(req.uri())
+    } else {
+        None
+    };
+    match bearer_token(req.headers()).or(query_token.as_deref()) {
+        Some(token) if constant_time_eq(token.trim().as_bytes(), expected.as_bytes()) => {
+            next.run(req).await
+        }
+        Some(_) => unauthorized("Invalid token"),
+        None => unauthorized("Missing bearer token"),
+    }
+}
+
+/// WebSocket endpoint, which also accepts the token as a query parameter
+const WS_PATH: &str = "/api/v1/ws";
+
+/// Whether a request presented the configured token (always true without one)
+///
+/// Checks the `Authorization` header, then `query_token` (the `?token=`
+/// parameter of a WebSocket upgrade).
+pub fn has_token(state: &AppState, headers: &HeaderMap, query_token: Option<&str>) -> bool {
+    let Some(expected) = state.config.auth_token.as_deref() else {
+        return true;
+    };
+    bearer_token(headers)
+        .or(query_token)
+        .is_some_and(|token| constant_time_eq(token.trim().as_bytes(), expected.as_bytes()))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+}
+
+fn ws_query_token(uri: &Uri) -> Option<String> {
+    Query::<crate::ws::WsQuery>::try_from_uri(uri)
+        .ok()
+        .and_then(|Query(query)| query.token)
+}
+
+/// Whether a request changes state and so needs a token by default
+fn is_protected(method: &Method, path: &str) -> bool {
+    path.starts_with("/api/v1/")
+        && matches!(
+            *method,
+            Method::POST | Method::PATCH | Method::PUT | Method::DELETE
+        )
+}
+
+fn unauthorized(message: &str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        Json(serde_json::json!({ "error": message })),
+    )
+        .into_response()
+}
+
+/// Compare secrets without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{build_router, EngineHandle, ServerConfig};
+    use audiotester_core::stats::store::StatsStore;
+    use std::sync::{Arc, Mutex};
+
+    /// Serve the full router on an ephemeral port and return its base URL
+    async fn serve(config: ServerConfig) -> String {
+        let state = AppState::new(
+            EngineHandle::spawn(),
+            Arc::new(Mutex::new(StatsStore::new())),
+            config,
+            None,
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, build_router(state)).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    fn with_token(auth_all: bool) -> ServerConfig {
+        ServerConfig {
+            auth_token: Some("s3cret".to_string()),
+            auth_all,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mutating_routes_require_token() {
+        let base = serve(with_token(false)).await;
+        let client = reqwest::Client::new();
+
+        let resp = client
+            .post(format!("{}/api/v1/reset", base))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 401);
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body["error"], "Missing bearer token");
+
+        let resp = client
+            .post(format!("{}/api/v1/reset", base))
+            .bearer_auth("wrong")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 401);
+
+        let resp = client
+            .post(format!("{}/api/v1/reset", base))
+            .bearer_auth("s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+
+        // Reads stay open
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_auth_all_gates_reads() {
+        let base = serve(with_token(true)).await;
+        let client = reqwest::Client::new();
+
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 401);
+
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .bearer_auth("s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_no_token_configured_allows_everything() {
+        let base = serve(ServerConfig::default()).await;
+        let resp = reqwest::Client::new()
+            .post(format!("{}/api/v1/reset", base))
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_mutations_from_other_origins_rejected() {
+        let base = serve(ServerConfig {
+            allowed_origins: vec!["http://studio.local:8080".to_string()],
+            ..Default::default()
+        })
+        .await;
+        let client = reqwest::Client::new();
+        let reset = |origin: Option<&str>| {
+            let req = client.post(format!("{}/api/v1/reset", base));
+            match origin {
+                Some(origin) => req.header(header::ORIGIN, origin),
+                None => req,
+            }
+            .send()
+        };
+
+        let resp = reset(Some("http://evil.example")).await.unwrap();
+        assert_eq!(resp.status(), 403);
+        let body: serde_json::Value = resp.json().await.unwrap();
+        assert_eq!(body["error"], "Origin not allowed");
+
+        // Allowlisted, same-origin (the dashboard) and non-browser clients pass
+        let own_origin = base.clone();
+        for origin in [
+            Some("http://studio.local:8080"),
+            Some(own_origin.as_str()),
+            None,
+        ] {
+            let resp = reset(origin).await.unwrap();
+            assert!(
+                resp.status().is_success(),
+                "{:?}: {}",
+                origin,
+                resp.status()
+            );
+        }
+
+        // Reads are left to CORS
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .header(header::ORIGIN, "http://evil.example")
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+    }
+
+    #[test]
+    fn test_protected_routes() {
+        assert!(is_protected(&Method::PATCH, "/api/v1/config"));
+        assert!(is_protected(&Method::DELETE, "/api/v1/device"));
+        assert!(!is_protected(&Method::GET, "/api/v1/config"));
+        assert!(!is_protected(&Method::POST, "/settings"));
+    }
+}
@@ -0,0 +1,62 @@
+//! Server-side latency chart rendering
+//!
+//! `GET /api/v1/chart.png` draws the latency history into a PNG, so status
+//! emails and wiki pages can embed the chart without a browser. Built with
+//! the `chart` cargo feature (enabled by default), which pulls in `plotters`.
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use image::ImageEncoder;
+use plotters::prelude::*;
+
+/// Chart image width (px)
+pub const CHART_WIDTH: u32 = 960;
+
+/// Chart image height (px)
+pub const CHART_HEIGHT: u32 = 400;
+
+/// Render latency measurements between `from` and `to` as a PNG line chart
+///
+/// `points` are `(timestamp, ms)` pairs, oldest first. The caption names
+/// `device` and the time range; an empty series still renders the axes.
+pub fn render_latency_png(
+    points: &[(DateTime<Utc>, f64)],
+    device: &str,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> anyhow::Result<Vec<u8>> {
+    let mut rgb = vec![0u8; (CHART_WIDTH * CHART_HEIGHT * 3) as usize];
+    {
+        let root =
+            BitMapBackend::with_buffer(&mut rgb, (CHART_WIDTH, CHART_HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let max_latency = points.iter().map(|&(_, ms)| ms).fold(0.0, f64::max);
+        let caption = format!(
+            "{} latency, {} to {} UTC",
+            device,
+            from.format("%Y-%m-%d %H:%M"),
+            to.format("%Y-%m-%d %H:%M")
+        );
+        let mut chart = ChartBuilder::on(&root)
+            .caption(caption, ("sans-serif", 20))
+            .margin(12)
+            .x_label_area_size(32)
+            .y_label_area_size(56)
+            .build_cartesian_2d(from..to, 0.0..(max_latency * 1.1).max(1.0))?;
+        chart
+            .configure_mesh()
+            .x_labels(8)
+            .x_label_formatter(&|t| t.format("%H:%M").to_string())
+            .y_desc("ms")
+            .draw()?;
+        chart.draw_series(LineSeries::new(points.iter().copied(), &BLUE))?;
+        root.present()?;
+    }
+
+    let mut png = Vec::new();
+    image::codecs::png::PngEncoder::new(&mut png)
+        .write_image(&rgb, CHART_WIDTH, CHART_HEIGHT, image::ColorType::Rgb8)
+        .context("Failed to encode chart PNG")?;
+    Ok(png)
+}
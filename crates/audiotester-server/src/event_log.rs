@@ -0,0 +1,263 @@
+//! In-memory structured event log
+//!
+//! A `tracing` layer keeps the most recent events in a ring buffer so the
+//! dashboard can query warnings and errors as JSON without re-reading the
+//! log file. Capture is rate-limited per second: a burst of repeated events
+//! (e.g. a stream error on every callback) is counted as dropped instead of
+//! pushing everything else out of the buffer.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Number of events kept in memory
+pub const MAX_LOG_EVENTS: usize = 1000;
+
+/// Events captured per second before further events are dropped
+pub const MAX_LOG_EVENTS_PER_SEC: u32 = 50;
+
+/// A captured log event
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEvent {
+    /// When the event was recorded (RFC 3339)
+    pub timestamp: String,
+    /// Level name ("ERROR", "WARN", "INFO", "DEBUG", "TRACE")
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    /// Module path the event came from
+    pub target: String,
+    /// Formatted message
+    pub message: String,
+    /// Structured fields other than the message
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+fn serialize_level<S: serde::Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(level.as_str())
+}
+
+#[derive(Debug)]
+struct EventLogInner {
+    events: VecDeque<LogEvent>,
+    /// Start of the current rate-limit window
+    window_start: Instant,
+    /// Events captured in the current window
+    window_count: u32,
+    /// Events dropped by the rate limit since start
+    dropped: u64,
+}
+
+/// Ring buffer of recent log events, shared between the layer and the API
+#[derive(Debug)]
+pub struct EventLog {
+    inner: Mutex<EventLogInner>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventLog {
+    /// Create an empty event log
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(EventLogInner {
+                events: VecDeque::with_capacity(MAX_LOG_EVENTS),
+                window_start: Instant::now(),
+                window_count: 0,
+                dropped: 0,
+            }),
+        }
+    }
+
+    /// Lock the buffer; a panic elsewhere must not silence the log
+    fn lock(&self) -> MutexGuard<'_, EventLogInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Append an event, subject to the per-second rate limit
+    pub fn push(&self, event: LogEvent) {
+        let mut inner = self.lock();
+        let now = Instant::now();
+        if now.duration_since(inner.window_start) >= Duration::from_secs(1) {
+            inner.window_start = now;
+            inner.window_count = 0;
+        }
+        if inner.window_count >= MAX_LOG_EVENTS_PER_SEC {
+            inner.dropped += 1;
+            return;
+        }
+        inner.window_count += 1;
+
+        if inner.events.len() >= MAX_LOG_EVENTS {
+            inner.events.pop_front();
+        }
+        inner.events.push_back(event);
+    }
+
+    /// Events at `min_level` or more severe (all events if None), oldest first
+    pub fn query(&self, min_level: Option<Level>) -> Vec<LogEvent> {
+        self.lock()
+            .events
+            .iter()
+            .filter(|e| min_level.is_none_or(|min| e.level <= min))
+            .cloned()
+            .collect()
+    }
+
+    /// Events dropped by the rate limit since start
+    pub fn dropped(&self) -> u64 {
+        self.lock().dropped
+    }
+}
+
+/// Collects an event's message and fields
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: serde_json::Value) {
+        if field.name() == "message" {
+            self.message = match value {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, format!("{:?}", value).into());
+    }
+}
+
+/// `tracing` layer feeding an [`EventLog`]
+///
+/// Add it to the subscriber after the (reloadable) filter so it captures
+/// the same events as the log file.
+pub struct EventLogLayer {
+    log: Arc<EventLog>,
+}
+
+impl EventLogLayer {
+    /// Create a layer recording into `log`
+    pub fn new(log: Arc<EventLog>) -> Self {
+        Self { log }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for EventLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.log.push(LogEvent {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_filter_by_level() {
+        let log = Arc::new(EventLog::new());
+        let subscriber = tracing_subscriber::registry().with(EventLogLayer::new(Arc::clone(&log)));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!("debug event");
+            tracing::info!(device = "VASIO-8", "info event");
+            tracing::warn!(attempt = 3u32, "warn event");
+            tracing::error!(latency_ms = 12.5, lost = false, "error event");
+        });
+
+        assert_eq!(log.query(None).len(), 4);
+
+        let warnings = log.query(Some(Level::WARN));
+        let messages: Vec<&str> = warnings.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, ["warn event", "error event"]);
+        assert_eq!(warnings[0].fields["attempt"], 3);
+        assert_eq!(warnings[1].fields["latency_ms"], 12.5);
+        assert_eq!(warnings[1].fields["lost"], false);
+
+        let info = log.query(Some(Level::INFO));
+        assert_eq!(info.len(), 3);
+        assert_eq!(info[0].fields["device"], "VASIO-8");
+
+        let json = serde_json::to_value(&warnings[0]).unwrap();
+        assert_eq!(json["level"], "WARN");
+        assert_eq!(json["target"], module_path!());
+    }
+
+    #[test]
+    fn test_rate_limit_and_capacity() {
+        let log = EventLog::new();
+        let event = |n: usize| LogEvent {
+            timestamp: String::new(),
+            level: Level::ERROR,
+            target: "test".to_string(),
+            message: n.to_string(),
+            fields: serde_json::Map::new(),
+        };
+
+        for n in 0..(MAX_LOG_EVENTS_PER_SEC as usize + 10) {
+            log.push(event(n));
+        }
+        assert_eq!(log.query(None).len(), MAX_LOG_EVENTS_PER_SEC as usize);
+        assert_eq!(log.dropped(), 10);
+
+        // Over many windows only the newest MAX_LOG_EVENTS are kept
+        for n in 0..MAX_LOG_EVENTS + 5 {
+            log.lock().window_count = 0;
+            log.push(event(n));
+        }
+        let events = log.query(None);
+        assert_eq!(events.len(), MAX_LOG_EVENTS);
+        assert_eq!(
+            events.last().unwrap().message,
+            (MAX_LOG_EVENTS + 4).to_string()
+        );
+    }
+}
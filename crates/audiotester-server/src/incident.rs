@@ -0,0 +1,233 @@
+//! Context captured around a loss spike
+//!
+//! When a single analysis cycle reports at least
+//! [`ServerConfig::incident_min_loss`](crate::ServerConfig::incident_min_loss)
+//! lost samples, the monitoring loop freezes the surrounding state (recent
+//! latency, confidence, detector diagnostics and log events) into an
+//! `incident-<timestamp>.json` file, so a glitch that happened overnight can
+//! be examined the next morning. `GET /api/v1/incidents` lists the files.
+
+use anyhow::Context;
+use audiotester_core::audio::detector::DetectorDiagnostics;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::event_log::LogEvent;
+
+/// Default lost samples in one analysis cycle that trigger a capture
+pub const DEFAULT_INCIDENT_MIN_LOSS: u64 = 480;
+
+/// Latency measurements kept in an incident
+pub const INCIDENT_LATENCY_POINTS: usize = 30;
+
+/// Most recent log events kept in an incident
+pub const INCIDENT_LOG_EVENTS: usize = 50;
+
+/// Shortest time between two captures (s), so a loss storm writes one file
+pub const INCIDENT_COOLDOWN_SECS: u64 = 60;
+
+/// Incident files kept in the directory; older ones are deleted on save
+pub const MAX_INCIDENT_FILES: usize = 100;
+
+/// Prefix of incident file names
+const INCIDENT_FILE_PREFIX: &str = "incident-";
+
+/// State frozen at the moment of a loss spike
+#[derive(Debug, Clone, Serialize)]
+pub struct Incident {
+    /// When the loss was recorded
+    pub timestamp: DateTime<Utc>,
+    /// Samples lost in the triggering analysis cycle
+    pub lost_samples: u64,
+    /// Last [`INCIDENT_LATENCY_POINTS`] latency measurements as `(timestamp, ms)`, oldest first
+    pub latency_ms: Vec<(DateTime<Utc>, f64)>,
+    /// Confidence of the triggering measurement (0.0 to 1.0)
+    pub confidence: f32,
+    /// Burst detector state (None while the engine is stopped)
+    pub detector: Option<DetectorDiagnostics>,
+    /// Last [`INCIDENT_LOG_EVENTS`] log events, oldest first
+    pub log: Vec<LogEvent>,
+}
+
+impl Incident {
+    /// File name for this incident, e.g. `incident-20260301T021500.250Z.json`
+    pub fn file_name(&self) -> String {
+        format!(
+            "{}{}.json",
+            INCIDENT_FILE_PREFIX,
+            self.timestamp.format("%Y%m%dT%H%M%S%.3fZ")
+        )
+    }
+
+    /// Write the incident as JSON into `dir`, creating it if needed
+    ///
+    /// Only the newest [`MAX_INCIDENT_FILES`] incidents are kept, so a link
+    /// that glitches every night does not fill the disk. Blocking; call it
+    /// from `spawn_blocking` in async code.
+    pub fn save_in(&self, dir: &Path) -> anyhow::Result<PathBuf> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize incident")?;
+        let path = dir.join(self.file_name());
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        if let Err(e) = prune_incidents(dir, MAX_INCIDENT_FILES) {
+            tracing::warn!(dir = %dir.display(), error = %e, "Failed to prune old incidents");
+        }
+        Ok(path)
+    }
+}
+
+/// One incident file in the incident directory
+#[derive(Debug, Serialize)]
+pub struct IncidentFileInfo {
+    /// File name
+    pub name: String,
+    /// Size in bytes
+    pub size_bytes: u64,
+}
+
+/// List the `incident-*.json` files in `dir`, newest first
+///
+/// A directory that does not exist yet (no incident so far) is empty.
+pub fn list_incidents(dir: &Path) -> std::io::Result<Vec<IncidentFileInfo>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut files: Vec<IncidentFileInfo> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let metadata = e.metadata().ok().filter(|m| m.is_file())?;
+            if !name.starts_with(INCIDENT_FILE_PREFIX) || !name.ends_with(".json") {
+                return None;
+            }
+            Some(IncidentFileInfo {
+                name,
+                size_bytes: metadata.len(),
+            })
+        })
+        .collect();
+    // The timestamp in the name sorts chronologically
+    files.sort_by(|a, b| b.name.cmp(&a.name));
+    Ok(files)
+}
+
+/// Delete all but the newest `keep` incident files in `dir`
+///
+/// Returns the number of files removed.
+pub fn prune_incidents(dir: &Path, keep: usize) -> std::io::Result<usize> {
+    let stale = list_incidents(dir)?.into_iter().skip(keep);
+    let mut removed = 0;
+    for file in stale {
+        std::fs::remove_file(dir.join(&file.name))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn incident(timestamp: DateTime<Utc>) -> Incident {
+        Incident {
+            timestamp,
+            lost_samples: 960,
+            latency_ms: vec![(timestamp, 5.0), (timestamp, 5.25)],
+            confidence: 0.4,
+            detector: Some(DetectorDiagnostics {
+                envelope: 0.1,
+                noise_floor: 0.001,
+                snr_db: 40.0,
+                threshold: 0.05,
+            }),
+            log: vec![LogEvent {
+                timestamp: timestamp.to_rfc3339(),
+                level: tracing::Level::WARN,
+                target: "audiotester".to_string(),
+                message: "Stream error".to_string(),
+                fields: serde_json::Map::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_incident_serialization() {
+        let timestamp = Utc.with_ymd_and_hms(2026, 3, 1, 2, 15, 0).unwrap();
+        let incident = incident(timestamp);
+        assert_eq!(incident.file_name(), "incident-20260301T021500.000Z.json");
+
+        let json = serde_json::to_value(&incident).unwrap();
+        assert_eq!(json["timestamp"], "2026-03-01T02:15:00Z");
+        assert_eq!(json["lost_samples"], 960);
+        assert_eq!(json["latency_ms"][1][1], 5.25);
+        assert_eq!(json["detector"]["snr_db"], 40.0);
+        assert_eq!(json["log"][0]["level"], "WARN");
+        assert_eq!(json["log"][0]["message"], "Stream error");
+    }
+
+    #[test]
+    fn test_save_and_list_incidents() {
+        let dir =
+            std::env::temp_dir().join(format!("audiotester-incidents-{}", std::process::id()));
+        assert!(list_incidents(&dir).unwrap().is_empty());
+
+        let first = Utc.with_ymd_and_hms(2026, 3, 1, 2, 15, 0).unwrap();
+        let second = first + chrono::Duration::minutes(5);
+        incident(first).save_in(&dir).unwrap();
+        let path = incident(second).save_in(&dir).unwrap();
+        std::fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        let files = list_incidents(&dir).unwrap();
+        let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "incident-20260301T022000.000Z.json",
+                "incident-20260301T021500.000Z.json"
+            ]
+        );
+        assert_eq!(files[0].size_bytes, std::fs::metadata(&path).unwrap().len());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_prune_keeps_newest() {
+        let dir = std::env::temp_dir().join(format!(
+            "audiotester-incidents-prune-{}",
+            std::process::id()
+        ));
+        let start = Utc.with_ymd_and_hms(2026, 3, 1, 2, 15, 0).unwrap();
+        for minute in 0..5 {
+            incident(start + chrono::Duration::minutes(minute))
+                .save_in(&dir)
+                .unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        assert_eq!(prune_incidents(&dir, 2).unwrap(), 3);
+        let names: Vec<String> = list_incidents(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "incident-20260301T021900.000Z.json",
+                "incident-20260301T021800.000Z.json"
+            ]
+        );
+        // Other files are left alone
+        assert!(dir.join("notes.txt").exists());
+        assert_eq!(prune_incidents(&dir, 2).unwrap(), 0);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
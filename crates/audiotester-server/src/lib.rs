@@ -3,16 +3,32 @@
 //! Provides a web interface for monitoring audio statistics,
 //! accessible from both local desktop and remote browsers.
 
+pub mod alert;
 pub mod api;
+pub mod auth;
+#[cfg(feature = "chart")]
+pub mod chart;
+pub mod event_log;
+pub mod incident;
+pub mod metrics;
+pub mod midi;
+pub mod osc;
+pub mod preferences;
 pub mod ui;
 pub mod ws;
 
-use audiotester_core::audio::engine::{AnalysisResult, AudioEngine, DeviceInfo, EngineState};
+use audiotester_core::audio::burst::BurstWaveform;
+use audiotester_core::audio::detector::DetectorDiagnostics;
+use audiotester_core::audio::engine::{
+    AnalysisResult, AudioEngine, DeviceInfo, EngineState, HostPreference, MeasurementMethod,
+};
+use audiotester_core::audio::levels::InputLevels;
 use audiotester_core::stats::store::StatsStore;
-use axum::http::{header, HeaderValue};
+use axum::http::{header, HeaderMap, HeaderValue, Method};
 use axum::response::IntoResponse;
 use axum::Router;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU16, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 use tokio::net::TcpListener;
 use tokio::sync::{mpsc, oneshot};
 use tower_http::cors::CorsLayer;
@@ -28,9 +44,39 @@ pub enum EngineCommand {
         name: String,
         reply: oneshot::Sender<anyhow::Result<()>>,
     },
+    SelectDevices {
+        output: String,
+        input: String,
+        reply: oneshot::Sender<anyhow::Result<()>>,
+    },
+    DeselectDevice {
+        reply: oneshot::Sender<anyhow::Result<()>>,
+    },
     SetSampleRate {
         rate: u32,
     },
+    SetSilenceThreshold {
+        ms: u32,
+    },
+    SetMlsFallbackCycles {
+        cycles: u32,
+    },
+    SetEchoGuard {
+        ms: u32,
+    },
+    SetBufferSize {
+        frames: u32,
+    },
+    SetSignalChannels {
+        burst: usize,
+        counter: usize,
+    },
+    SetBurstAmplitude {
+        amplitude: f32,
+    },
+    SetBurstWaveform {
+        waveform: BurstWaveform,
+    },
     Start {
         reply: oneshot::Sender<anyhow::Result<()>>,
     },
@@ -49,6 +95,42 @@ pub enum EngineCommand {
     IsStreamInvalidated {
         reply: oneshot::Sender<bool>,
     },
+    GetInputLevels {
+        reply: oneshot::Sender<Vec<f32>>,
+    },
+    GetDetectorDiagnostics {
+        reply: oneshot::Sender<Option<DetectorDiagnostics>>,
+    },
+    GetLevels {
+        reply: oneshot::Sender<Option<InputLevels>>,
+    },
+    RestartStreams {
+        reply: oneshot::Sender<anyhow::Result<()>>,
+    },
+    StartCapture {
+        path: std::path::PathBuf,
+        duration_secs: u32,
+        reply: oneshot::Sender<anyhow::Result<()>>,
+    },
+    StopCapture {
+        reply: oneshot::Sender<anyhow::Result<std::path::PathBuf>>,
+    },
+    /// Replace the burst output with a sine on one channel, then revert
+    PlayTestTone {
+        freq_hz: f32,
+        channel: usize,
+        duration_ms: u32,
+        reply: oneshot::Sender<anyhow::Result<()>>,
+    },
+    /// Discard latency analysis state (running average, pending bursts)
+    ResetAnalyzer,
+    /// Drop streams and end the engine thread
+    Shutdown {
+        reply: oneshot::Sender<()>,
+    },
+    /// Panic the engine thread (supervisor tests)
+    #[cfg(test)]
+    Crash,
 }
 
 /// Engine status snapshot (safe to send between threads)
@@ -56,61 +138,349 @@ pub enum EngineCommand {
 pub struct EngineStatus {
     pub state: EngineState,
     pub device_name: Option<String>,
+    /// Sample rate in use (effective while running, else requested)
     pub sample_rate: u32,
+    /// Sample rate requested by the configuration
+    pub requested_sample_rate: u32,
+    /// Sample rate the streams run at (None while stopped)
+    pub effective_sample_rate: Option<u32>,
+    /// True when the device rejected the requested rate
+    pub rate_fallback: bool,
+    /// Counter silence threshold (ms)
+    pub silence_threshold_ms: u32,
+    /// Requested buffer size in frames (None = driver default)
+    pub buffer_size: Option<u32>,
+    /// Buffer size delivered by the driver (frames, 0 until streams run)
+    pub actual_buffer_size: u32,
+    /// Channel indices carrying (burst, counter)
+    pub signal_channels: (usize, usize),
+    /// Burst amplitude (0.0 to 1.0)
+    pub burst_amplitude: f32,
+    /// Waveform played during the burst
+    pub burst_waveform: BurstWaveform,
+    /// When streams were last started (None while stopped)
+    pub started_at: Option<std::time::Instant>,
+    /// Driver-reported hardware latency (input_ms, output_ms), once known
+    pub hardware_latency_ms: Option<(f64, f64)>,
+    /// Estimated input/output clock drift (ppm), once enough data is in
+    pub clock_drift_ppm: Option<f64>,
+    /// Separate input device (None when input and output share a device)
+    pub input_device_name: Option<String>,
+    /// How latency is measured with the current device selection
+    pub measurement_method: MeasurementMethod,
+    /// A test tone is replacing the burst output
+    pub test_tone_active: bool,
+}
+
+/// Engine settings carried over to a respawned engine thread
+#[derive(Debug, Clone)]
+struct EngineSettings {
+    device: Option<String>,
+    input_device: Option<String>,
+    sample_rate: u32,
+    buffer_size: Option<u32>,
+    signal_channels: (usize, usize),
+    burst_amplitude: f32,
+    burst_waveform: BurstWaveform,
+    silence_threshold_ms: u32,
+    mls_fallback_cycles: u32,
+    echo_guard_ms: u32,
+    running: bool,
+}
+
+impl EngineSettings {
+    fn capture(engine: &AudioEngine) -> Self {
+        Self {
+            device: engine.device_name().map(str::to_string),
+            input_device: engine.input_device_name().map(str::to_string),
+            sample_rate: engine.requested_sample_rate(),
+            buffer_size: engine.buffer_size(),
+            signal_channels: engine.signal_channels(),
+            burst_amplitude: engine.burst_amplitude(),
+            burst_waveform: engine.burst_waveform(),
+            silence_threshold_ms: engine.silence_threshold_ms(),
+            mls_fallback_cycles: engine.mls_fallback_cycles(),
+            echo_guard_ms: engine.echo_guard_ms(),
+            running: engine.state() == EngineState::Running,
+        }
+    }
+
+    /// Apply the settings to a fresh engine, logging what could not be restored
+    fn restore(&self, engine: &mut AudioEngine) {
+        engine.set_sample_rate(self.sample_rate);
+        engine.set_buffer_size(self.buffer_size.unwrap_or(0));
+        engine.set_signal_channels(self.signal_channels.0, self.signal_channels.1);
+        engine.set_burst_amplitude(self.burst_amplitude);
+        engine.set_burst_waveform(self.burst_waveform);
+        engine.set_silence_threshold_ms(self.silence_threshold_ms);
+        engine.set_mls_fallback_cycles(self.mls_fallback_cycles);
+        engine.set_echo_guard_ms(self.echo_guard_ms);
+
+        let Some(device) = self.device.as_deref() else {
+            return;
+        };
+        let selected = match self.input_device.as_deref() {
+            Some(input) => engine.select_devices(device, input),
+            None => engine.select_device(device),
+        };
+        if let Err(e) = selected {
+            tracing::warn!(device, error = %e, "Failed to reselect device after engine restart");
+            return;
+        }
+        if self.running {
+            if let Err(e) = engine.start() {
+                tracing::warn!(device, error = %e, "Failed to restart monitoring after engine restart");
+            }
+        }
+    }
+}
+
+/// Serve engine commands until shutdown or until every handle is dropped
+///
+/// Publishes the settings after each command so a respawned engine can pick
+/// up where this one panicked.
+fn run_engine(
+    host: HostPreference,
+    rx: &mut mpsc::Receiver<EngineCommand>,
+    settings: &Mutex<Option<EngineSettings>>,
+    previous: Option<EngineSettings>,
+) {
+    let mut engine = AudioEngine::new().with_host(host);
+    if let Some(previous) = previous {
+        previous.restore(&mut engine);
+        tracing::info!(device = ?previous.device, "Engine restored after restart");
+    }
+    let publish = |engine: &AudioEngine| {
+        *settings.lock().unwrap_or_else(|e| e.into_inner()) = Some(EngineSettings::capture(engine));
+    };
+    publish(&engine);
+
+    while let Some(cmd) = rx.blocking_recv() {
+        match cmd {
+            EngineCommand::ListDevices { reply } => {
+                let _ = reply.send(AudioEngine::list_devices_for(engine.host_preference()));
+            }
+            EngineCommand::SelectDevice { name, reply } => {
+                let _ = reply.send(engine.select_device(&name));
+            }
+            EngineCommand::SelectDevices {
+                output,
+                input,
+                reply,
+            } => {
+                let _ = reply.send(engine.select_devices(&output, &input));
+            }
+            EngineCommand::DeselectDevice { reply } => {
+                let _ = reply.send(engine.deselect_device());
+            }
+            EngineCommand::SetSampleRate { rate } => {
+                engine.set_sample_rate(rate);
+            }
+            EngineCommand::SetSilenceThreshold { ms } => {
+                engine.set_silence_threshold_ms(ms);
+            }
+            EngineCommand::SetMlsFallbackCycles { cycles } => {
+                engine.set_mls_fallback_cycles(cycles);
+            }
+            EngineCommand::SetEchoGuard { ms } => {
+                engine.set_echo_guard_ms(ms);
+            }
+            EngineCommand::SetBufferSize { frames } => {
+                engine.set_buffer_size(frames);
+            }
+            EngineCommand::SetSignalChannels { burst, counter } => {
+                engine.set_signal_channels(burst, counter);
+            }
+            EngineCommand::SetBurstAmplitude { amplitude } => {
+                engine.set_burst_amplitude(amplitude);
+            }
+            EngineCommand::SetBurstWaveform { waveform } => {
+                engine.set_burst_waveform(waveform);
+            }
+            EngineCommand::Start { reply } => {
+                let _ = reply.send(engine.start());
+            }
+            EngineCommand::Stop { reply } => {
+                let _ = reply.send(engine.stop());
+            }
+            EngineCommand::GetStatus { reply } => {
+                let _ = reply.send(EngineStatus {
+                    state: engine.state(),
+                    device_name: engine.device_name().map(|s| s.to_string()),
+                    sample_rate: engine.sample_rate(),
+                    requested_sample_rate: engine.requested_sample_rate(),
+                    effective_sample_rate: engine.effective_sample_rate(),
+                    rate_fallback: engine.rate_fallback(),
+                    silence_threshold_ms: engine.silence_threshold_ms(),
+                    buffer_size: engine.buffer_size(),
+                    actual_buffer_size: engine.actual_buffer_size(),
+                    signal_channels: engine.signal_channels(),
+                    burst_amplitude: engine.burst_amplitude(),
+                    burst_waveform: engine.burst_waveform(),
+                    started_at: engine.started_at(),
+                    hardware_latency_ms: engine.hardware_latency_ms(),
+                    clock_drift_ppm: engine.clock_drift_ppm(),
+                    input_device_name: engine.input_device_name().map(|s| s.to_string()),
+                    measurement_method: engine.measurement_method(),
+                    test_tone_active: engine.test_tone_active(),
+                });
+            }
+            EngineCommand::Analyze { reply } => {
+                let _ = reply.send(engine.analyze());
+            }
+            EngineCommand::GetSampleCounts { reply } => {
+                let _ = reply.send(engine.sample_counts());
+            }
+            EngineCommand::IsStreamInvalidated { reply } => {
+                let _ = reply.send(engine.is_stream_invalidated());
+            }
+            EngineCommand::GetInputLevels { reply } => {
+                let _ = reply.send(engine.take_input_levels());
+            }
+            EngineCommand::GetDetectorDiagnostics { reply } => {
+                let _ = reply.send(engine.detector_diagnostics());
+            }
+            EngineCommand::GetLevels { reply } => {
+                let _ = reply.send(engine.signal_levels());
+            }
+            EngineCommand::RestartStreams { reply } => {
+                let _ = reply.send(engine.restart_streams());
+            }
+            EngineCommand::StartCapture {
+                path,
+                duration_secs,
+                reply,
+            } => {
+                let _ = reply.send(engine.start_capture(path, duration_secs));
+            }
+            EngineCommand::StopCapture { reply } => {
+                let _ = reply.send(engine.stop_capture());
+            }
+            EngineCommand::PlayTestTone {
+                freq_hz,
+                channel,
+                duration_ms,
+                reply,
+            } => {
+                let _ = reply.send(engine.play_test_tone(freq_hz, channel, duration_ms));
+            }
+            EngineCommand::ResetAnalyzer => {
+                engine.reset_analysis();
+            }
+            #[cfg(test)]
+            EngineCommand::Crash => panic!("simulated engine crash"),
+            EngineCommand::Shutdown { reply } => {
+                // Release the driver before acknowledging so the
+                // caller can exit the process right away
+                let _ = engine.stop();
+                drop(engine);
+                tracing::info!("Engine thread shutting down");
+                let _ = reply.send(());
+                return;
+            }
+        }
+
+        publish(&engine);
+    }
 }
 
 /// Handle to communicate with the engine thread
 #[derive(Clone)]
 pub struct EngineHandle {
     tx: mpsc::Sender<EngineCommand>,
+    /// Engine supervisor thread, taken by the first [`EngineHandle::shutdown`] to join it
+    thread: Arc<Mutex<Option<std::thread::JoinHandle<()>>>>,
+    /// Engine threads respawned after a panic
+    restarts: Arc<AtomicU32>,
 }
 
 impl EngineHandle {
     /// Spawn the engine on a dedicated thread and return a handle
     pub fn spawn() -> Self {
-        let (tx, mut rx) = mpsc::channel::<EngineCommand>(32);
+        Self::spawn_with_host(HostPreference::default())
+    }
 
-        std::thread::spawn(move || {
-            let mut engine = AudioEngine::new();
+    /// Spawn the engine using the given audio host preference
+    ///
+    /// The engine runs on a worker thread watched by a supervisor thread. If
+    /// the worker panics, the supervisor spawns a new one with the last
+    /// device, sample rate and signal settings (restarting monitoring if it
+    /// was running), so handles keep working. Queued commands are kept; only
+    /// the command that panicked fails with "Engine thread died".
+    pub fn spawn_with_host(host: HostPreference) -> Self {
+        let (tx, mut rx) = mpsc::channel::<EngineCommand>(32);
+        let restarts = Arc::new(AtomicU32::new(0));
 
-            while let Some(cmd) = rx.blocking_recv() {
-                match cmd {
-                    EngineCommand::ListDevices { reply } => {
-                        let _ = reply.send(AudioEngine::list_devices());
-                    }
-                    EngineCommand::SelectDevice { name, reply } => {
-                        let _ = reply.send(engine.select_device(&name));
-                    }
-                    EngineCommand::SetSampleRate { rate } => {
-                        engine.set_sample_rate(rate);
-                    }
-                    EngineCommand::Start { reply } => {
-                        let _ = reply.send(engine.start());
-                    }
-                    EngineCommand::Stop { reply } => {
-                        let _ = reply.send(engine.stop());
-                    }
-                    EngineCommand::GetStatus { reply } => {
-                        let _ = reply.send(EngineStatus {
-                            state: engine.state(),
-                            device_name: engine.device_name().map(|s| s.to_string()),
-                            sample_rate: engine.sample_rate(),
-                        });
-                    }
-                    EngineCommand::Analyze { reply } => {
-                        let _ = reply.send(engine.analyze());
-                    }
-                    EngineCommand::GetSampleCounts { reply } => {
-                        let _ = reply.send(engine.sample_counts());
-                    }
-                    EngineCommand::IsStreamInvalidated { reply } => {
-                        let _ = reply.send(engine.is_stream_invalidated());
+        let thread = std::thread::spawn({
+            let restarts = restarts.clone();
+            move || {
+                let settings = Mutex::new(None);
+                loop {
+                    // A worker that panicked while restoring leaves no
+                    // settings, so the next one starts fresh instead of
+                    // repeating the panic
+                    let previous = settings.lock().unwrap_or_else(|e| e.into_inner()).take();
+                    let worker = std::thread::scope(|scope| {
+                        scope
+                            .spawn(|| run_engine(host, &mut rx, &settings, previous))
+                            .join()
+                    });
+                    if worker.is_ok() {
+                        break;
                     }
+                    let count = restarts.fetch_add(1, Ordering::Relaxed) + 1;
+                    tracing::error!(restarts = count, "Engine thread panicked, respawning");
                 }
             }
         });
 
-        Self { tx }
+        Self {
+            tx,
+            thread: Arc::new(Mutex::new(Some(thread))),
+            restarts,
+        }
+    }
+
+    /// Times the engine thread was respawned after a panic
+    pub fn restarts(&self) -> u32 {
+        self.restarts.load(Ordering::Relaxed)
+    }
+
+    /// Panic the engine thread, as a driver bug would
+    #[cfg(test)]
+    async fn crash(&self) {
+        let _ = self.tx.send(EngineCommand::Crash).await;
+    }
+
+    /// Stop the engine, end its thread and wait up to `timeout` for it to exit
+    ///
+    /// Every handle is unusable afterwards (commands fail with
+    /// "Engine thread died").
+    pub async fn shutdown(&self, timeout: std::time::Duration) -> anyhow::Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::Shutdown { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        tokio::time::timeout(timeout, rx)
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine did not stop within {:?}", timeout))?
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+
+        let thread = self.thread.lock().ok().and_then(|mut t| t.take());
+        if let Some(thread) = thread {
+            let deadline = tokio::time::Instant::now() + timeout;
+            while !thread.is_finished() {
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(anyhow::anyhow!("Engine thread did not exit"));
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            thread
+                .join()
+                .map_err(|_| anyhow::anyhow!("Engine thread panicked"))?;
+        }
+        Ok(())
     }
 
     pub async fn list_devices(&self) -> anyhow::Result<Vec<DeviceInfo>> {
@@ -133,10 +503,91 @@ impl EngineHandle {
             .map_err(|_| anyhow::anyhow!("Engine thread died"))?
     }
 
+    /// Send on `output` and receive on `input` (same name = single device)
+    pub async fn select_devices(&self, output: String, input: String) -> anyhow::Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::SelectDevices {
+                output,
+                input,
+                reply,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?
+    }
+
+    /// Release the selected device (stops the engine if running)
+    pub async fn deselect_device(&self) -> anyhow::Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::DeselectDevice { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?
+    }
+
     pub async fn set_sample_rate(&self, rate: u32) {
         let _ = self.tx.send(EngineCommand::SetSampleRate { rate }).await;
     }
 
+    /// Set the counter silence threshold for frame loss analysis
+    pub async fn set_silence_threshold_ms(&self, ms: u32) {
+        let _ = self
+            .tx
+            .send(EngineCommand::SetSilenceThreshold { ms })
+            .await;
+    }
+
+    /// Set the unmatched analysis cycles before the MLS fallback (0 = disabled)
+    pub async fn set_mls_fallback_cycles(&self, cycles: u32) {
+        let _ = self
+            .tx
+            .send(EngineCommand::SetMlsFallbackCycles { cycles })
+            .await;
+    }
+
+    /// Set the time after a burst match during which detections are echoes (ms, 0 = disabled)
+    pub async fn set_echo_guard_ms(&self, ms: u32) {
+        let _ = self.tx.send(EngineCommand::SetEchoGuard { ms }).await;
+    }
+
+    /// Place the burst and frame counter on the given channel indices
+    pub async fn set_signal_channels(&self, burst: usize, counter: usize) {
+        let _ = self
+            .tx
+            .send(EngineCommand::SetSignalChannels { burst, counter })
+            .await;
+    }
+
+    /// Set the burst amplitude, applied on next start
+    pub async fn set_burst_amplitude(&self, amplitude: f32) {
+        let _ = self
+            .tx
+            .send(EngineCommand::SetBurstAmplitude { amplitude })
+            .await;
+    }
+
+    /// Set the burst waveform, applied on next start
+    pub async fn set_burst_waveform(&self, waveform: BurstWaveform) {
+        let _ = self
+            .tx
+            .send(EngineCommand::SetBurstWaveform { waveform })
+            .await;
+    }
+
+    /// Discard the latency analyzer's running average and pending bursts
+    pub async fn reset_analyzer(&self) {
+        let _ = self.tx.send(EngineCommand::ResetAnalyzer).await;
+    }
+
+    /// Request a fixed ASIO buffer size in frames (0 = driver default)
+    pub async fn set_buffer_size(&self, frames: u32) {
+        let _ = self.tx.send(EngineCommand::SetBufferSize { frames }).await;
+    }
+
     pub async fn start(&self) -> anyhow::Result<()> {
         let (reply, rx) = oneshot::channel();
         self.tx
@@ -157,6 +608,68 @@ impl EngineHandle {
             .map_err(|_| anyhow::anyhow!("Engine thread died"))?
     }
 
+    /// Restart streams on the current device without re-selecting it
+    pub async fn restart_streams(&self) -> anyhow::Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::RestartStreams { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?
+    }
+
+    /// Start recording input channel 0 to a WAV file
+    pub async fn start_capture(
+        &self,
+        path: std::path::PathBuf,
+        duration_secs: u32,
+    ) -> anyhow::Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::StartCapture {
+                path,
+                duration_secs,
+                reply,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?
+    }
+
+    /// Play a sine test tone on an output channel in place of the burst
+    pub async fn play_test_tone(
+        &self,
+        freq_hz: f32,
+        channel: usize,
+        duration_ms: u32,
+    ) -> anyhow::Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::PlayTestTone {
+                freq_hz,
+                channel,
+                duration_ms,
+                reply,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?
+    }
+
+    /// Stop the current capture and return the path of the closed WAV file
+    pub async fn stop_capture(&self) -> anyhow::Result<std::path::PathBuf> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::StopCapture { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?
+    }
+
     pub async fn get_status(&self) -> anyhow::Result<EngineStatus> {
         let (reply, rx) = oneshot::channel();
         self.tx
@@ -175,6 +688,48 @@ impl EngineHandle {
         rx.await.map_err(|_| anyhow::anyhow!("Engine thread died"))
     }
 
+    /// Start the engine, wait for the first valid measurement, then stop it
+    ///
+    /// Polls [`analyze`](Self::analyze) until a result passes `is_valid`
+    /// (normally [`ServerConfig::has_valid_signal`]) and returns it with the
+    /// sample rate the streams ran at. Returns `None` if none arrives within
+    /// `timeout`.
+    pub async fn probe(
+        &self,
+        timeout: std::time::Duration,
+        is_valid: impl Fn(&AnalysisResult) -> bool,
+    ) -> anyhow::Result<Option<(AnalysisResult, u32)>> {
+        self.start().await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut found = None;
+        while tokio::time::Instant::now() < deadline {
+            match self.analyze().await {
+                Ok(Some(result)) if is_valid(&result) => {
+                    // Read the rate before stopping, which clears it
+                    let sample_rate = match self.get_status().await {
+                        Ok(status) => status.sample_rate,
+                        Err(e) => {
+                            let _ = self.stop().await;
+                            return Err(e);
+                        }
+                    };
+                    found = Some((result, sample_rate));
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    let _ = self.stop().await;
+                    return Err(e);
+                }
+            }
+            tokio::time::sleep(PROBE_POLL_INTERVAL).await;
+        }
+
+        self.stop().await?;
+        Ok(found)
+    }
+
     /// Get sample counts from the audio engine
     ///
     /// Returns (output_samples, input_samples) as cumulative counters
@@ -198,6 +753,108 @@ impl EngineHandle {
             .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
         rx.await.map_err(|_| anyhow::anyhow!("Engine thread died"))
     }
+
+    /// Get per-channel input peak levels since the previous call
+    ///
+    /// Returns linear peaks for the first few input channels (empty when stopped)
+    pub async fn get_input_levels(&self) -> anyhow::Result<Vec<f32>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::GetInputLevels { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await.map_err(|_| anyhow::anyhow!("Engine thread died"))
+    }
+
+    /// Burst detector envelope, noise floor and SNR (None while stopped)
+    pub async fn get_detector_diagnostics(&self) -> anyhow::Result<Option<DetectorDiagnostics>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::GetDetectorDiagnostics { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await.map_err(|_| anyhow::anyhow!("Engine thread died"))
+    }
+
+    /// Peak and RMS of the burst and counter inputs (None while stopped)
+    pub async fn get_levels(&self) -> anyhow::Result<Option<InputLevels>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(EngineCommand::GetLevels { reply })
+            .await
+            .map_err(|_| anyhow::anyhow!("Engine thread died"))?;
+        rx.await.map_err(|_| anyhow::anyhow!("Engine thread died"))
+    }
+}
+
+/// Reload handle for the global tracing filter
+///
+/// The subscriber must be built as `registry().with(reload_layer)` so the
+/// filter layer sits directly on the [`tracing_subscriber::Registry`].
+pub type LogFilterHandle =
+    tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>;
+
+/// Target raised to TRACE during a timed debug trace
+pub const DEBUG_TRACE_TARGET: &str = "audiotester_core";
+
+/// Time-bounded TRACE logging for `audiotester_core`
+///
+/// Starting a trace saves the current filter and raises the core crate to
+/// TRACE; only the most recently started trace restores it, so a second
+/// request while one is running extends it instead of reverting early.
+#[derive(Debug, Default)]
+pub struct DebugTrace {
+    /// Filter to restore when the trace ends (None while no trace is active)
+    saved_filter: Mutex<Option<String>>,
+    /// Incremented on every start; a finish only applies to the latest start
+    generation: AtomicU64,
+}
+
+impl DebugTrace {
+    /// Raise `audiotester_core` to TRACE, returning the trace generation and
+    /// the active filter
+    pub fn start(&self, handle: &LogFilterHandle) -> anyhow::Result<(u64, String)> {
+        let mut saved = self
+            .saved_filter
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Debug trace lock poisoned"))?;
+        let current = handle.with_current(|f| f.to_string())?;
+        let traced = api::merge_log_directive(&current, Some(DEBUG_TRACE_TARGET), "trace");
+        handle.reload(tracing_subscriber::EnvFilter::try_new(&traced)?)?;
+        if saved.is_none() {
+            *saved = Some(current);
+        }
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        Ok((generation, traced))
+    }
+
+    /// Restore the saved filter if `generation` is the latest trace
+    ///
+    /// Returns the restored filter, or None when a newer trace superseded
+    /// this one or no trace is active.
+    pub fn finish(
+        &self,
+        handle: &LogFilterHandle,
+        generation: u64,
+    ) -> anyhow::Result<Option<String>> {
+        let mut saved = self
+            .saved_filter
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Debug trace lock poisoned"))?;
+        if self.generation.load(Ordering::Relaxed) != generation {
+            return Ok(None);
+        }
+        let Some(filter) = saved.take() else {
+            return Ok(None);
+        };
+        handle.reload(tracing_subscriber::EnvFilter::try_new(&filter)?)?;
+        Ok(Some(filter))
+    }
+
+    /// Whether a trace is currently raising the log level
+    pub fn is_active(&self) -> bool {
+        self.saved_filter.lock().is_ok_and(|saved| saved.is_some())
+    }
 }
 
 /// Shared application state accessible from all handlers
@@ -213,6 +870,221 @@ pub struct AppState {
     pub config: ServerConfig,
     /// Log directory for diagnostic file logging
     pub log_dir: Option<std::path::PathBuf>,
+    /// Handle for adjusting the log filter at runtime
+    pub log_filter: Option<LogFilterHandle>,
+    /// Timed TRACE logging started via `POST /api/v1/debug-trace`
+    pub debug_trace: Arc<DebugTrace>,
+    /// Recent structured log events (fed by an [`event_log::EventLogLayer`])
+    pub event_log: Arc<event_log::EventLog>,
+    /// Identifier of this application run, included in support snapshots
+    pub session_id: String,
+    /// Signal-loss timeouts used by the monitoring loop (adjustable at runtime)
+    pub loss_timeouts: Arc<SignalLossTimeouts>,
+    /// Latency alarm thresholds used for the tray status (adjustable at runtime)
+    pub latency_thresholds: Arc<LatencyThresholds>,
+    /// Port the server actually bound to (0 until listening)
+    pub bound_port: Arc<AtomicU16>,
+    /// Measurements are analyzed but not recorded while set
+    pub recording_paused: Arc<AtomicBool>,
+}
+
+/// Interval between analysis polls while probing
+const PROBE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// Smallest accepted analysis timeout before declaring signal loss (ms)
+pub const MIN_SIGNAL_TIMEOUT_MS: u64 = 100;
+
+/// Signal-loss detection timeouts shared with the monitoring loop
+///
+/// Seeded from [`ServerConfig`] and adjustable via `PATCH /api/v1/config`.
+#[derive(Debug)]
+pub struct SignalLossTimeouts {
+    signal_timeout_ms: AtomicU64,
+    reconnect_after_loss_ms: AtomicU64,
+}
+
+impl SignalLossTimeouts {
+    /// Create timeouts without validation (see [`SignalLossTimeouts::validate`])
+    pub fn new(signal_timeout_ms: u64, reconnect_after_loss_ms: u64) -> Self {
+        Self {
+            signal_timeout_ms: AtomicU64::new(signal_timeout_ms),
+            reconnect_after_loss_ms: AtomicU64::new(reconnect_after_loss_ms),
+        }
+    }
+
+    /// Check a (signal_timeout_ms, reconnect_after_loss_ms) pair
+    pub fn validate(signal_timeout_ms: u64, reconnect_after_loss_ms: u64) -> Result<(), String> {
+        if signal_timeout_ms < MIN_SIGNAL_TIMEOUT_MS {
+            return Err(format!(
+                "signal_timeout_ms must be at least {} (got {})",
+                MIN_SIGNAL_TIMEOUT_MS, signal_timeout_ms
+            ));
+        }
+        if reconnect_after_loss_ms < signal_timeout_ms {
+            return Err(format!(
+                "reconnect_after_loss_ms ({}) must be >= signal_timeout_ms ({})",
+                reconnect_after_loss_ms, signal_timeout_ms
+            ));
+        }
+        Ok(())
+    }
+
+    /// Analysis timeout before declaring signal loss (ms)
+    pub fn signal_timeout_ms(&self) -> u64 {
+        self.signal_timeout_ms.load(Ordering::Relaxed)
+    }
+
+    /// Time the signal must stay lost before reconnecting (ms)
+    pub fn reconnect_after_loss_ms(&self) -> u64 {
+        self.reconnect_after_loss_ms.load(Ordering::Relaxed)
+    }
+
+    /// Validate and apply new timeouts
+    pub fn set(&self, signal_timeout_ms: u64, reconnect_after_loss_ms: u64) -> Result<(), String> {
+        Self::validate(signal_timeout_ms, reconnect_after_loss_ms)?;
+        self.signal_timeout_ms
+            .store(signal_timeout_ms, Ordering::Relaxed);
+        self.reconnect_after_loss_ms
+            .store(reconnect_after_loss_ms, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Severity of a latency measurement against the alarm thresholds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LatencyLevel {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// Latency alarm thresholds shared with the monitoring loop
+///
+/// Seeded from [`ServerConfig`] and adjustable via `PATCH /api/v1/config`.
+/// Values are stored as `f64` bits so they can be read without locking.
+#[derive(Debug)]
+pub struct LatencyThresholds {
+    warning_ms: AtomicU64,
+    error_ms: AtomicU64,
+}
+
+impl LatencyThresholds {
+    /// Create thresholds without validation (see [`LatencyThresholds::validate`])
+    pub fn new(warning_ms: f64, error_ms: f64) -> Self {
+        Self {
+            warning_ms: AtomicU64::new(warning_ms.to_bits()),
+            error_ms: AtomicU64::new(error_ms.to_bits()),
+        }
+    }
+
+    /// Check a (latency_warning_ms, latency_error_ms) pair
+    pub fn validate(warning_ms: f64, error_ms: f64) -> Result<(), String> {
+        if !warning_ms.is_finite() || warning_ms <= 0.0 {
+            return Err(format!(
+                "latency_warning_ms must be greater than 0 (got {})",
+                warning_ms
+            ));
+        }
+        if !error_ms.is_finite() || error_ms < warning_ms {
+            return Err(format!(
+                "latency_error_ms ({}) must be >= latency_warning_ms ({})",
+                error_ms, warning_ms
+            ));
+        }
+        Ok(())
+    }
+
+    /// Latency at or above which the status turns to warning (ms)
+    pub fn warning_ms(&self) -> f64 {
+        f64::from_bits(self.warning_ms.load(Ordering::Relaxed))
+    }
+
+    /// Latency at or above which the status turns to error (ms)
+    pub fn error_ms(&self) -> f64 {
+        f64::from_bits(self.error_ms.load(Ordering::Relaxed))
+    }
+
+    /// Validate and apply new thresholds
+    pub fn set(&self, warning_ms: f64, error_ms: f64) -> Result<(), String> {
+        Self::validate(warning_ms, error_ms)?;
+        self.warning_ms
+            .store(warning_ms.to_bits(), Ordering::Relaxed);
+        self.error_ms.store(error_ms.to_bits(), Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Classify a measurement; a value exactly at a threshold crosses it
+    pub fn classify(&self, latency_ms: f64) -> LatencyLevel {
+        if latency_ms >= self.error_ms() {
+            LatencyLevel::Error
+        } else if latency_ms >= self.warning_ms() {
+            LatencyLevel::Warning
+        } else {
+            LatencyLevel::Ok
+        }
+    }
+}
+
+/// How the monitoring loop reconnects after an engine error or signal loss
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconnectStrategy {
+    /// Stop, re-select the device (fresh ASIO host handle), then start
+    #[default]
+    Full,
+    /// Rebuild streams on the existing device handle, escalating to
+    /// [`ReconnectStrategy::Full`] after [`STREAMS_ONLY_MAX_ATTEMPTS`] failures
+    StreamsOnly,
+}
+
+/// Auto-configure schedule, reported by `GET /api/v1/config/effective`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct AutoConfigInfo {
+    /// Wait before the first select+start attempt (s)
+    pub delay_secs: u64,
+    /// Select+start attempts before giving up
+    pub attempts: u32,
+    /// Wait between attempts (s)
+    pub interval_secs: u64,
+}
+
+/// Number of light (streams-only) reconnect attempts before escalating to a full re-select
+pub const STREAMS_ONLY_MAX_ATTEMPTS: u32 = 2;
+
+impl ReconnectStrategy {
+    /// Whether reconnect attempt `attempt` (1-based) should re-select the device
+    pub fn is_full_attempt(self, attempt: u32) -> bool {
+        match self {
+            ReconnectStrategy::Full => true,
+            ReconnectStrategy::StreamsOnly => attempt > STREAMS_ONLY_MAX_ATTEMPTS,
+        }
+    }
+}
+
+impl std::str::FromStr for ReconnectStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(ReconnectStrategy::Full),
+            "streams_only" | "streams-only" | "streamsonly" => Ok(ReconnectStrategy::StreamsOnly),
+            other => Err(format!(
+                "Unknown reconnect strategy: {} (expected full or streams_only)",
+                other
+            )),
+        }
+    }
+}
+
+/// What the monitoring loop does after a consecutive engine failure
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReconnectAction {
+    /// Back off and try to reconnect again
+    Retry,
+    /// The attempt limit was just exceeded: record the failed disconnection once
+    GiveUp,
+    /// Already gave up; wait for manual intervention
+    Idle,
 }
 
 /// Server configuration
@@ -222,6 +1094,132 @@ pub struct ServerConfig {
     pub port: u16,
     /// Bind address
     pub bind_addr: String,
+    /// Reconnect strategy used by the monitoring loop
+    pub reconnect_strategy: ReconnectStrategy,
+    /// Reconnection attempts after an engine error before giving up (0 = unlimited)
+    pub max_reconnect_attempts: u32,
+    /// Time after each engine start during which signal loss is not reported (ms)
+    pub startup_grace_ms: u64,
+    /// File statistics are persisted to (None disables persistence)
+    pub stats_path: Option<std::path::PathBuf>,
+    /// How often statistics are auto-saved to `stats_path` (seconds, 0 = disabled)
+    pub stats_save_interval_secs: u64,
+    /// File the reference profile is saved to (None disables baselines)
+    pub baseline_path: Option<std::path::PathBuf>,
+    /// File dashboard preferences are saved to (None disables preferences)
+    pub preferences_path: Option<std::path::PathBuf>,
+    /// Directory loss incidents are captured to (None disables capture)
+    pub incident_dir: Option<std::path::PathBuf>,
+    /// Lost samples in one analysis cycle that trigger an incident capture
+    pub incident_min_loss: u64,
+    /// Show desktop notifications when the signal is lost or recovered
+    pub notifications_enabled: bool,
+    /// Time without a valid analysis before declaring signal loss (ms)
+    pub signal_timeout_ms: u64,
+    /// Time the signal must stay lost before reconnecting the engine (ms)
+    pub reconnect_after_loss_ms: u64,
+    /// Bearer token required for mutating API requests (None = open)
+    pub auth_token: Option<String>,
+    /// Require the token for every request, not only mutating API calls
+    pub auth_all: bool,
+    /// Origins allowed to call the API from other sites (empty = any origin)
+    pub allowed_origins: Vec<String>,
+    /// Incoming webhook URL notified on disconnections and reconnections
+    pub alert_webhook: Option<String>,
+    /// OSC receiver sent live metrics each analysis cycle (None = disabled)
+    pub osc_target: Option<std::net::SocketAddr>,
+    /// MIDI output port the tray status is sent to as notes (None = disabled)
+    pub midi_port: Option<String>,
+    /// Latency at or above which the tray shows a warning (ms)
+    pub latency_warning_ms: f64,
+    /// Latency at or above which the tray shows an error (ms)
+    pub latency_error_ms: f64,
+    /// Lowest confidence the monitoring loop accepts as a valid signal (0.0 to 1.0)
+    pub min_confidence: f32,
+    /// Latency below which a measurement is not a valid signal (ms)
+    pub min_latency_ms: f64,
+    /// Latency at or above which a measurement is not a valid signal (ms)
+    pub max_latency_ms: f64,
+    /// Latency the audio path is expected to have (ms, None disables the range check)
+    pub expected_latency_ms: Option<f64>,
+    /// Deviation from `expected_latency_ms` still considered in range (ms)
+    pub latency_tolerance_ms: f64,
+    /// Auto-configure schedule (None when auto-configure is not enabled)
+    pub autoconfig: Option<AutoConfigInfo>,
+}
+
+impl ServerConfig {
+    /// Whether an analysis result counts as a valid signal in the monitoring loop
+    ///
+    /// Latency at or above `max_latency_ms` indicates burst mismatching
+    /// rather than a real path; latency below `min_latency_ms` is the output
+    /// bleeding into the input at near-zero delay; confidence below
+    /// `min_confidence` is noise.
+    pub fn has_valid_signal(&self, latency_ms: f64, confidence: f32) -> bool {
+        latency_ms > 0.0
+            && latency_ms >= self.min_latency_ms
+            && latency_ms < self.max_latency_ms
+            && confidence >= self.min_confidence
+    }
+
+    /// Check that the valid-latency window is not empty
+    ///
+    /// With `min_latency_ms >= max_latency_ms` no measurement could ever
+    /// count as a valid signal and the link would report permanent loss.
+    pub fn validate_latency_window(min_latency_ms: f64, max_latency_ms: f64) -> Result<(), String> {
+        if min_latency_ms >= max_latency_ms {
+            return Err(format!(
+                "min_latency_ms ({}) must be < max_latency_ms ({})",
+                min_latency_ms, max_latency_ms
+            ));
+        }
+        Ok(())
+    }
+
+    /// Whether a latency deviates from `expected_latency_ms` by more than the tolerance
+    ///
+    /// A deviation of exactly `latency_tolerance_ms` is still in range.
+    /// Always false when no expected latency is configured.
+    pub fn latency_out_of_range(&self, latency_ms: f64) -> bool {
+        self.expected_latency_ms
+            .is_some_and(|expected| (latency_ms - expected).abs() > self.latency_tolerance_ms)
+    }
+
+    /// Next value of the `latency_out_of_range` flag after one analysis cycle
+    ///
+    /// Only a valid signal can change the flag; without one the previous
+    /// value is kept, so a dropout neither raises nor clears it.
+    pub fn update_latency_out_of_range(
+        &self,
+        was_out_of_range: bool,
+        has_valid_signal: bool,
+        latency_ms: f64,
+    ) -> bool {
+        if has_valid_signal {
+            self.latency_out_of_range(latency_ms)
+        } else {
+            was_out_of_range
+        }
+    }
+
+    /// Whether an engine started at `started_at` is still inside the startup grace period
+    pub fn in_startup_grace(&self, started_at: Option<std::time::Instant>) -> bool {
+        started_at
+            .map(|t| t.elapsed() < std::time::Duration::from_millis(self.startup_grace_ms))
+            .unwrap_or(false)
+    }
+
+    /// Action for the `failures`-th consecutive engine failure (1-based)
+    pub fn reconnect_action(&self, failures: u32) -> ReconnectAction {
+        let max = self.max_reconnect_attempts;
+        if max == 0 || failures <= max {
+            ReconnectAction::Retry
+        } else if failures == max + 1 {
+            ReconnectAction::GiveUp
+        } else {
+            ReconnectAction::Idle
+        }
+    }
 }
 
 impl Default for ServerConfig {
@@ -229,6 +1227,32 @@ impl Default for ServerConfig {
         Self {
             port: 8920,
             bind_addr: "0.0.0.0".to_string(),
+            reconnect_strategy: ReconnectStrategy::Full,
+            max_reconnect_attempts: 5,
+            startup_grace_ms: 2000,
+            stats_path: None,
+            stats_save_interval_secs: 60,
+            baseline_path: None,
+            preferences_path: None,
+            incident_dir: None,
+            incident_min_loss: incident::DEFAULT_INCIDENT_MIN_LOSS,
+            notifications_enabled: true,
+            signal_timeout_ms: 1000,
+            reconnect_after_loss_ms: 10000,
+            auth_token: None,
+            auth_all: false,
+            allowed_origins: Vec::new(),
+            alert_webhook: None,
+            osc_target: None,
+            midi_port: None,
+            latency_warning_ms: 50.0,
+            latency_error_ms: 50.0,
+            min_confidence: 0.3,
+            min_latency_ms: 0.05,
+            max_latency_ms: 100.0,
+            expected_latency_ms: None,
+            latency_tolerance_ms: 1.0,
+            autoconfig: None,
         }
     }
 }
@@ -242,16 +1266,172 @@ impl AppState {
         log_dir: Option<std::path::PathBuf>,
     ) -> Self {
         let (ws_tx, _) = tokio::sync::broadcast::channel(256);
+        let session_id = format!(
+            "{:x}-{:x}",
+            chrono::Utc::now().timestamp_millis(),
+            std::process::id()
+        );
+        let loss_timeouts = Arc::new(SignalLossTimeouts::new(
+            config.signal_timeout_ms,
+            config.reconnect_after_loss_ms,
+        ));
+        let latency_thresholds = Arc::new(LatencyThresholds::new(
+            config.latency_warning_ms,
+            config.latency_error_ms,
+        ));
         Self {
             engine,
             stats,
             ws_tx,
             config,
             log_dir,
+            log_filter: None,
+            debug_trace: Arc::new(DebugTrace::default()),
+            event_log: Arc::new(event_log::EventLog::new()),
+            session_id,
+            loss_timeouts,
+            latency_thresholds,
+            bound_port: Arc::new(AtomicU16::new(0)),
+            recording_paused: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Attach a reload handle so the log level can be changed via the API
+    pub fn with_log_filter(mut self, handle: LogFilterHandle) -> Self {
+        self.log_filter = Some(handle);
+        self
+    }
+
+    /// Serve events from a log that is fed by the global subscriber
+    pub fn with_event_log(mut self, log: Arc<event_log::EventLog>) -> Self {
+        self.event_log = log;
+        self
+    }
+
+    /// Port the server is listening on (the configured port until bound)
+    pub fn port(&self) -> u16 {
+        match self.bound_port.load(Ordering::Relaxed) {
+            0 => self.config.port,
+            port => port,
+        }
+    }
+
+    /// Lock the statistics store, recovering from mutex poisoning
+    pub fn stats_lock(&self) -> MutexGuard<'_, StatsStore> {
+        lock_stats(&self.stats)
+    }
+
+    /// Whether recording is paused via `POST /api/v1/recording`
+    pub fn is_recording_paused(&self) -> bool {
+        self.recording_paused.load(Ordering::Relaxed)
+    }
+
+    /// Pause or resume recording measurements into the store
+    pub fn set_recording_paused(&self, paused: bool) {
+        self.recording_paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Record an analysis result's latency, confidence, loss and corruption
+    ///
+    /// Returns false, leaving the store untouched, while recording is paused.
+    pub fn record_analysis(&self, result: &AnalysisResult) -> bool {
+        if self.is_recording_paused() {
+            return false;
+        }
+        let mut store = self.stats_lock();
+        // A stale result repeats the last latency; once its decayed confidence
+        // is no longer a valid signal, recording it would flat-line the graph
+        // through the outage
+        if !result.stale
+            || self
+                .config
+                .has_valid_signal(result.latency_ms, result.confidence)
+        {
+            store.record_latency(result.latency_ms);
+        }
+        store.record_confidence(result.confidence);
+        store.set_latency_method(result.method);
+        if result.lost_samples > 0 {
+            let timestamp = result.loss_timestamp.unwrap_or_else(chrono::Utc::now);
+            store.record_loss_at(result.lost_samples as u64, timestamp);
+        }
+        if result.corrupted_samples > 0 {
+            store.record_corruption(result.corrupted_samples as u64);
+        }
+        true
+    }
+
+    /// Update the loss estimate for a counter silent for `silent_for`
+    ///
+    /// Leaves the store untouched while recording is paused.
+    pub fn estimate_silent_loss(&self, silent_for: std::time::Duration, sample_rate: u32) {
+        if self.is_recording_paused() {
+            return;
+        }
+        self.stats_lock()
+            .estimate_silent_loss(silent_for, sample_rate);
+    }
+
+    /// Commit the counter-silence loss estimate to `total_lost` and clear it
+    ///
+    /// Returns the committed samples. Leaves the store untouched (and
+    /// returns 0) while recording is paused.
+    pub fn commit_estimated_loss(&self) -> u64 {
+        if self.is_recording_paused() {
+            return 0;
+        }
+        let mut store = self.stats_lock();
+        let estimated = store.stats().estimated_loss;
+        if estimated > 0 {
+            store.record_loss(estimated);
+        }
+        store.reset_estimated_loss();
+        estimated
+    }
+
+    /// Freeze the context around a loss of `lost_samples` into an [`incident::Incident`]
+    pub async fn incident_snapshot(&self, lost_samples: u64) -> incident::Incident {
+        let detector = self.engine.get_detector_diagnostics().await.ok().flatten();
+        let (latency_ms, confidence) = {
+            let store = self.stats_lock();
+            let history = store.latency_history();
+            let skip = history
+                .len()
+                .saturating_sub(incident::INCIDENT_LATENCY_POINTS);
+            let points = history
+                .iter()
+                .skip(skip)
+                .map(|m| (m.timestamp, m.value))
+                .collect();
+            (points, store.stats().last_confidence)
+        };
+        let mut log = self.event_log.query(None);
+        log.drain(..log.len().saturating_sub(incident::INCIDENT_LOG_EVENTS));
+        incident::Incident {
+            timestamp: chrono::Utc::now(),
+            lost_samples,
+            latency_ms,
+            confidence,
+            detector,
+            log,
         }
     }
 }
 
+/// Lock a statistics store, recovering from mutex poisoning
+///
+/// A panic while the lock was held poisons the mutex. The store only holds
+/// counters and history, so the data is still usable: the guard is recovered,
+/// the poison flag is cleared and the event is logged once per poisoning,
+/// keeping the dashboard and monitoring loop alive.
+pub fn lock_stats(stats: &Mutex<StatsStore>) -> MutexGuard<'_, StatsStore> {
+    stats.lock().unwrap_or_else(|poisoned| {
+        tracing::error!("Stats store mutex was poisoned by a panic, recovering");
+        stats.clear_poison();
+        poisoned.into_inner()
+    })
+}
+
 /// Serve the PWA manifest.json
 async fn serve_manifest() -> impl IntoResponse {
     (
@@ -260,46 +1440,194 @@ async fn serve_manifest() -> impl IntoResponse {
     )
 }
 
+/// Whether a browser `Origin` may use the API under `allowed_origins`
+///
+/// Any origin is allowed when the list is empty or contains `*`. Entries
+/// match without a trailing slash, the way browsers send the origin.
+pub fn origin_allowed(allowed_origins: &[String], origin: &str) -> bool {
+    allowed_origins.is_empty()
+        || allowed_origins.iter().any(|allowed| {
+            let allowed = allowed.trim();
+            allowed == "*" || allowed.trim_end_matches('/') == origin
+        })
+}
+
+/// Whether a request's `Origin` header passes `allowed_origins`
+///
+/// Requests without an `Origin` (curl, scripts) and same-origin requests
+/// from the dashboard itself (the origin names the `Host` being served)
+/// always pass; anything else must be listed.
+pub fn request_origin_allowed(allowed_origins: &[String], headers: &HeaderMap) -> bool {
+    let Some(origin) = headers.get(header::ORIGIN) else {
+        return true;
+    };
+    let origin = origin.to_str().unwrap_or_default();
+    let same_origin = headers
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .zip(origin.split_once("://"))
+        .is_some_and(|(host, (_, origin_host))| origin_host == host);
+    same_origin || origin_allowed(allowed_origins, origin)
+}
+
+/// CORS policy for the API
+///
+/// Any origin is allowed unless `allowed_origins` lists some (a `*` entry
+/// also allows any). The dashboard is served from the same origin and never
+/// needs CORS, so a list only affects pages hosted elsewhere.
+fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    if allowed_origins.is_empty() || allowed_origins.iter().any(|o| o.trim() == "*") {
+        return CorsLayer::permissive();
+    }
+    let origins: Vec<HeaderValue> = allowed_origins
+        .iter()
+        .filter_map(|origin| {
+            // Browsers send the origin without a trailing slash
+            let origin = origin.trim().trim_end_matches('/');
+            match HeaderValue::from_str(origin) {
+                Ok(value) => Some(value),
+                Err(_) => {
+                    tracing::warn!(origin, "Ignoring invalid CORS origin");
+                    None
+                }
+            }
+        })
+        .collect();
+    if origins.is_empty() {
+        tracing::error!(
+            ?allowed_origins,
+            "No valid CORS origin configured, all cross-origin requests will be refused"
+        );
+    }
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+}
+
 /// Build the Axum router with all routes
 pub fn build_router(state: AppState) -> Router {
-    Router::new()
+    let router = Router::new()
         // Leptos SSR pages
         .route("/", axum::routing::get(ui::dashboard::dashboard_page))
         .route("/settings", axum::routing::get(ui::settings::settings_page))
         // REST API
         .route("/api/v1/status", axum::routing::get(api::get_status))
+        .route("/api/v1/health", axum::routing::get(api::get_health))
         .route("/api/v1/stats", axum::routing::get(api::get_stats))
         .route("/api/v1/devices", axum::routing::get(api::list_devices))
+        .route(
+            "/api/v1/diagnostics",
+            axum::routing::get(api::get_diagnostics),
+        )
+        .route(
+            "/api/v1/device",
+            axum::routing::put(api::select_device).delete(api::clear_device),
+        )
         .route(
             "/api/v1/config",
             axum::routing::get(api::get_config).patch(api::update_config),
         )
+        .route(
+            "/api/v1/config/effective",
+            axum::routing::get(api::get_effective_config),
+        )
         .route(
             "/api/v1/monitoring",
             axum::routing::post(api::toggle_monitoring),
         )
         .route("/api/v1/reset", axum::routing::post(api::reset_stats))
+        .route("/api/v1/recording", axum::routing::post(api::set_recording))
+        .route("/api/v1/probe", axum::routing::post(api::probe_latency))
+        .route("/api/v1/selftest", axum::routing::post(api::run_selftest))
         .route(
             "/api/v1/loss-timeline",
             axum::routing::get(api::get_loss_timeline),
         )
+        .route(
+            "/api/v1/loss-timeline/report",
+            axum::routing::get(api::get_loss_report),
+        )
         .route(
             "/api/v1/latency-timeline",
             axum::routing::get(api::get_latency_timeline),
         )
+        .route("/api/v1/latency", axum::routing::get(api::get_latency))
+        .route(
+            "/api/v1/annotations",
+            axum::routing::get(api::get_annotations),
+        )
+        .route("/api/v1/detector", axum::routing::get(api::get_detector))
+        .route("/api/v1/levels", axum::routing::get(api::get_levels))
+        .route("/api/v1/spikes", axum::routing::get(api::get_spikes))
+        .route("/api/v1/summary", axum::routing::get(api::get_summary))
+        .route("/api/v1/baseline", axum::routing::post(api::save_baseline))
+        .route(
+            "/api/v1/baseline/diff",
+            axum::routing::get(api::get_baseline_diff),
+        )
+        .route(
+            "/api/v1/asio-events",
+            axum::routing::get(api::get_asio_events),
+        )
+        .route(
+            "/api/v1/jitter-histogram",
+            axum::routing::get(api::get_jitter_histogram),
+        )
         .route(
             "/api/v1/remote-url",
             axum::routing::get(api::get_remote_url),
         )
         // Diagnostic logs
         .route("/api/v1/logs", axum::routing::get(api::get_logs))
+        .route("/api/v1/logs/files", axum::routing::get(api::get_log_files))
+        .route("/api/v1/events/log", axum::routing::get(api::get_event_log))
+        .route("/api/v1/incidents", axum::routing::get(api::get_incidents))
+        .route(
+            "/api/v1/preferences",
+            axum::routing::get(api::get_preferences).put(api::set_preferences),
+        )
+        .route("/api/v1/capture", axum::routing::post(api::start_capture))
+        .route(
+            "/api/v1/test-tone",
+            axum::routing::post(api::play_test_tone),
+        )
+        .route(
+            "/api/v1/export/latency.csv",
+            axum::routing::get(api::export_latency_csv),
+        )
+        .route("/api/v1/snapshot", axum::routing::get(api::get_snapshot))
+        .route(
+            "/api/v1/log-level",
+            axum::routing::get(api::get_log_level).post(api::set_log_level),
+        )
+        .route(
+            "/api/v1/debug-trace",
+            axum::routing::post(api::start_debug_trace),
+        )
+        .route("/api/v1/openapi.json", axum::routing::get(api::get_openapi))
         // WebSocket
         .route("/api/v1/ws", axum::routing::get(ws::ws_handler))
+        .route("/metrics", axum::routing::get(metrics::get_metrics))
         // PWA manifest
         .route("/manifest.json", axum::routing::get(serve_manifest))
         // Static assets (CSS, JS)
-        .nest_service("/assets", ServeDir::new("assets"))
-        .layer(CorsLayer::permissive())
+        .nest_service("/assets", ServeDir::new("assets"));
+    // Server-side chart rendering pulls in plotters, so it is optional
+    #[cfg(feature = "chart")]
+    let router = router.route("/api/v1/chart.png", axum::routing::get(api::get_chart_png));
+    router
+        .layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            auth::require_token,
+        ))
+        .layer(cors_layer(&state.config.allowed_origins))
         .layer(SetResponseHeaderLayer::overriding(
             header::X_FRAME_OPTIONS,
             HeaderValue::from_static("DENY"),
@@ -311,14 +1639,517 @@ pub fn build_router(state: AppState) -> Router {
         .with_state(state)
 }
 
+/// Further ports tried above the configured one when it is in use
+pub const PORT_FALLBACK_ATTEMPTS: u16 = 10;
+
+/// Bind to `bind_addr:port`, moving up to the next free port if it is taken
+///
+/// Tries at most [`PORT_FALLBACK_ATTEMPTS`] ports above `port` and returns
+/// the last bind error when all of them fail.
+pub async fn bind_with_fallback(bind_addr: &str, port: u16) -> std::io::Result<TcpListener> {
+    let last_port = port.saturating_add(PORT_FALLBACK_ATTEMPTS);
+    let mut candidate = port;
+    loop {
+        match TcpListener::bind((bind_addr, candidate)).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) if candidate < last_port => {
+                tracing::error!(
+                    bind_addr,
+                    port = candidate,
+                    error = %e,
+                    "Cannot listen on port, trying the next one"
+                );
+                candidate += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 /// Start the web server
 pub async fn start_server(state: AppState) -> anyhow::Result<()> {
-    let addr = format!("{}:{}", state.config.bind_addr, state.config.port);
+    let listener = bind_with_fallback(&state.config.bind_addr, state.config.port).await?;
+    let addr = listener.local_addr()?;
+    state.bound_port.store(addr.port(), Ordering::Relaxed);
+    if addr.port() != state.config.port {
+        tracing::warn!(
+            configured = state.config.port,
+            port = addr.port(),
+            "Configured port in use, listening on a fallback port"
+        );
+    }
     let app = build_router(state);
 
-    let listener = TcpListener::bind(&addr).await?;
     tracing::info!(%addr, "Audiotester web server listening");
 
     axum::serve(listener, app).await?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_trace_raises_and_restores_level() {
+        use tracing_subscriber::filter::LevelFilter;
+        let (_layer, handle) = tracing_subscriber::reload::Layer::<
+            tracing_subscriber::EnvFilter,
+            tracing_subscriber::Registry,
+        >::new(tracing_subscriber::EnvFilter::new("info"));
+        let level = || handle.with_current(|f| f.max_level_hint()).unwrap();
+        assert_eq!(level(), Some(LevelFilter::INFO));
+
+        let trace = DebugTrace::default();
+        let (first, filter) = trace.start(&handle).unwrap();
+        assert!(filter.contains("audiotester_core=trace"));
+        assert_eq!(level(), Some(LevelFilter::TRACE));
+
+        // A newer trace supersedes the first timer
+        let (second, _) = trace.start(&handle).unwrap();
+        assert_eq!(trace.finish(&handle, first).unwrap(), None);
+        assert_eq!(level(), Some(LevelFilter::TRACE));
+
+        assert_eq!(
+            trace.finish(&handle, second).unwrap().as_deref(),
+            Some("info")
+        );
+        assert_eq!(level(), Some(LevelFilter::INFO));
+        assert!(!trace.is_active());
+    }
+
+    #[test]
+    fn test_reconnect_strategy_parse() {
+        assert_eq!("full".parse(), Ok(ReconnectStrategy::Full));
+        assert_eq!(" Streams_Only ".parse(), Ok(ReconnectStrategy::StreamsOnly));
+        assert!("reboot".parse::<ReconnectStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_startup_grace_period() {
+        let config = ServerConfig::default();
+        assert!(config.in_startup_grace(Some(std::time::Instant::now())));
+        assert!(!config.in_startup_grace(None));
+
+        let started = std::time::Instant::now() - std::time::Duration::from_secs(3);
+        assert!(!config.in_startup_grace(Some(started)));
+
+        let disabled = ServerConfig {
+            startup_grace_ms: 0,
+            ..Default::default()
+        };
+        assert!(!disabled.in_startup_grace(Some(std::time::Instant::now())));
+    }
+
+    #[test]
+    fn test_signal_loss_timeouts_validation() {
+        let config = ServerConfig::default();
+        let timeouts =
+            SignalLossTimeouts::new(config.signal_timeout_ms, config.reconnect_after_loss_ms);
+        assert_eq!(timeouts.signal_timeout_ms(), 1000);
+        assert_eq!(timeouts.reconnect_after_loss_ms(), 10000);
+
+        assert!(timeouts.set(2000, 2000).is_ok());
+        assert_eq!(timeouts.reconnect_after_loss_ms(), 2000);
+
+        // Rejected updates leave the previous values in place
+        assert!(timeouts.set(3000, 2000).is_err());
+        assert!(timeouts.set(MIN_SIGNAL_TIMEOUT_MS - 1, 5000).is_err());
+        assert_eq!(timeouts.signal_timeout_ms(), 2000);
+    }
+
+    #[test]
+    fn test_latency_thresholds_boundaries() {
+        let thresholds = LatencyThresholds::new(15.0, 30.0);
+        assert_eq!(thresholds.classify(14.99), LatencyLevel::Ok);
+        assert_eq!(thresholds.classify(15.0), LatencyLevel::Warning);
+        assert_eq!(thresholds.classify(29.99), LatencyLevel::Warning);
+        assert_eq!(thresholds.classify(30.0), LatencyLevel::Error);
+
+        // Rejected updates leave the previous values in place
+        assert!(thresholds.set(20.0, 10.0).is_err());
+        assert!(thresholds.set(0.0, 10.0).is_err());
+        assert!(thresholds.set(f64::NAN, 10.0).is_err());
+        assert_eq!(thresholds.warning_ms(), 15.0);
+        assert_eq!(thresholds.error_ms(), 30.0);
+
+        assert!(thresholds.set(20.0, 20.0).is_ok());
+        assert_eq!(thresholds.classify(20.5), LatencyLevel::Error);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_joins_engine_thread() {
+        let engine = EngineHandle::spawn();
+        let other = engine.clone();
+        engine
+            .shutdown(std::time::Duration::from_secs(2))
+            .await
+            .unwrap();
+
+        assert!(engine.thread.lock().unwrap().is_none());
+        assert!(other.get_status().await.is_err());
+        assert!(other
+            .shutdown(std::time::Duration::from_secs(2))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_engine_respawned_after_panic() {
+        let engine = EngineHandle::spawn();
+        let other = engine.clone();
+        engine.set_sample_rate(44100).await;
+        engine.set_burst_amplitude(0.3).await;
+        engine.crash().await;
+
+        // Served by a new engine thread that kept the settings
+        let status = other.get_status().await.unwrap();
+        assert_eq!(engine.restarts(), 1);
+        assert_eq!(status.requested_sample_rate, 44100);
+        assert_eq!(status.burst_amplitude, 0.3);
+
+        // And it keeps taking commands, including shutdown
+        engine.set_sample_rate(48000).await;
+        assert_eq!(
+            engine.get_status().await.unwrap().requested_sample_rate,
+            48000
+        );
+        engine
+            .shutdown(std::time::Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(engine.restarts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowlist() {
+        async fn serve(allowed_origins: &[&str]) -> String {
+            let state = AppState::new(
+                EngineHandle::spawn(),
+                Arc::new(Mutex::new(StatsStore::new())),
+                ServerConfig {
+                    allowed_origins: allowed_origins.iter().map(|o| o.to_string()).collect(),
+                    ..Default::default()
+                },
+                None,
+            );
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                axum::serve(listener, build_router(state)).await.unwrap();
+            });
+            format!("http://{}", addr)
+        }
+        let client = reqwest::Client::new();
+        let allow_origin = |resp: &reqwest::Response| {
+            resp.headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .map(|v| v.to_str().unwrap().to_string())
+        };
+
+        // Default: any origin
+        let base = serve(&[]).await;
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .header(header::ORIGIN, "http://elsewhere.example")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(allow_origin(&resp).as_deref(), Some("*"));
+
+        let base = serve(&["http://studio.local:8080/"]).await;
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .header(header::ORIGIN, "http://studio.local:8080")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(
+            allow_origin(&resp).as_deref(),
+            Some("http://studio.local:8080")
+        );
+
+        // A disallowed origin gets no CORS headers, neither on reads nor on
+        // the preflight of a mutating request
+        let resp = client
+            .get(format!("{}/api/v1/stats", base))
+            .header(header::ORIGIN, "http://elsewhere.example")
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+        assert_eq!(allow_origin(&resp), None);
+        let resp = client
+            .request(reqwest::Method::OPTIONS, format!("{}/api/v1/reset", base))
+            .header(header::ORIGIN, "http://elsewhere.example")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(allow_origin(&resp), None);
+    }
+
+    #[test]
+    fn test_valid_signal_thresholds() {
+        let config = ServerConfig::default();
+        assert!(config.has_valid_signal(5.0, 0.3));
+        // A short direct loopback is a real signal
+        assert!(config.has_valid_signal(0.5, 0.9));
+        assert!(!config.has_valid_signal(5.0, 0.29));
+        assert!(config.has_valid_signal(99.9, 0.9));
+        assert!(!config.has_valid_signal(100.0, 0.9));
+        assert!(!config.has_valid_signal(0.0, 0.9));
+
+        // Noisy link: accept weaker bursts and a longer path
+        let lenient = ServerConfig {
+            min_confidence: 0.1,
+            max_latency_ms: 250.0,
+            ..Default::default()
+        };
+        assert!(lenient.has_valid_signal(150.0, 0.15));
+        assert!(!lenient.has_valid_signal(250.0, 0.15));
+        assert!(!lenient.has_valid_signal(150.0, 0.05));
+
+        // Clean link: reject low-confidence artifacts
+        let strict = ServerConfig {
+            min_confidence: 0.8,
+            ..Default::default()
+        };
+        assert!(!strict.has_valid_signal(5.0, 0.5));
+        assert!(strict.has_valid_signal(5.0, 0.8));
+    }
+
+    #[test]
+    fn test_min_latency_floor() {
+        let config = ServerConfig::default();
+        // Coincident frames at startup: a 0-sample match is never valid
+        assert!(!config.has_valid_signal(0.0, 1.0));
+        // Output bleeding into the input one sample later (48 kHz)
+        assert!(!config.has_valid_signal(1.0 / 48.0, 1.0));
+        // A value exactly at the floor is accepted
+        assert!(config.has_valid_signal(0.05, 1.0));
+
+        // A zero floor still rejects 0 ms
+        let no_floor = ServerConfig {
+            min_latency_ms: 0.0,
+            ..Default::default()
+        };
+        assert!(!no_floor.has_valid_signal(0.0, 1.0));
+        assert!(no_floor.has_valid_signal(0.01, 1.0));
+    }
+
+    #[test]
+    fn test_latency_window_validation() {
+        let defaults = ServerConfig::default();
+        assert!(ServerConfig::validate_latency_window(
+            defaults.min_latency_ms,
+            defaults.max_latency_ms
+        )
+        .is_ok());
+        assert!(ServerConfig::validate_latency_window(0.0, 0.05).is_ok());
+        // An empty window would turn every measurement into signal loss
+        assert!(ServerConfig::validate_latency_window(100.0, 100.0).is_err());
+        assert!(ServerConfig::validate_latency_window(150.0, 100.0).is_err());
+    }
+
+    #[test]
+    fn test_latency_out_of_range_boundaries() {
+        // Disabled by default
+        assert!(!ServerConfig::default().latency_out_of_range(500.0));
+
+        let config = ServerConfig {
+            expected_latency_ms: Some(5.0),
+            latency_tolerance_ms: 0.5,
+            ..Default::default()
+        };
+        assert!(!config.latency_out_of_range(5.0));
+        assert!(!config.latency_out_of_range(5.5));
+        assert!(!config.latency_out_of_range(4.5));
+        assert!(config.latency_out_of_range(5.501));
+        assert!(config.latency_out_of_range(4.499));
+        // A routing change adding a buffer of 256 frames at 48 kHz
+        assert!(config.latency_out_of_range(5.0 + 256.0 / 48.0));
+
+        // Zero tolerance: only the exact value is in range
+        let exact = ServerConfig {
+            expected_latency_ms: Some(5.0),
+            latency_tolerance_ms: 0.0,
+            ..Default::default()
+        };
+        assert!(!exact.latency_out_of_range(5.0));
+        assert!(exact.latency_out_of_range(5.001));
+    }
+
+    #[test]
+    fn test_latency_out_of_range_held_without_signal() {
+        let config = ServerConfig {
+            expected_latency_ms: Some(5.0),
+            latency_tolerance_ms: 0.5,
+            ..Default::default()
+        };
+        // A valid signal sets and clears the flag
+        assert!(config.update_latency_out_of_range(false, true, 12.0));
+        assert!(!config.update_latency_out_of_range(true, true, 5.0));
+        // A dropout keeps whatever the last valid measurement decided
+        assert!(config.update_latency_out_of_range(true, false, 0.0));
+        assert!(!config.update_latency_out_of_range(false, false, 12.0));
+    }
+
+    #[test]
+    fn test_reconnect_action_limited() {
+        let config = ServerConfig::default();
+        for failures in 1..=5 {
+            assert_eq!(config.reconnect_action(failures), ReconnectAction::Retry);
+        }
+        assert_eq!(config.reconnect_action(6), ReconnectAction::GiveUp);
+        assert_eq!(config.reconnect_action(7), ReconnectAction::Idle);
+    }
+
+    #[test]
+    fn test_reconnect_action_unlimited_never_gives_up() {
+        let config = ServerConfig {
+            max_reconnect_attempts: 0,
+            ..Default::default()
+        };
+        // Never reaches GiveUp, so no failed disconnection is recorded
+        assert!((1..=10_000).all(|f| config.reconnect_action(f) == ReconnectAction::Retry));
+        assert_eq!(config.reconnect_action(u32::MAX), ReconnectAction::Retry);
+    }
+
+    #[test]
+    fn test_streams_only_escalates_to_full() {
+        let strategy = ReconnectStrategy::StreamsOnly;
+        assert!(!strategy.is_full_attempt(1));
+        assert!(!strategy.is_full_attempt(STREAMS_ONLY_MAX_ATTEMPTS));
+        assert!(strategy.is_full_attempt(STREAMS_ONLY_MAX_ATTEMPTS + 1));
+        assert!(ReconnectStrategy::Full.is_full_attempt(1));
+    }
+
+    #[tokio::test]
+    async fn test_stats_lock_recovers_from_poisoning() {
+        let state = AppState::new(
+            EngineHandle::spawn(),
+            Arc::new(Mutex::new(StatsStore::new())),
+            ServerConfig::default(),
+            None,
+        );
+        let stats = Arc::clone(&state.stats);
+        let result = std::thread::spawn(move || {
+            let _guard = stats.lock().unwrap();
+            panic!("poison the stats store");
+        })
+        .join();
+        assert!(result.is_err());
+        assert!(state.stats.is_poisoned());
+
+        state.stats_lock().record_latency(5.0);
+        assert_eq!(state.stats_lock().stats().measurement_count, 1);
+        assert!(!state.stats.is_poisoned());
+    }
+
+    #[tokio::test]
+    async fn test_paused_recording_skips_measurements() {
+        let state = AppState::new(
+            EngineHandle::spawn(),
+            Arc::new(Mutex::new(StatsStore::new())),
+            ServerConfig::default(),
+            None,
+        );
+        let result = AnalysisResult {
+            latency_ms: 5.0,
+            confidence: 0.9,
+            lost_samples: 12,
+            ..Default::default()
+        };
+
+        state.set_recording_paused(true);
+        assert!(!state.record_analysis(&result));
+        {
+            let store = state.stats_lock();
+            assert_eq!(store.stats().measurement_count, 0);
+            assert_eq!(store.stats().total_lost, 0);
+        }
+
+        state.set_recording_paused(false);
+        assert!(state.record_analysis(&result));
+        let store = state.stats_lock();
+        assert_eq!(store.stats().measurement_count, 1);
+        assert_eq!(store.stats().total_lost, 12);
+    }
+
+    #[tokio::test]
+    async fn test_paused_recording_skips_silence_estimate() {
+        let state = AppState::new(
+            EngineHandle::spawn(),
+            Arc::new(Mutex::new(StatsStore::new())),
+            ServerConfig::default(),
+            None,
+        );
+        state.estimate_silent_loss(std::time::Duration::from_secs(1), 48000);
+
+        // Counter recovers while paused: neither total nor estimate moves
+        state.set_recording_paused(true);
+        state.estimate_silent_loss(std::time::Duration::from_secs(2), 48000);
+        assert_eq!(state.commit_estimated_loss(), 0);
+        {
+            let store = state.stats_lock();
+            assert_eq!(store.stats().total_lost, 0);
+            assert_eq!(store.stats().estimated_loss, 48000);
+        }
+
+        state.set_recording_paused(false);
+        assert_eq!(state.commit_estimated_loss(), 48000);
+        let store = state.stats_lock();
+        assert_eq!(store.stats().total_lost, 48000);
+        assert_eq!(store.stats().estimated_loss, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stale_results_not_recorded() {
+        let state = AppState::new(
+            EngineHandle::spawn(),
+            Arc::new(Mutex::new(StatsStore::new())),
+            ServerConfig::default(),
+            None,
+        );
+        let fresh = AnalysisResult {
+            latency_ms: 5.0,
+            confidence: 0.9,
+            ..Default::default()
+        };
+        assert!(state.record_analysis(&fresh));
+
+        // Shortly after the last detection the stale latency is still valid
+        let recent = AnalysisResult {
+            confidence: 0.6,
+            stale: true,
+            ..fresh.clone()
+        };
+        assert!(state.record_analysis(&recent));
+        assert_eq!(state.stats_lock().latency_history().len(), 2);
+
+        // Decayed below the validity threshold: no more latency points
+        for confidence in [0.2, 0.1, 0.05] {
+            let decayed = AnalysisResult {
+                confidence,
+                stale: true,
+                ..fresh.clone()
+            };
+            assert!(state.record_analysis(&decayed));
+        }
+        let store = state.stats_lock();
+        assert_eq!(store.latency_history().len(), 2);
+        assert_eq!(store.stats().measurement_count, 2);
+        assert_eq!(store.stats().last_confidence, 0.05);
+    }
+
+    #[tokio::test]
+    async fn test_bind_falls_back_to_next_port() {
+        let taken = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = taken.local_addr().unwrap().port();
+
+        let listener = bind_with_fallback("127.0.0.1", port).await.unwrap();
+        let bound = listener.local_addr().unwrap().port();
+        assert!(bound > port && bound <= port + PORT_FALLBACK_ATTEMPTS);
+    }
+}
@@ -0,0 +1,147 @@
+//! Prometheus metrics endpoint
+//!
+//! Exposes current statistics at /metrics in the Prometheus text
+//! exposition format for scraping by Prometheus/Grafana.
+
+use crate::AppState;
+use audiotester_core::stats::store::RunningStats;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use std::fmt::Write;
+
+/// Content type of the Prometheus text exposition format
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// GET /metrics
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    // Clone stats out so the lock is not held across the engine await
+    let stats = state.stats_lock().stats().clone();
+    let device = state
+        .engine
+        .get_status()
+        .await
+        .ok()
+        .and_then(|status| status.device_name);
+
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        render_metrics(&stats, device.as_deref()),
+    )
+}
+
+/// Render statistics as Prometheus text exposition
+pub fn render_metrics(stats: &RunningStats, device: Option<&str>) -> String {
+    let label = escape_label_value(device.unwrap_or(""));
+    let mut out = String::with_capacity(1024);
+
+    let mut metric = |name: &str, kind: &str, help: &str, value: f64| {
+        // Writing to a String cannot fail
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {kind}");
+        let _ = writeln!(out, "{name}{{device=\"{label}\"}} {value}");
+    };
+
+    metric(
+        "audiotester_latency_ms",
+        "gauge",
+        "Most recent round-trip latency in milliseconds.",
+        stats.current_latency,
+    );
+    metric(
+        "audiotester_latency_avg_ms",
+        "gauge",
+        "Average latency over the recent history in milliseconds.",
+        stats.avg_latency,
+    );
+    metric(
+        "audiotester_total_lost_samples",
+        "counter",
+        "Samples lost since the last reset.",
+        stats.total_lost as f64,
+    );
+    metric(
+        "audiotester_total_corrupted_samples",
+        "counter",
+        "Samples corrupted since the last reset.",
+        stats.total_corrupted as f64,
+    );
+    metric(
+        "audiotester_signal_lost",
+        "gauge",
+        "1 when no test signal is being received, 0 otherwise.",
+        if stats.signal_lost { 1.0 } else { 0.0 },
+    );
+    metric(
+        "audiotester_uptime_seconds",
+        "gauge",
+        "Seconds since monitoring started.",
+        stats.uptime_seconds as f64,
+    );
+    metric(
+        "audiotester_confidence",
+        "gauge",
+        "Confidence of the last latency measurement (0 to 1).",
+        stats.last_confidence as f64,
+    );
+
+    out
+}
+
+/// Escape a label value per the exposition format (backslash, quote, newline)
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_exposition_format() {
+        let stats = RunningStats {
+            current_latency: 5.25,
+            avg_latency: 5.0,
+            total_lost: 12,
+            signal_lost: true,
+            uptime_seconds: 3600,
+            last_confidence: 0.5,
+            ..Default::default()
+        };
+        let text = render_metrics(&stats, Some("VASIO-8"));
+
+        // Every metric: HELP, then TYPE, then exactly one numeric sample
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len() % 3, 0);
+        for chunk in lines.chunks(3) {
+            let name = chunk[0]
+                .strip_prefix("# HELP ")
+                .and_then(|rest| rest.split(' ').next())
+                .expect("HELP line first");
+            let type_line = chunk[1]
+                .strip_prefix(&format!("# TYPE {name} "))
+                .expect("TYPE line follows HELP");
+            assert!(type_line == "gauge" || type_line == "counter");
+
+            let (series, value) = chunk[2].rsplit_once(' ').unwrap();
+            assert_eq!(series, format!("{name}{{device=\"VASIO-8\"}}"));
+            value.parse::<f64>().expect("numeric sample value");
+        }
+
+        assert!(text.contains("audiotester_latency_ms{device=\"VASIO-8\"} 5.25\n"));
+        assert!(text.contains("audiotester_total_lost_samples{device=\"VASIO-8\"} 12\n"));
+        assert!(text.contains("audiotester_signal_lost{device=\"VASIO-8\"} 1\n"));
+    }
+
+    #[test]
+    fn test_metrics_label_escaping() {
+        let text = render_metrics(&RunningStats::default(), Some("a\"b\\c"));
+        assert!(text.contains("{device=\"a\\\"b\\\\c\"}"));
+
+        let text = render_metrics(&RunningStats::default(), None);
+        assert!(text.contains("audiotester_uptime_seconds{device=\"\"} 0\n"));
+    }
+}
@@ -0,0 +1,200 @@
+//! MIDI output of the link status for hardware indicators
+//!
+//! Studios with a MIDI-controlled status light get a Note On for the current
+//! light (green, yellow or red) whenever the status changes, and a Note Off
+//! for the previous one. The port is opened once at startup through the
+//! Windows multimedia API; other platforms report it as unsupported.
+
+use std::sync::Mutex;
+
+/// MIDI channel the notes are sent on (0-based, i.e. channel 1)
+pub const MIDI_CHANNEL: u8 = 0;
+
+/// Velocity of the Note On for the active light
+pub const MIDI_VELOCITY: u8 = 127;
+
+/// Indicator light shown for a link status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusLight {
+    /// Not monitoring: all lights off
+    Off,
+    Green,
+    Yellow,
+    Red,
+}
+
+impl StatusLight {
+    /// Note lighting this light (None for [`StatusLight::Off`])
+    pub fn note(self) -> Option<u8> {
+        match self {
+            StatusLight::Off => None,
+            StatusLight::Green => Some(60),  // C4
+            StatusLight::Yellow => Some(62), // D4
+            StatusLight::Red => Some(64),    // E4
+        }
+    }
+}
+
+/// Note On message for `note` at `velocity`
+pub fn note_on(note: u8, velocity: u8) -> [u8; 3] {
+    [0x90 | MIDI_CHANNEL, note & 0x7F, velocity & 0x7F]
+}
+
+/// Note Off message for `note`
+pub fn note_off(note: u8) -> [u8; 3] {
+    [0x80 | MIDI_CHANNEL, note & 0x7F, 0]
+}
+
+/// Pack a three-byte message the way `midiOutShortMsg` expects it
+pub fn short_msg(msg: [u8; 3]) -> u32 {
+    u32::from_le_bytes([msg[0], msg[1], msg[2], 0])
+}
+
+/// Sends the status light to a MIDI output port
+pub struct MidiStatusSender {
+    port: port::MidiPort,
+    /// Light currently shown (None until the first update)
+    current: Mutex<Option<StatusLight>>,
+}
+
+impl MidiStatusSender {
+    /// Open the MIDI output port called `name`
+    pub fn open(name: &str) -> std::io::Result<Self> {
+        Ok(Self {
+            port: port::MidiPort::open(name)?,
+            current: Mutex::new(None),
+        })
+    }
+
+    /// Show `light`, switching off the previous one; no-op if unchanged
+    pub fn show(&self, light: StatusLight) {
+        let mut current = self
+            .current
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if *current == Some(light) {
+            return;
+        }
+
+        let previous = current.and_then(StatusLight::note);
+        let messages = previous
+            .map(note_off)
+            .into_iter()
+            .chain(light.note().map(|note| note_on(note, MIDI_VELOCITY)));
+        for msg in messages {
+            if let Err(e) = self.port.send(msg) {
+                tracing::warn!(error = %e, ?light, "Failed to send MIDI status");
+            }
+        }
+        *current = Some(light);
+    }
+}
+
+#[cfg(windows)]
+mod port {
+    use super::short_msg;
+    use std::io;
+    use windows_sys::Win32::Media::Audio::{
+        midiOutClose, midiOutGetDevCapsW, midiOutGetNumDevs, midiOutOpen, midiOutShortMsg,
+        CALLBACK_NULL, HMIDIOUT, MIDIOUTCAPSW,
+    };
+
+    /// Open winmm MIDI output handle
+    pub struct MidiPort(HMIDIOUT);
+
+    // SAFETY: winmm MIDI output handles are not tied to the opening thread
+    unsafe impl Send for MidiPort {}
+    unsafe impl Sync for MidiPort {}
+
+    impl MidiPort {
+        pub fn open(name: &str) -> io::Result<Self> {
+            let count = unsafe { midiOutGetNumDevs() };
+            for id in 0..count {
+                let mut caps: MIDIOUTCAPSW = unsafe { std::mem::zeroed() };
+                let size = std::mem::size_of::<MIDIOUTCAPSW>() as u32;
+                if unsafe { midiOutGetDevCapsW(id as usize, &mut caps, size) } != 0 {
+                    continue;
+                }
+                let pname = caps.szPname;
+                let len = pname.iter().position(|&c| c == 0).unwrap_or(pname.len());
+                if String::from_utf16_lossy(&pname[..len]) != name {
+                    continue;
+                }
+
+                let mut handle: HMIDIOUT = std::ptr::null_mut();
+                let result = unsafe { midiOutOpen(&mut handle, id, 0, 0, CALLBACK_NULL) };
+                if result != 0 {
+                    return Err(io::Error::other(format!(
+                        "midiOutOpen failed with error {}",
+                        result
+                    )));
+                }
+                return Ok(Self(handle));
+            }
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("MIDI output port not found: {}", name),
+            ))
+        }
+
+        pub fn send(&self, msg: [u8; 3]) -> io::Result<()> {
+            match unsafe { midiOutShortMsg(self.0, short_msg(msg)) } {
+                0 => Ok(()),
+                e => Err(io::Error::other(format!(
+                    "midiOutShortMsg failed with error {}",
+                    e
+                ))),
+            }
+        }
+    }
+
+    impl Drop for MidiPort {
+        fn drop(&mut self) {
+            unsafe {
+                midiOutClose(self.0);
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod port {
+    use std::io;
+
+    /// MIDI output is only implemented on Windows
+    pub struct MidiPort;
+
+    impl MidiPort {
+        pub fn open(_name: &str) -> io::Result<Self> {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "MIDI output is only supported on Windows",
+            ))
+        }
+
+        pub fn send(&self, _msg: [u8; 3]) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_notes() {
+        assert_eq!(StatusLight::Off.note(), None);
+        assert_eq!(StatusLight::Green.note(), Some(60));
+        assert_eq!(StatusLight::Yellow.note(), Some(62));
+        assert_eq!(StatusLight::Red.note(), Some(64));
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        assert_eq!(note_on(62, MIDI_VELOCITY), [0x90, 62, 127]);
+        assert_eq!(note_off(62), [0x80, 62, 0]);
+        // Status byte in the low byte, then note, then velocity
+        assert_eq!(short_msg([0x90, 60, 127]), 0x007F_3C90);
+    }
+}
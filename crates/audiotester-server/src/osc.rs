@@ -0,0 +1,205 @@
+//! OSC output of live metrics for show-control integration
+//!
+//! Sends latency, lost-sample and signal state to an OSC receiver (QLab or
+//! any show-control rig) as a single bundle per analysis cycle. Delivery is
+//! fire-and-forget over a non-blocking UDP socket so a slow or missing
+//! receiver never holds up monitoring.
+//!
+//! Only the OSC 1.0 subset needed here (int32 and float32 arguments in an
+//! immediate bundle) is encoded.
+
+use std::net::{SocketAddr, UdpSocket};
+
+/// Address carrying the current latency (float, ms)
+pub const OSC_LATENCY_ADDR: &str = "/audiotester/latency";
+
+/// Address carrying the total lost samples (int)
+pub const OSC_LOST_ADDR: &str = "/audiotester/lost";
+
+/// Address carrying the signal state (int, 1 = signal present)
+pub const OSC_SIGNAL_ADDR: &str = "/audiotester/signal";
+
+/// OSC time tag meaning "immediately"
+const OSC_IMMEDIATE: u64 = 1;
+
+/// A single OSC argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OscArg {
+    Int(i32),
+    Float(f32),
+}
+
+/// An OSC message: address pattern plus arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscMessage {
+    pub addr: &'static str,
+    pub args: Vec<OscArg>,
+}
+
+/// Append an OSC string: null-terminated and padded to a multiple of 4 bytes
+fn push_padded_str(buf: &mut Vec<u8>, s: &str) {
+    buf.extend_from_slice(s.as_bytes());
+    let padding = 4 - s.len() % 4;
+    buf.resize(buf.len() + padding, 0);
+}
+
+impl OscMessage {
+    /// Encode the message in OSC 1.0 binary form
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        push_padded_str(&mut buf, self.addr);
+
+        let tags: String = std::iter::once(',')
+            .chain(self.args.iter().map(|arg| match arg {
+                OscArg::Int(_) => 'i',
+                OscArg::Float(_) => 'f',
+            }))
+            .collect();
+        push_padded_str(&mut buf, &tags);
+
+        for arg in &self.args {
+            match arg {
+                OscArg::Int(v) => buf.extend_from_slice(&v.to_be_bytes()),
+                OscArg::Float(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            }
+        }
+        buf
+    }
+}
+
+/// Encode messages as an OSC bundle to be dispatched immediately
+pub fn encode_bundle(messages: &[OscMessage]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    push_padded_str(&mut buf, "#bundle");
+    buf.extend_from_slice(&OSC_IMMEDIATE.to_be_bytes());
+    for msg in messages {
+        let element = msg.encode();
+        buf.extend_from_slice(&(element.len() as i32).to_be_bytes());
+        buf.extend_from_slice(&element);
+    }
+    buf
+}
+
+/// Build the metrics bundle sent each analysis cycle
+pub fn metrics_bundle(latency_ms: f64, total_lost: u64, signal: bool) -> Vec<u8> {
+    encode_bundle(&[
+        OscMessage {
+            addr: OSC_LATENCY_ADDR,
+            args: vec![OscArg::Float(latency_ms as f32)],
+        },
+        OscMessage {
+            addr: OSC_LOST_ADDR,
+            args: vec![OscArg::Int(total_lost.min(i32::MAX as u64) as i32)],
+        },
+        OscMessage {
+            addr: OSC_SIGNAL_ADDR,
+            args: vec![OscArg::Int(signal as i32)],
+        },
+    ])
+}
+
+/// Fire-and-forget OSC sender
+#[derive(Debug)]
+pub struct OscSender {
+    socket: UdpSocket,
+    target: SocketAddr,
+}
+
+impl OscSender {
+    /// Open a non-blocking UDP socket for sending to `target`
+    pub fn new(target: SocketAddr) -> std::io::Result<Self> {
+        let local: SocketAddr = if target.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            (std::net::Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = UdpSocket::bind(local)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket, target })
+    }
+
+    /// Send the live metrics; errors (including a full send buffer) are dropped
+    pub fn send_metrics(&self, latency_ms: f64, total_lost: u64, signal: bool) {
+        let packet = metrics_bundle(latency_ms, total_lost, signal);
+        if let Err(e) = self.socket.send_to(&packet, self.target) {
+            tracing::trace!(error = %e, target = %self.target, "OSC send failed");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Read a padded OSC string at `pos`, returning it and the next offset
+    fn read_str(buf: &[u8], pos: usize) -> (&str, usize) {
+        let end = pos + buf[pos..].iter().position(|&b| b == 0).unwrap();
+        let s = std::str::from_utf8(&buf[pos..end]).unwrap();
+        (s, pos + (s.len() / 4 + 1) * 4)
+    }
+
+    #[test]
+    fn test_message_encoding() {
+        let msg = OscMessage {
+            addr: OSC_LATENCY_ADDR,
+            args: vec![OscArg::Float(5.25)],
+        };
+        let buf = msg.encode();
+        // "/audiotester/latency" is 20 bytes -> 24 padded, ",f" -> 4, float -> 4
+        assert_eq!(buf.len(), 32);
+        assert_eq!(read_str(&buf, 0), (OSC_LATENCY_ADDR, 24));
+        assert_eq!(read_str(&buf, 24), (",f", 28));
+        assert_eq!(f32::from_be_bytes(buf[28..32].try_into().unwrap()), 5.25);
+    }
+
+    #[test]
+    fn test_metrics_bundle() {
+        let buf = metrics_bundle(5.25, 42, true);
+        assert_eq!(read_str(&buf, 0), ("#bundle", 8));
+        assert_eq!(u64::from_be_bytes(buf[8..16].try_into().unwrap()), 1);
+
+        let mut pos = 16;
+        let mut messages = Vec::new();
+        while pos < buf.len() {
+            let size = i32::from_be_bytes(buf[pos..pos + 4].try_into().unwrap()) as usize;
+            let element = &buf[pos + 4..pos + 4 + size];
+            let (addr, next) = read_str(element, 0);
+            let (tags, next) = read_str(element, next);
+            let arg = &element[next..next + 4];
+            messages.push((addr.to_string(), tags.to_string(), arg.to_vec()));
+            pos += 4 + size;
+        }
+        assert_eq!(pos, buf.len());
+        assert_eq!(messages.len(), 3);
+
+        assert_eq!(messages[0].0, OSC_LATENCY_ADDR);
+        assert_eq!(messages[0].1, ",f");
+        assert_eq!(
+            f32::from_be_bytes(messages[0].2[..].try_into().unwrap()),
+            5.25
+        );
+        assert_eq!(messages[1].0, OSC_LOST_ADDR);
+        assert_eq!(messages[1].1, ",i");
+        assert_eq!(
+            i32::from_be_bytes(messages[1].2[..].try_into().unwrap()),
+            42
+        );
+        assert_eq!(messages[2].0, OSC_SIGNAL_ADDR);
+        assert_eq!(messages[2].1, ",i");
+        assert_eq!(i32::from_be_bytes(messages[2].2[..].try_into().unwrap()), 1);
+    }
+
+    #[test]
+    fn test_sender_delivers_datagram() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+        receiver
+            .set_read_timeout(Some(std::time::Duration::from_secs(2)))
+            .unwrap();
+        let sender = OscSender::new(receiver.local_addr().unwrap()).unwrap();
+        sender.send_metrics(3.0, u64::MAX, false);
+
+        let mut buf = [0u8; 256];
+        let len = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], &metrics_bundle(3.0, u64::MAX, false)[..]);
+    }
+}
@@ -0,0 +1,123 @@
+//! Persisted dashboard preferences
+//!
+//! Display settings the dashboard reads on load, kept in
+//! `preferences.json` in the data directory so they survive restarts and
+//! apply to every browser. Served via `GET`/`PUT /api/v1/preferences`.
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Dashboard display preferences
+///
+/// Missing fields take their defaults, so files written by older versions
+/// still load.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// Lower bound of the latency chart Y axis (ms, None = auto-scale)
+    pub latency_y_min: Option<f64>,
+    /// Upper bound of the latency chart Y axis (ms, None = auto-scale)
+    pub latency_y_max: Option<f64>,
+}
+
+impl Preferences {
+    /// Check the values, returning a message for the first invalid one
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, value) in [
+            ("latency_y_min", self.latency_y_min),
+            ("latency_y_max", self.latency_y_max),
+        ] {
+            if let Some(value) = value {
+                if !value.is_finite() || value < 0.0 {
+                    return Err(format!("{} must be a finite value >= 0", name));
+                }
+            }
+        }
+        if let (Some(min), Some(max)) = (self.latency_y_min, self.latency_y_max) {
+            if min >= max {
+                return Err(format!(
+                    "latency_y_min ({}) must be less than latency_y_max ({})",
+                    min, max
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the preferences to `path` as JSON
+    ///
+    /// Writes to a temporary file first and renames it into place, so a
+    /// crash mid-write never leaves a truncated file behind.
+    pub fn save_to_path(&self, path: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_vec_pretty(self).context("Failed to serialize preferences")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)
+            .with_context(|| format!("Failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to replace {}", path.display()))
+    }
+
+    /// Load preferences from `path`, or the defaults if none were saved yet
+    pub fn load_from_path(path: &Path) -> anyhow::Result<Self> {
+        let data = match std::fs::read(path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        serde_json::from_slice(&data).context("Failed to parse preferences file")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preferences_round_trip() {
+        let dir =
+            std::env::temp_dir().join(format!("audiotester-preferences-{}", std::process::id()));
+        let path = dir.join("preferences.json");
+
+        // Nothing saved yet: auto-scale
+        assert_eq!(
+            Preferences::load_from_path(&path).unwrap(),
+            Preferences::default()
+        );
+
+        let pinned = Preferences {
+            latency_y_min: Some(2.0),
+            latency_y_max: Some(12.5),
+        };
+        pinned.save_to_path(&path).unwrap();
+        assert_eq!(Preferences::load_from_path(&path).unwrap(), pinned);
+
+        // Unset bounds serialize as null
+        let json = serde_json::to_value(Preferences::default()).unwrap();
+        assert!(json["latency_y_max"].is_null());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_preferences_validation() {
+        let prefs = |min, max| Preferences {
+            latency_y_min: min,
+            latency_y_max: max,
+        };
+        assert!(prefs(None, None).validate().is_ok());
+        assert!(prefs(Some(0.0), None).validate().is_ok());
+        assert!(prefs(None, Some(20.0)).validate().is_ok());
+        assert!(prefs(Some(1.0), Some(20.0)).validate().is_ok());
+
+        let err = prefs(Some(20.0), Some(5.0)).validate().unwrap_err();
+        assert!(err.contains("must be less than"), "{}", err);
+        assert!(prefs(Some(5.0), Some(5.0)).validate().is_err());
+        assert!(prefs(Some(-1.0), None).validate().is_err());
+        assert!(prefs(None, Some(f64::INFINITY)).validate().is_err());
+    }
+}
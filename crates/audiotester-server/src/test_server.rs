@@ -14,11 +14,15 @@ async fn main() {
     let log_dir = std::env::temp_dir().join("audiotester-test-logs");
     std::fs::create_dir_all(&log_dir).ok();
 
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive("audiotester=debug".parse().unwrap()),
-        )
+    let env_filter = tracing_subscriber::EnvFilter::from_default_env()
+        .add_directive("audiotester=debug".parse().unwrap());
+    let (filter_layer, filter_handle) = tracing_subscriber::reload::Layer::new(env_filter);
+
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(tracing_subscriber::fmt::layer())
         .init();
 
     // Write a marker file so /api/v1/logs can find it
@@ -40,8 +44,10 @@ async fn main() {
     let config = ServerConfig {
         port,
         bind_addr: "127.0.0.1".to_string(),
+        ..Default::default()
     };
-    let state = AppState::new(engine, Arc::clone(&stats), config, Some(log_dir));
+    let state = AppState::new(engine, Arc::clone(&stats), config, Some(log_dir))
+        .with_log_filter(filter_handle);
 
     tracing::info!(port, "Test server starting");
 
@@ -15,6 +15,7 @@ pub fn SummaryBar() -> impl IntoView {
             <div class="metric">
                 <span class="metric-label">"Lost"</span>
                 <span class="metric-value" data-testid="lost-value">"0"</span>
+                <span class="metric-unit" data-testid="lost-unverified" hidden></span>
             </div>
             <div class="metric">
                 <span class="metric-label">"Corrupted"</span>
@@ -4,6 +4,7 @@
 //! Features:
 //! - Summary bar with current/min/max/avg latency and loss counters
 //! - Device info bar showing active device, sample rate, uptime
+//! - Peak/RMS input meters for the burst and counter channels
 //! - Reset button for counters (preserves graph history)
 //! - Flexbox no-scroll layout (height: 100vh)
 //! - PWA-ready meta tags
@@ -50,6 +51,10 @@ fn DashboardPage() -> impl IntoView {
                 </header>
                 <main>
                     <SummaryBar/>
+                    <div class="paused-banner" id="paused-banner" data-testid="paused-banner" hidden>
+                        <span>"Recording paused - audio keeps running, graphs are frozen"</span>
+                        <button class="btn-resume" id="resume-btn">"Resume"</button>
+                    </div>
                     <div class="device-info-bar" id="device-info-bar">
                         <div class="info-item">
                             <span class="info-label">"Device:"</span>
@@ -71,6 +76,22 @@ fn DashboardPage() -> impl IntoView {
                             <span class="info-label">"IN:"</span>
                             <span class="info-value" id="samples-received">"0"</span>
                         </div>
+                        <div class="info-item level-meter" data-testid="level-burst" title="Burst input peak / RMS">
+                            <span class="info-label">"CH0:"</span>
+                            <div class="level-bar">
+                                <div class="level-rms" id="level-burst-rms"></div>
+                                <div class="level-peak" id="level-burst-peak"></div>
+                            </div>
+                            <span class="info-value" id="level-burst-db">"--"</span>
+                        </div>
+                        <div class="info-item level-meter" data-testid="level-counter" title="Counter input peak / RMS">
+                            <span class="info-label">"CH1:"</span>
+                            <div class="level-bar">
+                                <div class="level-rms" id="level-counter-rms"></div>
+                                <div class="level-peak" id="level-counter-peak"></div>
+                            </div>
+                            <span class="info-value" id="level-counter-db">"--"</span>
+                        </div>
                         <span
                             class="signal-status"
                             id="signal-status"
@@ -78,6 +99,7 @@ fn DashboardPage() -> impl IntoView {
                         >"Signal OK"</span>
                         <button class="btn-reset" id="reset-btn" title="Reset counters (preserves graph history)">"Reset"</button>
                     </div>
+                    <ul class="diagnostics-list" id="diagnostics-list" data-testid="diagnostics"></ul>
                     <section class="charts">
                         <div class="chart-container">
                             <div class="chart-header">
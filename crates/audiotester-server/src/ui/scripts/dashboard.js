@@ -8,6 +8,7 @@
   const els = {
     latency: document.querySelector('[data-testid="latency-value"]'),
     lost: document.querySelector('[data-testid="lost-value"]'),
+    lostUnverified: document.querySelector('[data-testid="lost-unverified"]'),
     corrupted: document.querySelector('[data-testid="corrupted-value"]'),
   };
 
@@ -20,6 +21,8 @@
   const signalStatusEl = document.getElementById("signal-status");
   const remoteUrlEl = document.getElementById("remote-url");
   const resetBtn = document.getElementById("reset-btn");
+  const pausedBannerEl = document.getElementById("paused-banner");
+  const resumeBtn = document.getElementById("resume-btn");
 
   // Format uptime seconds into human-readable string
   function formatUptime(seconds) {
@@ -59,6 +62,14 @@
         els.lost.className = "metric-value";
       }
     }
+    if (els.lostUnverified) {
+      // Counter muted: loss cannot be measured, only estimated from elapsed time
+      var unverified = stats.counter_silent && stats.estimated_loss > 0;
+      els.lostUnverified.hidden = !unverified;
+      els.lostUnverified.textContent = unverified
+        ? "\u2248" + stats.estimated_loss + " samples unverified"
+        : "";
+    }
     if (els.corrupted)
       els.corrupted.textContent = stats.total_corrupted.toString();
 
@@ -69,6 +80,15 @@
     if (sampleRateEl) {
       sampleRateEl.textContent =
         stats.sample_rate > 0 ? stats.sample_rate / 1000 + " kHz" : "--";
+      // Device rejected the configured rate and runs at its default
+      if (stats.rate_fallback) {
+        sampleRateEl.textContent += " (fallback)";
+        sampleRateEl.classList.add("warning");
+        sampleRateEl.title = "Configured sample rate was rejected by the device";
+      } else {
+        sampleRateEl.classList.remove("warning");
+        sampleRateEl.title = "";
+      }
     }
     if (uptimeEl && stats.uptime_seconds !== undefined) {
       uptimeEl.textContent = formatUptime(stats.uptime_seconds);
@@ -92,6 +112,9 @@
         signalStatusEl.classList.remove("warning");
       }
     }
+    if (pausedBannerEl) {
+      pausedBannerEl.hidden = !stats.recording_paused;
+    }
   }
 
   // Format large sample counts (e.g., 1.2M, 450K)
@@ -585,17 +608,90 @@
       });
   }
 
+  // ─── Input level meters ───────────────────────────────────────────
+
+  // Linear level to a 0-100% bar width over a -60..0 dBFS scale
+  function levelPercent(level) {
+    if (!level || level <= 0) return 0;
+    var db = 20 * Math.log10(level);
+    return Math.max(0, Math.min(100, ((db + 60) / 60) * 100));
+  }
+
+  function updateLevelMeter(key, level) {
+    var rmsEl = document.getElementById("level-" + key + "-rms");
+    var peakEl = document.getElementById("level-" + key + "-peak");
+    var dbEl = document.getElementById("level-" + key + "-db");
+    if (!rmsEl || !peakEl || !dbEl) return;
+    var peak = level ? level.peak : 0;
+    var rms = level ? level.rms : 0;
+    rmsEl.style.width = levelPercent(rms) + "%";
+    peakEl.style.left = levelPercent(peak) + "%";
+    dbEl.textContent = peak > 0 ? (20 * Math.log10(peak)).toFixed(1) : "--";
+    dbEl.className = "info-value" + (peak >= 0.99 ? " warning" : "");
+  }
+
+  function fetchLevels() {
+    fetch("/api/v1/levels")
+      .then(function (r) {
+        return r.ok ? r.json() : null;
+      })
+      .then(function (levels) {
+        updateLevelMeter("burst", levels && levels.burst);
+        updateLevelMeter("counter", levels && levels.counter);
+      })
+      .catch(function (err) {
+        console.error("Failed to fetch levels:", err);
+      });
+  }
+
+  // ─── Access token for mutating requests ───────────────────────────
+
+  // With AUDIOTESTER_TOKEN set, POST requests need a bearer token. It is
+  // asked for on the first 401 and remembered in this browser.
+  const TOKEN_KEY = "audiotester-token";
+
+  function postWithToken(url, options) {
+    function send() {
+      const headers = Object.assign({}, options.headers);
+      const token = localStorage.getItem(TOKEN_KEY);
+      if (token) headers.Authorization = "Bearer " + token;
+      return fetch(
+        url,
+        Object.assign({}, options, { method: "POST", headers: headers }),
+      );
+    }
+    return send().then(function (res) {
+      if (res.status !== 401) return res;
+      localStorage.removeItem(TOKEN_KEY);
+      const token = window.prompt(
+        "This server requires an access token (AUDIOTESTER_TOKEN):",
+      );
+      if (!token || !token.trim()) return res;
+      localStorage.setItem(TOKEN_KEY, token.trim());
+      return send();
+    });
+  }
+
+  // Show `text` on a button for a moment, then restore its label
+  function flashButton(btn, text, label) {
+    btn.textContent = text;
+    setTimeout(function () {
+      btn.textContent = label;
+    }, 1500);
+  }
+
   // ─── Reset button handler ─────────────────────────────────────────
 
   if (resetBtn) {
     resetBtn.addEventListener("click", function () {
-      fetch("/api/v1/reset", { method: "POST" })
+      postWithToken("/api/v1/reset", {})
         .then(function (res) {
           if (res.ok) {
-            resetBtn.textContent = "Done!";
-            setTimeout(function () {
-              resetBtn.textContent = "Reset";
-            }, 1500);
+            flashButton(resetBtn, "Done!", "Reset");
+          } else if (res.status === 401) {
+            flashButton(resetBtn, "Token required", "Reset");
+          } else {
+            flashButton(resetBtn, "Failed", "Reset");
           }
         })
         .catch(function (err) {
@@ -604,6 +700,29 @@
     });
   }
 
+  // ─── Resume recording button handler ──────────────────────────────
+
+  if (resumeBtn) {
+    resumeBtn.addEventListener("click", function () {
+      postWithToken("/api/v1/recording", {
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({ paused: false }),
+      })
+        .then(function (res) {
+          if (res.ok) {
+            if (pausedBannerEl) pausedBannerEl.hidden = true;
+          } else if (res.status === 401) {
+            flashButton(resumeBtn, "Token required", "Resume");
+          } else {
+            flashButton(resumeBtn, "Failed", "Resume");
+          }
+        })
+        .catch(function (err) {
+          console.error("Resume failed:", err);
+        });
+    });
+  }
+
   // ─── WebSocket connection with auto-reconnect ─────────────────────
 
   let ws = null;
@@ -687,6 +806,68 @@
       });
   }
 
+  // Fetch the startup self-diagnostic report and render it as a checklist
+  function loadDiagnostics() {
+    var listEl = document.getElementById("diagnostics-list");
+    if (!listEl) return;
+    fetch("/api/v1/diagnostics")
+      .then(function (resp) {
+        return resp.json();
+      })
+      .then(function (d) {
+        var checks = [
+          ["ASIO host", d.asio_available],
+          ["Devices: " + d.device_count, d.device_count > 0],
+          ["Default: " + (d.default_device || "none"), !!d.default_device],
+          [
+            "Configured: " + (d.configured_device || "none"),
+            d.configured_device_found,
+          ],
+          ["Log directory writable", d.log_dir_writable],
+          ["Remote: " + (d.local_ip || "unavailable"), !!d.local_ip],
+        ];
+        listEl.textContent = "";
+        checks.forEach(function (check) {
+          var item = document.createElement("li");
+          item.className = check[1] ? "ok" : "fail";
+          item.textContent = (check[1] ? "\u2713 " : "\u2717 ") + check[0];
+          if (!check[1] && d.error) item.title = d.error;
+          listEl.appendChild(item);
+        });
+      })
+      .catch(function (err) {
+        console.error("Failed to load diagnostics:", err);
+      });
+  }
+
+  // Pin the latency chart Y axis to the saved bounds (null = auto-scale)
+  function loadPreferences() {
+    if (!latencyLine) return;
+    fetch("/api/v1/preferences")
+      .then(function (resp) {
+        if (!resp.ok) return null;
+        return resp.json();
+      })
+      .then(function (prefs) {
+        if (!prefs) return;
+        var yMin = prefs.latency_y_min;
+        var yMax = prefs.latency_y_max;
+        if (yMin == null && yMax == null) return;
+        latencyLine.applyOptions({
+          autoscaleInfoProvider: function (original) {
+            var res = original();
+            if (res === null) return res;
+            if (yMin != null) res.priceRange.minValue = yMin;
+            if (yMax != null) res.priceRange.maxValue = yMax;
+            return res;
+          },
+        });
+      })
+      .catch(function (err) {
+        console.error("Failed to load preferences:", err);
+      });
+  }
+
   // Fetch and display version info
   function loadVersionInfo() {
     var versionEl = document.getElementById("version-info");
@@ -708,7 +889,11 @@
 
   loadVersionInfo();
   loadRemoteUrl();
+  loadDiagnostics();
   initLossTimeline();
   initLatencyTimeline();
+  loadPreferences();
+  fetchLevels();
+  setInterval(fetchLevels, 500);
   connect();
 })();
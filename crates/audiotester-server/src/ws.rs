@@ -1,24 +1,264 @@
 //! WebSocket handler for real-time stats push
 //!
-//! Clients connect to /api/v1/ws to receive live statistics updates.
+//! Clients connect to /api/v1/ws to receive live statistics updates as JSON
+//! text frames, or to /api/v1/ws?format=binary for compact binary frames
+//! (see [`BinaryStats`]). Clients may also send JSON commands (see
+//! [`WsCommand`]) and get the reply on the same socket. When an access token
+//! is configured, commands need it (`?token=` or an `Authorization` header),
+//! and upgrades from a browser origin outside `allowed_origins` are refused.
 
 use crate::AppState;
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::State;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Query parameters for the WebSocket endpoint
+#[derive(Deserialize, Default)]
+pub struct WsQuery {
+    /// "json" (default) or "binary"
+    pub format: Option<String>,
+    /// Access token allowing commands (browsers cannot set headers on a WebSocket)
+    pub token: Option<String>,
+}
+
+/// Encoding used for frames sent to a client
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WsFormat {
+    Json,
+    Binary,
+}
+
+/// Version byte at the start of every binary frame
+pub const BINARY_FRAME_VERSION: u8 = 1;
+
+/// Size of an encoded [`BinaryStats`] frame in bytes
+pub const BINARY_FRAME_LEN: usize = 22;
+
+/// Hot stats fields sent in binary mode
+///
+/// Encoded little-endian, [`BINARY_FRAME_LEN`] bytes:
+///
+/// | Offset | Type | Field                                      |
+/// |--------|------|--------------------------------------------|
+/// | 0      | u8   | version ([`BINARY_FRAME_VERSION`])         |
+/// | 1      | u8   | flags (bit 0 = signal lost)                |
+/// | 2      | f64  | current latency (ms)                       |
+/// | 10     | f32  | confidence (0.0 to 1.0)                    |
+/// | 14     | u64  | samples lost since the previous frame      |
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BinaryStats {
+    pub current_latency: f64,
+    pub confidence: f32,
+    pub lost_delta: u64,
+    pub signal_lost: bool,
+}
+
+impl BinaryStats {
+    /// Encode into the little-endian wire layout
+    pub fn encode(&self) -> [u8; BINARY_FRAME_LEN] {
+        let mut buf = [0u8; BINARY_FRAME_LEN];
+        buf[0] = BINARY_FRAME_VERSION;
+        buf[1] = self.signal_lost as u8;
+        buf[2..10].copy_from_slice(&self.current_latency.to_le_bytes());
+        buf[10..14].copy_from_slice(&self.confidence.to_le_bytes());
+        buf[14..22].copy_from_slice(&self.lost_delta.to_le_bytes());
+        buf
+    }
+
+    /// Decode a frame, returning None for a wrong length or version
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() != BINARY_FRAME_LEN || buf[0] != BINARY_FRAME_VERSION {
+            return None;
+        }
+        Some(Self {
+            signal_lost: buf[1] & 1 != 0,
+            current_latency: f64::from_le_bytes(buf[2..10].try_into().ok()?),
+            confidence: f32::from_le_bytes(buf[10..14].try_into().ok()?),
+            lost_delta: u64::from_le_bytes(buf[14..22].try_into().ok()?),
+        })
+    }
+}
+
+/// Command sent by a client as a JSON text frame
+///
+/// `{"cmd":"toggle","enabled":true}` starts or stops monitoring like
+/// POST /api/v1/monitoring; `{"cmd":"reset"}` (optionally `"hard":true`)
+/// resets statistics like POST /api/v1/reset.
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum WsCommand {
+    Toggle {
+        enabled: bool,
+    },
+    Reset {
+        #[serde(default)]
+        hard: bool,
+    },
+}
+
+impl WsCommand {
+    /// Name used as `reply` in the answer
+    fn name(&self) -> &'static str {
+        match self {
+            WsCommand::Toggle { .. } => "toggle",
+            WsCommand::Reset { .. } => "reset",
+        }
+    }
+}
+
+/// Reply to a [`WsCommand`], sent as a JSON text frame
+///
+/// `reply` names the command ("error" for frames that are not a valid
+/// command), which tells replies apart from stats frames.
+#[derive(Serialize)]
+pub struct WsReply {
+    pub reply: &'static str,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Engine status after a toggle
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<crate::api::StatusResponse>,
+}
+
+impl WsReply {
+    fn error(reply: &'static str, error: String) -> Self {
+        Self {
+            reply,
+            ok: false,
+            error: Some(error),
+            status: None,
+        }
+    }
+}
+
+/// Run a client command and build the reply
+///
+/// Unknown commands and malformed frames are answered with an error reply
+/// and otherwise ignored, as are commands on a connection without the
+/// access token (`authorized` false).
+async fn handle_command(state: &AppState, text: &str, authorized: bool) -> WsReply {
+    let command = match serde_json::from_str::<WsCommand>(text) {
+        Ok(command) => command,
+        Err(e) => return WsReply::error("error", format!("Invalid command: {}", e)),
+    };
+    if !authorized {
+        return WsReply::error(
+            command.name(),
+            "Commands need the access token (connect with ?token=<token>)".to_string(),
+        );
+    }
+    match command {
+        WsCommand::Toggle { enabled } => {
+            let request = axum::Json(crate::api::MonitoringRequest { enabled });
+            match crate::api::toggle_monitoring(State(state.clone()), request).await {
+                Ok(axum::Json(status)) => WsReply {
+                    reply: "toggle",
+                    ok: true,
+                    error: None,
+                    status: Some(status),
+                },
+                Err((_, e)) => WsReply::error("toggle", e),
+            }
+        }
+        WsCommand::Reset { hard } => {
+            let query = Query(crate::api::ResetQuery { hard: Some(hard) });
+            match crate::api::reset_stats(State(state.clone()), query).await {
+                Ok(_) => WsReply {
+                    reply: "reset",
+                    ok: true,
+                    error: None,
+                    status: None,
+                },
+                Err((_, e)) => WsReply::error("reset", e),
+            }
+        }
+    }
+}
 
 /// WebSocket upgrade handler
-pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_ws(socket, state))
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    Query(query): Query<WsQuery>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    // CORS does not cover WebSockets: check the browser origin here
+    if !crate::request_origin_allowed(&state.config.allowed_origins, &headers) {
+        let origin = headers
+            .get(header::ORIGIN)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        return Err((
+            StatusCode::FORBIDDEN,
+            format!("Origin not allowed: {}", origin),
+        ));
+    }
+    let authorized = crate::auth::has_token(&state, &headers, query.token.as_deref());
+    let format = match query.format.as_deref() {
+        None | Some("json") => WsFormat::Json,
+        Some("binary") => WsFormat::Binary,
+        Some(other) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Invalid format: {} (must be json or binary)", other),
+            ))
+        }
+    };
+    Ok(ws.on_upgrade(move |socket| handle_ws(socket, state, format, authorized)))
+}
+
+/// Build a binary frame, advancing `last_total_lost` to the current total
+///
+/// The first frame of a connection reports a delta of 0. A lower total
+/// (statistics reset) also counts as 0.
+fn build_stats_binary(state: &AppState, last_total_lost: &mut Option<u64>) -> Vec<u8> {
+    let store = state.stats_lock();
+    let stats = store.stats();
+    let total_lost = stats.total_lost;
+    let frame = BinaryStats {
+        current_latency: stats.current_latency,
+        confidence: stats.last_confidence,
+        lost_delta: last_total_lost.map_or(0, |last| total_lost.saturating_sub(last)),
+        signal_lost: stats.signal_lost,
+    };
+    drop(store);
+    *last_total_lost = Some(total_lost);
+    frame.encode().to_vec()
 }
 
 /// Build a stats JSON snapshot (must not hold lock across await)
 fn build_stats_json(state: &AppState) -> Option<String> {
-    let store = state.stats.lock().ok()?;
+    serde_json::to_string(&build_stats_response(state)).ok()
+}
+
+/// Build a full stats snapshot for a client that fell behind
+///
+/// Same as [`build_stats_json`] plus `missed_updates`, the number of
+/// broadcasts the client's receiver dropped.
+fn build_resync_json(state: &AppState, missed: u64) -> Option<String> {
+    let mut value = serde_json::to_value(build_stats_response(state)).ok()?;
+    value
+        .as_object_mut()?
+        .insert("missed_updates".to_string(), missed.into());
+    serde_json::to_string(&value).ok()
+}
+
+/// Snapshot the stats store into a response
+fn build_stats_response(state: &AppState) -> crate::api::StatsResponse {
+    let store = state.stats_lock();
     let stats = store.stats().clone();
+    let percentiles = store.latency_percentiles(&[50.0, 95.0, 99.0]);
+    let rolling_avg = [60, 300, 900].map(|secs| store.avg_latency_window(secs));
+    let loss_rate_per_min = store.loss_rate_per_min();
+    let availability_percent = store.availability_percent();
     let latency_history = store.latency_plot_data(300);
     let loss_history = store.loss_plot_data(300);
+    let confidence_history = store.confidence_plot_data(300);
     let loss_events: Vec<crate::api::LossEventResponse> = store
         .loss_events()
         .iter()
@@ -31,8 +271,10 @@ fn build_stats_json(state: &AppState) -> Option<String> {
         .collect();
     drop(store);
 
-    let response = crate::api::StatsResponse {
+    let excess_latency_ms = stats.excess_latency_ms();
+    crate::api::StatsResponse {
         current_latency: stats.current_latency,
+        latency_us: stats.current_latency_us(),
         min_latency: if stats.min_latency == f64::MAX {
             0.0
         } else {
@@ -40,15 +282,26 @@ fn build_stats_json(state: &AppState) -> Option<String> {
         },
         max_latency: stats.max_latency,
         avg_latency: stats.avg_latency,
+        jitter_ms: stats.jitter_ms,
+        max_jitter_ms: stats.max_jitter_ms,
+        avg_1m: rolling_avg[0],
+        avg_5m: rolling_avg[1],
+        avg_15m: rolling_avg[2],
+        loss_rate_per_min,
+        p50_latency: percentiles[0],
+        p95_latency: percentiles[1],
+        p99_latency: percentiles[2],
         total_lost: stats.total_lost,
         total_corrupted: stats.total_corrupted,
         measurement_count: stats.measurement_count,
         latency_history,
         loss_history,
+        confidence_history,
         // Device info from cached stats (updated by monitoring loop)
         device_name: stats.device_name,
         buffer_size: stats.buffer_size,
         sample_rate: stats.sample_rate,
+        rate_fallback: stats.rate_fallback,
         uptime_seconds: stats.uptime_seconds,
         loss_events,
         samples_sent: stats.samples_sent,
@@ -57,17 +310,57 @@ fn build_stats_json(state: &AppState) -> Option<String> {
         confidence: stats.last_confidence,
         estimated_loss: stats.estimated_loss,
         counter_silent: stats.counter_silent,
-    };
-    serde_json::to_string(&response).ok()
+        counter_stuck: stats.counter_stuck,
+        input_levels: stats.input_levels,
+        anomaly: stats.anomaly,
+        latency_out_of_range: stats.latency_out_of_range,
+        hardware_latency_ms: stats.hardware_latency_ms,
+        excess_latency_ms,
+        clock_drift_ppm: stats.clock_drift_ppm,
+        availability_percent,
+        recording_paused: state.is_recording_paused(),
+        latency_method: stats.latency_method,
+    }
+}
+
+/// Frame to forward to a client for one broadcast receive, None to close
+///
+/// A lagging receiver (a slow client that let the channel overflow) gets a
+/// full current snapshot instead of the dropped updates, so one slow remote
+/// client neither blocks the others nor gets disconnected.
+fn forward_frame(
+    result: Result<String, RecvError>,
+    state: &AppState,
+    format: WsFormat,
+    last_total_lost: &mut Option<u64>,
+) -> Option<Message> {
+    match (result, format) {
+        // Binary clients use the broadcast as a tick and read the hot fields directly
+        (Ok(_) | Err(RecvError::Lagged(_)), WsFormat::Binary) => Some(Message::Binary(
+            build_stats_binary(state, last_total_lost).into(),
+        )),
+        (Ok(msg), WsFormat::Json) => Some(Message::Text(msg.into())),
+        (Err(RecvError::Lagged(missed)), WsFormat::Json) => {
+            build_resync_json(state, missed).map(|json| Message::Text(json.into()))
+        }
+        (Err(RecvError::Closed), _) => None,
+    }
 }
 
 /// Handle an individual WebSocket connection
-async fn handle_ws(socket: WebSocket, state: AppState) {
+async fn handle_ws(socket: WebSocket, state: AppState, format: WsFormat, authorized: bool) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
+    let mut last_total_lost = None;
 
     // Send initial stats snapshot (lock is dropped before await)
-    if let Some(json) = build_stats_json(&state) {
-        let _ = ws_sender.send(Message::Text(json.into())).await;
+    let initial = match format {
+        WsFormat::Json => build_stats_json(&state).map(|json| Message::Text(json.into())),
+        WsFormat::Binary => Some(Message::Binary(
+            build_stats_binary(&state, &mut last_total_lost).into(),
+        )),
+    };
+    if let Some(msg) = initial {
+        let _ = ws_sender.send(msg).await;
     }
 
     // Subscribe to broadcast channel
@@ -76,18 +369,29 @@ async fn handle_ws(socket: WebSocket, state: AppState) {
     // Use oneshot for graceful shutdown instead of abort()
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel::<()>();
 
-    // Spawn task to forward broadcast messages to this client
+    // Command replies go through the send task, which owns the sender
+    let (reply_tx, mut reply_rx) = tokio::sync::mpsc::channel::<String>(16);
+    let command_state = state.clone();
+
+    // Spawn task to forward broadcast messages and replies to this client
     let send_task = tokio::spawn(async move {
+        let mut missed_total = 0u64;
         loop {
             tokio::select! {
                 result = rx.recv() => {
-                    match result {
-                        Ok(msg) => {
-                            if ws_sender.send(Message::Text(msg.into())).await.is_err() {
-                                break;
-                            }
-                        }
-                        Err(_) => break,
+                    if let Err(RecvError::Lagged(missed)) = result {
+                        missed_total += missed;
+                        tracing::debug!(missed, missed_total, "WebSocket client lagged, resyncing");
+                    }
+                    let msg = forward_frame(result, &state, format, &mut last_total_lost);
+                    let Some(msg) = msg else { break };
+                    if ws_sender.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Some(reply) = reply_rx.recv() => {
+                    if ws_sender.send(Message::Text(reply.into())).await.is_err() {
+                        break;
                     }
                 }
                 _ = &mut shutdown_rx => break,
@@ -95,11 +399,21 @@ async fn handle_ws(socket: WebSocket, state: AppState) {
         }
     });
 
-    // Spawn task to handle incoming messages (pings, close)
+    // Spawn task to handle incoming messages (commands, pings, close)
     let recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = ws_receiver.next().await {
-            if matches!(msg, Message::Close(_)) {
-                break;
+            match msg {
+                Message::Text(text) => {
+                    let reply = handle_command(&command_state, text.as_str(), authorized).await;
+                    let Ok(json) = serde_json::to_string(&reply) else {
+                        continue;
+                    };
+                    if reply_tx.send(json).await.is_err() {
+                        break;
+                    }
+                }
+                Message::Close(_) => break,
+                _ => {}
             }
         }
     });
@@ -124,3 +438,191 @@ pub fn broadcast_stats(state: &AppState) {
         let _ = state.ws_tx.send(json);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use audiotester_core::audio::engine::EngineState;
+
+    #[test]
+    fn test_binary_stats_round_trip() {
+        let stats = BinaryStats {
+            current_latency: 5.125,
+            confidence: 0.875,
+            lost_delta: 48_000,
+            signal_lost: true,
+        };
+        let buf = stats.encode();
+        assert_eq!(buf.len(), BINARY_FRAME_LEN);
+        assert_eq!(buf[0], BINARY_FRAME_VERSION);
+        assert_eq!(BinaryStats::decode(&buf), Some(stats));
+
+        // Little-endian layout at the documented offsets
+        assert_eq!(buf[1], 1);
+        assert_eq!(&buf[14..22], &48_000u64.to_le_bytes());
+
+        assert_eq!(BinaryStats::decode(&buf[..21]), None);
+        let mut wrong_version = buf;
+        wrong_version[0] = 0;
+        assert_eq!(BinaryStats::decode(&wrong_version), None);
+    }
+
+    #[test]
+    fn test_binary_frames_report_lost_delta() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_loss(10);
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(store)),
+            crate::ServerConfig::default(),
+            None,
+        );
+
+        let mut last = None;
+        let first = build_stats_binary(&state, &mut last);
+        assert_eq!(BinaryStats::decode(&first).unwrap().lost_delta, 0);
+
+        state.stats.lock().unwrap().record_loss(25);
+        let second = build_stats_binary(&state, &mut last);
+        assert_eq!(BinaryStats::decode(&second).unwrap().lost_delta, 25);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_command_frames() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_latency(5.0);
+        store.record_loss(10);
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(store)),
+            crate::ServerConfig::default(),
+            None,
+        );
+
+        // A soft reset clears the counters but keeps the chart history
+        let reply = handle_command(&state, r#"{"cmd":"reset"}"#, true).await;
+        assert!(reply.ok);
+        assert_eq!(reply.reply, "reset");
+        assert_eq!(state.stats_lock().stats().total_lost, 0);
+        assert_eq!(state.stats_lock().stats().measurement_count, 0);
+        assert_eq!(state.stats_lock().latency_history().len(), 1);
+
+        let reply = handle_command(&state, r#"{"cmd":"reset","hard":true}"#, true).await;
+        assert!(reply.ok);
+        assert!(state.stats_lock().latency_history().is_empty());
+
+        // Toggle reaches the engine: starting without a device fails after
+        // the retries (paused time skips the backoff) and is reported
+        let reply = handle_command(&state, r#"{"cmd":"toggle","enabled":true}"#, true).await;
+        assert_eq!(reply.reply, "toggle");
+        assert!(!reply.ok);
+        let error = reply.error.unwrap();
+        assert!(error.contains("Failed to start"), "{}", error);
+        let status = state.engine.get_status().await.unwrap();
+        assert_eq!(status.state, EngineState::Stopped);
+
+        // Unknown commands and garbage get an error reply and change nothing
+        for frame in [r#"{"cmd":"reboot"}"#, r#"{"cmd":"toggle"}"#, "ping"] {
+            let reply = handle_command(&state, frame, true).await;
+            assert!(!reply.ok);
+            assert!(reply.error.is_some());
+            let json = serde_json::to_value(&reply).unwrap();
+            assert_eq!(json["ok"], false);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_commands_need_token() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_latency(5.0);
+        store.record_loss(10);
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(store)),
+            crate::ServerConfig {
+                auth_token: Some("s3cret".to_string()),
+                allowed_origins: vec!["http://studio-pc:8080".to_string()],
+                ..Default::default()
+            },
+            None,
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = crate::build_router(state.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        // Without the token the stream works but commands are refused
+        let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/api/v1/ws", addr))
+            .await
+            .unwrap();
+        socket.next().await.unwrap().unwrap(); // initial stats
+        socket
+            .send(r#"{"cmd":"reset","hard":true}"#.into())
+            .await
+            .unwrap();
+        let reply: serde_json::Value =
+            serde_json::from_str(socket.next().await.unwrap().unwrap().to_text().unwrap()).unwrap();
+        assert_eq!(reply["reply"], "reset");
+        assert_eq!(reply["ok"], false);
+        assert_eq!(state.stats_lock().stats().total_lost, 10);
+
+        // With the token the same command runs
+        let url = format!("ws://{}/api/v1/ws?token=s3cret", addr);
+        let (mut socket, _) = tokio_tungstenite::connect_async(url).await.unwrap();
+        socket.next().await.unwrap().unwrap();
+        socket
+            .send(r#"{"cmd":"reset","hard":true}"#.into())
+            .await
+            .unwrap();
+        let reply: serde_json::Value =
+            serde_json::from_str(socket.next().await.unwrap().unwrap().to_text().unwrap()).unwrap();
+        assert_eq!(reply["ok"], true);
+        assert_eq!(state.stats_lock().stats().total_lost, 0);
+
+        // A page on another site cannot open the socket at all
+        let mut request =
+            tokio_tungstenite::tungstenite::client::IntoClientRequest::into_client_request(
+                format!("ws://{}/api/v1/ws?token=s3cret", addr),
+            )
+            .unwrap();
+        request
+            .headers_mut()
+            .insert(header::ORIGIN, "http://evil.example".parse().unwrap());
+        assert!(tokio_tungstenite::connect_async(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lagging_client_gets_resync() {
+        let mut store = audiotester_core::stats::store::StatsStore::new();
+        store.record_latency(5.0);
+        let state = AppState::new(
+            crate::EngineHandle::spawn(),
+            std::sync::Arc::new(std::sync::Mutex::new(store)),
+            crate::ServerConfig::default(),
+            None,
+        );
+
+        // A slow consumer that reads nothing while 300 updates are broadcast
+        let mut rx = state.ws_tx.subscribe();
+        for i in 0..300 {
+            state.ws_tx.send(format!("update {}", i)).unwrap();
+        }
+
+        let mut last = None;
+        let result = rx.recv().await;
+        assert!(matches!(result, Err(RecvError::Lagged(44))));
+        let Some(Message::Text(text)) = forward_frame(result, &state, WsFormat::Json, &mut last)
+        else {
+            panic!("lagging client should get a resync frame");
+        };
+        let json: serde_json::Value = serde_json::from_str(text.as_str()).unwrap();
+        assert_eq!(json["missed_updates"], 44);
+        assert_eq!(json["current_latency"], 5.0);
+
+        // The client then continues with the retained updates
+        let next = forward_frame(rx.recv().await, &state, WsFormat::Json, &mut last);
+        assert!(matches!(next, Some(Message::Text(t)) if t.as_str() == "update 44"));
+    }
+}
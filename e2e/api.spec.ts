@@ -1,6 +1,43 @@
 import { test, expect } from "@playwright/test";
 
 test.describe("REST API", () => {
+  test("POST /api/v1/reset?hard=true wipes latency history", async ({
+    request,
+  }) => {
+    const resp = await request.post("/api/v1/reset?hard=true");
+    expect(resp.status()).toBe(204);
+    const stats = await (await request.get("/api/v1/stats")).json();
+    expect(stats.latency_history).toHaveLength(0);
+    expect(stats.measurement_count).toBe(0);
+  });
+
+  test("GET /api/v1/health reports 503 while not monitoring", async ({
+    request,
+  }) => {
+    const resp = await request.get("/api/v1/health");
+    expect(resp.status()).toBe(503);
+    const body = await resp.json();
+    expect(body.ok).toBe(false);
+    expect(typeof body.reason).toBe("string");
+  });
+
+  test("POST /api/v1/probe fails without a signal", async ({ request }) => {
+    const resp = await request.post("/api/v1/probe?timeout_ms=200");
+    expect(resp.ok()).toBe(false);
+    expect([500, 504]).toContain(resp.status());
+  });
+
+  test("POST /api/v1/selftest measures the injected delay", async ({
+    request,
+  }) => {
+    const resp = await request.post("/api/v1/selftest");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.ok).toBe(true);
+    expect(body.expected_ms).toBe(5);
+    expect(Math.abs(body.measured_ms - body.expected_ms)).toBeLessThan(0.5);
+  });
+
   test("GET /api/v1/status returns valid status", async ({ request }) => {
     const resp = await request.get("/api/v1/status");
     expect(resp.ok()).toBeTruthy();
@@ -11,6 +48,8 @@ test.describe("REST API", () => {
     expect(body).toHaveProperty("monitoring");
     expect(typeof body.version).toBe("string");
     expect(typeof body.sample_rate).toBe("number");
+    expect(typeof body.requested_sample_rate).toBe("number");
+    expect(body.rate_fallback).toBe(false);
     expect(typeof body.monitoring).toBe("boolean");
   });
 
@@ -63,6 +102,116 @@ test.describe("REST API", () => {
     expect(body.sample_rate).toBe(48000);
   });
 
+  test("PATCH /api/v1/config updates silence threshold", async ({
+    request,
+  }) => {
+    const resp = await request.patch("/api/v1/config", {
+      data: { silence_threshold_ms: 50 },
+    });
+    expect(resp.ok()).toBeTruthy();
+    expect((await resp.json()).silence_threshold_ms).toBe(50);
+
+    const bad = await request.patch("/api/v1/config", {
+      data: { silence_threshold_ms: 1 },
+    });
+    expect(bad.status()).toBe(400);
+
+    await request.patch("/api/v1/config", {
+      data: { silence_threshold_ms: 100 },
+    });
+  });
+
+  test("PATCH /api/v1/config sets and clears buffer size", async ({
+    request,
+  }) => {
+    const resp = await request.patch("/api/v1/config", {
+      data: { buffer_size: 128 },
+    });
+    expect(resp.ok()).toBeTruthy();
+    expect((await resp.json()).buffer_size).toBe(128);
+
+    const bad = await request.patch("/api/v1/config", {
+      data: { buffer_size: 7 },
+    });
+    expect(bad.status()).toBe(400);
+
+    const cleared = await request.patch("/api/v1/config", {
+      data: { buffer_size: 0 },
+    });
+    expect((await cleared.json()).buffer_size).toBeNull();
+  });
+
+  test("PATCH /api/v1/config updates signal-loss timeouts", async ({
+    request,
+  }) => {
+    const initial = await (await request.get("/api/v1/config")).json();
+    expect(initial.signal_timeout_ms).toBe(1000);
+    expect(initial.reconnect_after_loss_ms).toBe(10000);
+
+    const resp = await request.patch("/api/v1/config", {
+      data: { signal_timeout_ms: 2500, reconnect_after_loss_ms: 20000 },
+    });
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.signal_timeout_ms).toBe(2500);
+    expect(body.reconnect_after_loss_ms).toBe(20000);
+
+    // Reconnect delay shorter than the loss timeout is rejected
+    const bad = await request.patch("/api/v1/config", {
+      data: { reconnect_after_loss_ms: 2000 },
+    });
+    expect(bad.status()).toBe(400);
+
+    await request.patch("/api/v1/config", {
+      data: { signal_timeout_ms: 1000, reconnect_after_loss_ms: 10000 },
+    });
+  });
+
+  test("PATCH /api/v1/config updates latency alarm thresholds", async ({
+    request,
+  }) => {
+    const initial = await (await request.get("/api/v1/config")).json();
+    expect(initial.latency_warning_ms).toBe(30);
+    expect(initial.latency_error_ms).toBe(50);
+
+    const resp = await request.patch("/api/v1/config", {
+      data: { latency_warning_ms: 15, latency_error_ms: 30 },
+    });
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.latency_warning_ms).toBe(15);
+    expect(body.latency_error_ms).toBe(30);
+
+    // Warning above the error threshold is rejected
+    const bad = await request.patch("/api/v1/config", {
+      data: { latency_warning_ms: 40 },
+    });
+    expect(bad.status()).toBe(400);
+
+    await request.patch("/api/v1/config", {
+      data: { latency_warning_ms: 30, latency_error_ms: 50 },
+    });
+  });
+
+  test("PATCH /api/v1/config sets signal channels", async ({ request }) => {
+    const resp = await request.patch("/api/v1/config", {
+      data: { burst_channel: 2, counter_channel: 3 },
+    });
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.burst_channel).toBe(2);
+    expect(body.counter_channel).toBe(3);
+
+    const clash = await request.patch("/api/v1/config", {
+      data: { burst_channel: 3 },
+    });
+    expect(clash.status()).toBe(400);
+
+    await request.patch("/api/v1/config", {
+      data: { burst_channel: 0, counter_channel: 1 },
+    });
+  });
+
   test("POST /api/v1/monitoring accepts toggle request", async ({
     request,
   }) => {
@@ -74,6 +223,81 @@ test.describe("REST API", () => {
     expect([200, 500]).toContain(resp.status());
   });
 
+  test("PUT /api/v1/device rejects unknown device", async ({ request }) => {
+    const resp = await request.put("/api/v1/device", {
+      data: { name: "No Such Device" },
+    });
+    expect(resp.status()).toBe(400);
+  });
+
+  test("DELETE /api/v1/device clears selection", async ({ request }) => {
+    const resp = await request.delete("/api/v1/device");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.device).toBeNull();
+    expect(body.monitoring).toBe(false);
+  });
+
+  test("POST /api/v1/log-level adjusts filter at runtime", async ({
+    request,
+  }) => {
+    const resp = await request.post("/api/v1/log-level", {
+      data: { target: "audiotester_core", level: "trace" },
+    });
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.filter).toContain("audiotester_core=trace");
+
+    const current = await (await request.get("/api/v1/log-level")).json();
+    expect(current.filter).toContain("audiotester_core=trace");
+
+    const bad = await request.post("/api/v1/log-level", {
+      data: { level: "loud" },
+    });
+    expect(bad.status()).toBe(400);
+  });
+
+  test("GET /api/v1/snapshot bundles status, stats, config and events", async ({
+    request,
+  }) => {
+    const resp = await request.get("/api/v1/snapshot");
+    expect(resp.ok()).toBeTruthy();
+    expect(resp.headers()["content-disposition"]).toContain("attachment");
+    const body = await resp.json();
+    expect(typeof body.session_id).toBe("string");
+    expect(body).toHaveProperty("version");
+    expect(body).toHaveProperty("status");
+    expect(body).toHaveProperty("stats");
+    expect(body.config).toHaveProperty("reconnect_strategy");
+    expect(body.config).toHaveProperty("data_paths");
+    expect(body).toHaveProperty("diagnostics");
+    expect(Array.isArray(body.events.events)).toBeTruthy();
+    expect(Array.isArray(body.asio_events.events)).toBeTruthy();
+    expect(body).toHaveProperty("logs");
+  });
+
+  test("POST /api/v1/capture validates duration", async ({ request }) => {
+    const bad = await request.post("/api/v1/capture", {
+      data: { duration_secs: 0 },
+    });
+    expect(bad.status()).toBe(400);
+
+    // Without running streams there is nothing to capture
+    const resp = await request.post("/api/v1/capture", {
+      data: { duration_secs: 5 },
+    });
+    expect([200, 409]).toContain(resp.status());
+  });
+
+  test("GET /metrics returns Prometheus exposition", async ({ request }) => {
+    const resp = await request.get("/metrics");
+    expect(resp.ok()).toBeTruthy();
+    expect(resp.headers()["content-type"]).toContain("text/plain");
+    const text = await resp.text();
+    expect(text).toContain("# TYPE audiotester_latency_ms gauge");
+    expect(text).toMatch(/^audiotester_signal_lost\{device="[^"]*"\} [01]$/m);
+  });
+
   test("GET /api/v1/ws WebSocket endpoint exists", async ({ request }) => {
     // Just verify the endpoint doesn't 404 (it will fail upgrade without WS headers)
     const resp = await request.get("/api/v1/ws");
@@ -251,6 +251,11 @@ test.describe("Counter Silence Detection (CH1 Mute)", () => {
     const lostEl = page.locator('[data-testid="lost-value"]');
     const text = await lostEl.textContent();
     expect(text).toMatch(/^~/);
+
+    // Estimated part is called out separately
+    const unverifiedEl = page.locator('[data-testid="lost-unverified"]');
+    await expect(unverifiedEl).toBeVisible();
+    await expect(unverifiedEl).toHaveText(/samples unverified$/);
   });
 
   test("8: dashboard removes ~ prefix after unmute", async ({
@@ -156,3 +156,59 @@ test.describe("Latency Timeline UI", () => {
     await expect(liveBtn).toHaveClass(/active/);
   });
 });
+
+test.describe("Downsampled Latency API", () => {
+  test("GET /api/v1/latency returns at most the requested points", async ({
+    request,
+  }) => {
+    const resp = await request.get("/api/v1/latency?points=50");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(Array.isArray(body.points)).toBe(true);
+    expect(body.points.length).toBeLessThanOrEqual(50);
+  });
+});
+
+test.describe("Annotations API", () => {
+  test("GET /api/v1/annotations returns chart markers", async ({ request }) => {
+    const resp = await request.get("/api/v1/annotations?range=1h");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(Array.isArray(body.annotations)).toBe(true);
+    for (const a of body.annotations) {
+      expect(["disconnection", "loss"]).toContain(a.type);
+      expect(a.label.length).toBeGreaterThan(0);
+    }
+  });
+});
+
+test.describe("Jitter Histogram API", () => {
+  test("GET /api/v1/jitter-histogram returns buckets", async ({ request }) => {
+    const resp = await request.get("/api/v1/jitter-histogram?bucket_ms=0.1");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.bucket_ms).toBe(0.1);
+    expect(typeof body.max_jitter_ms).toBe("number");
+    expect(Array.isArray(body.buckets)).toBe(true);
+  });
+
+  test("GET /api/v1/jitter-histogram rejects zero bucket width", async ({
+    request,
+  }) => {
+    const resp = await request.get("/api/v1/jitter-histogram?bucket_ms=0");
+    expect(resp.status()).toBe(400);
+  });
+});
+
+test.describe("Rolling Average Latency", () => {
+  test("GET /api/v1/stats includes 1/5/15 minute averages", async ({
+    request,
+  }) => {
+    const resp = await request.get("/api/v1/stats");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    for (const key of ["avg_1m", "avg_5m", "avg_15m"]) {
+      expect(typeof body[key]).toBe("number");
+    }
+  });
+});
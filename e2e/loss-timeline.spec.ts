@@ -183,4 +183,19 @@ test.describe("Loss Timeline UI", () => {
     await liveBtn.click();
     await expect(liveBtn).toHaveClass(/active/);
   });
+
+  test("GET /api/v1/loss-timeline/report returns structured summary", async ({
+    request,
+  }) => {
+    const resp = await request.get("/api/v1/loss-timeline/report?range=24h");
+    expect(resp.ok()).toBeTruthy();
+    const body = await resp.json();
+    expect(body.range).toBe("24h");
+    expect(typeof body.total_loss).toBe("number");
+    expect(typeof body.active_buckets).toBe("number");
+    expect(Array.isArray(body.worst_buckets)).toBe(true);
+    expect(body.worst_buckets.length).toBeLessThanOrEqual(5);
+    expect(body).toHaveProperty("longest_clean");
+    expect(typeof body.summary).toBe("string");
+  });
 });
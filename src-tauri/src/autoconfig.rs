@@ -0,0 +1,136 @@
+//! Auto-configure retry schedule
+//!
+//! After boot the ASIO driver (VBMatrix in particular) may need a while
+//! before a device can be selected, so auto-configuration waits and then
+//! retries select+start. `AUDIOTESTER_AUTOCONFIG_DELAY`,
+//! `AUDIOTESTER_AUTOCONFIG_ATTEMPTS` and `AUDIOTESTER_AUTOCONFIG_INTERVAL`
+//! tune the schedule for fast-booting or slow machines.
+
+use std::time::Duration;
+
+/// Longest accepted initial delay or retry interval (seconds)
+pub const MAX_AUTOCONFIG_SECS: u64 = 600;
+
+/// Most accepted select+start attempts
+pub const MAX_AUTOCONFIG_ATTEMPTS: u32 = 1000;
+
+/// When and how often auto-configuration tries to select and start the device
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutoConfigSchedule {
+    /// Wait before the first attempt
+    pub delay: Duration,
+    /// Select+start attempts before giving up (at least 1)
+    pub attempts: u32,
+    /// Wait between attempts
+    pub interval: Duration,
+}
+
+impl Default for AutoConfigSchedule {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_secs(10),
+            attempts: 20,
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl AutoConfigSchedule {
+    /// Read the schedule from the environment, logging and ignoring invalid values
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        let (schedule, errors) = Self::from_values(
+            var("AUDIOTESTER_AUTOCONFIG_DELAY").as_deref(),
+            var("AUDIOTESTER_AUTOCONFIG_ATTEMPTS").as_deref(),
+            var("AUDIOTESTER_AUTOCONFIG_INTERVAL").as_deref(),
+        );
+        for error in errors {
+            tracing::warn!(%error, "Invalid auto-configure setting, using default");
+        }
+        schedule
+    }
+
+    /// Build a schedule from raw values, keeping the default for unset or invalid ones
+    ///
+    /// Returns the schedule and one message per rejected value.
+    pub fn from_values(
+        delay: Option<&str>,
+        attempts: Option<&str>,
+        interval: Option<&str>,
+    ) -> (Self, Vec<String>) {
+        let mut schedule = Self::default();
+        let mut errors = Vec::new();
+        if let Some(value) = delay {
+            match parse_secs("AUDIOTESTER_AUTOCONFIG_DELAY", value, 0) {
+                Ok(delay) => schedule.delay = delay,
+                Err(e) => errors.push(e),
+            }
+        }
+        if let Some(value) = attempts {
+            match parse_attempts(value) {
+                Ok(attempts) => schedule.attempts = attempts,
+                Err(e) => errors.push(e),
+            }
+        }
+        if let Some(value) = interval {
+            match parse_secs("AUDIOTESTER_AUTOCONFIG_INTERVAL", value, 1) {
+                Ok(interval) => schedule.interval = interval,
+                Err(e) => errors.push(e),
+            }
+        }
+        (schedule, errors)
+    }
+}
+
+/// Parse whole seconds of at least `min`, clamped to [`MAX_AUTOCONFIG_SECS`]
+fn parse_secs(name: &str, value: &str, min: u64) -> Result<Duration, String> {
+    match value.trim().parse::<u64>() {
+        Ok(secs) if secs >= min => Ok(Duration::from_secs(secs.min(MAX_AUTOCONFIG_SECS))),
+        _ => Err(format!(
+            "{} must be a whole number of seconds >= {} (got {:?})",
+            name, min, value
+        )),
+    }
+}
+
+/// Parse an attempt count of at least 1, clamped to [`MAX_AUTOCONFIG_ATTEMPTS`]
+fn parse_attempts(value: &str) -> Result<u32, String> {
+    match value.trim().parse::<u32>() {
+        Ok(attempts) if attempts > 0 => Ok(attempts.min(MAX_AUTOCONFIG_ATTEMPTS)),
+        _ => Err(format!(
+            "AUDIOTESTER_AUTOCONFIG_ATTEMPTS must be at least 1 (got {:?})",
+            value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_parsing() {
+        let (schedule, errors) = AutoConfigSchedule::from_values(None, None, None);
+        assert_eq!(schedule, AutoConfigSchedule::default());
+        assert!(errors.is_empty());
+
+        let (schedule, errors) =
+            AutoConfigSchedule::from_values(Some("0"), Some(" 60 "), Some("2"));
+        assert!(errors.is_empty());
+        assert_eq!(schedule.delay, Duration::ZERO);
+        assert_eq!(schedule.attempts, 60);
+        assert_eq!(schedule.interval, Duration::from_secs(2));
+
+        // Out-of-range values are clamped
+        let (schedule, _) = AutoConfigSchedule::from_values(Some("3600"), Some("99999"), None);
+        assert_eq!(schedule.delay, Duration::from_secs(MAX_AUTOCONFIG_SECS));
+        assert_eq!(schedule.attempts, MAX_AUTOCONFIG_ATTEMPTS);
+
+        // Zero attempts, a zero interval and garbage keep the defaults
+        let (schedule, errors) =
+            AutoConfigSchedule::from_values(Some("soon"), Some("0"), Some("0"));
+        assert_eq!(schedule, AutoConfigSchedule::default());
+        assert_eq!(errors.len(), 3);
+        assert!(errors[1].contains("AUDIOTESTER_AUTOCONFIG_ATTEMPTS"));
+    }
+}
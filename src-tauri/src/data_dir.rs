@@ -0,0 +1,65 @@
+//! Data directory selection
+//!
+//! Logs and saved statistics live in `dirs::data_dir()/audiotester`
+//! (`%APPDATA%\audiotester` on Windows). `AUDIOTESTER_DATA_DIR` moves them
+//! elsewhere for portable installs on a USB stick or locked-down machines.
+
+use std::path::PathBuf;
+
+/// Default data directory under the platform data dir
+pub fn default_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("audiotester")
+}
+
+/// Resolve and create the data directory
+///
+/// An override wins when it is non-empty and can be created; otherwise the
+/// default is used. Returns the directory and, when the override was
+/// rejected, why (logging is not up yet when this runs).
+pub fn resolve(override_dir: Option<PathBuf>, default: PathBuf) -> (PathBuf, Option<String>) {
+    let mut rejected = None;
+    if let Some(dir) = override_dir.filter(|dir| !dir.as_os_str().is_empty()) {
+        match std::fs::create_dir_all(&dir) {
+            Ok(()) => return (dir, None),
+            Err(e) => rejected = Some(format!("{}: {}", dir.display(), e)),
+        }
+    }
+    std::fs::create_dir_all(&default).ok();
+    (default, rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_precedence() {
+        let root = std::env::temp_dir().join(format!("audiotester-data-{}", std::process::id()));
+        let default = root.join("default");
+
+        // A valid override is created and wins
+        let portable = root.join("usb").join("audiotester");
+        let (dir, rejected) = resolve(Some(portable.clone()), default.clone());
+        assert_eq!(dir, portable);
+        assert!(dir.is_dir());
+        assert_eq!(rejected, None);
+
+        // No override, or an empty one, uses the default
+        assert_eq!(resolve(None, default.clone()), (default.clone(), None));
+        assert_eq!(
+            resolve(Some(PathBuf::new()), default.clone()),
+            (default.clone(), None)
+        );
+
+        // An override that cannot be created falls back to the default
+        let file = root.join("file");
+        std::fs::write(&file, b"").unwrap();
+        let (dir, rejected) = resolve(Some(file.join("data")), default.clone());
+        assert_eq!(dir, default);
+        assert!(rejected.is_some());
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+}
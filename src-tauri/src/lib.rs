@@ -3,10 +3,21 @@
 //! Desktop shell providing tray icon, window, and NSIS installer.
 //! All UI is served by the embedded Axum + Leptos SSR server.
 
+pub mod autoconfig;
+pub mod data_dir;
+pub mod notify;
+pub mod priority;
 pub mod tray;
 
-use audiotester_core::stats::store::StatsStore;
-use audiotester_server::{AppState, EngineHandle, ServerConfig};
+use audiotester_core::audio::engine::HostPreference;
+use audiotester_core::stats::store::{AsioEventKind, StatsStore};
+use audiotester_server::alert::{AlertPayload, WebhookAlerter};
+use audiotester_server::midi::MidiStatusSender;
+use audiotester_server::osc::OscSender;
+use audiotester_server::{
+    lock_stats, AppState, AutoConfigInfo, EngineHandle, LatencyThresholds, ReconnectAction,
+    ReconnectStrategy, ServerConfig, SignalLossTimeouts,
+};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Listener, Manager, WindowEvent};
@@ -14,9 +25,47 @@ use tauri::{AppHandle, Emitter, Listener, Manager, WindowEvent};
 /// Global AppHandle storage for cross-thread tray updates
 static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
 
+/// MIDI status light output, opened once when monitoring starts
+static MIDI_STATUS: OnceLock<MidiStatusSender> = OnceLock::new();
+
 /// Notify when APP_HANDLE becomes available (replaces busy-wait polling)
 static APP_HANDLE_NOTIFY: OnceLock<Arc<tokio::sync::Notify>> = OnceLock::new();
 
+/// How long to wait for the engine thread to release the driver on exit
+const ENGINE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Persist statistics to `path`, logging (not propagating) failures
+fn save_stats(store: &mut StatsStore, path: &std::path::Path) {
+    match store.save_to_path(path) {
+        Ok(()) => tracing::debug!(path = %path.display(), "Statistics saved"),
+        Err(e) => tracing::warn!(error = %e, "Failed to save statistics"),
+    }
+}
+
+/// Save statistics from the async monitoring loop
+///
+/// Serializes under the stats lock, then writes the file on a blocking
+/// thread with the lock released, so API readers and the engine result path
+/// never wait on disk I/O.
+async fn persist_stats(stats: &Mutex<StatsStore>, path: &std::path::Path) {
+    let json = match lock_stats(stats).persisted_json() {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to save statistics");
+            return;
+        }
+    };
+    let target = path.to_path_buf();
+    match tokio::task::spawn_blocking(move || StatsStore::write_persisted(&json, &target)).await {
+        Ok(Ok(())) => {
+            lock_stats(stats).mark_saved();
+            tracing::debug!(path = %path.display(), "Statistics saved");
+        }
+        Ok(Err(e)) => tracing::warn!(error = %e, "Failed to save statistics"),
+        Err(e) => tracing::warn!(error = %e, "Statistics save task failed"),
+    }
+}
+
 /// Run the Tauri application
 pub fn run() {
     // Set panic handler for better diagnostics
@@ -24,47 +73,86 @@ pub fn run() {
         tracing::error!("PANIC: {}", info);
     }));
 
+    // AUDIOTESTER_DATA_DIR relocates logs and saved statistics (portable installs)
+    let (data_dir, data_dir_error) = data_dir::resolve(
+        std::env::var_os("AUDIOTESTER_DATA_DIR").map(std::path::PathBuf::from),
+        data_dir::default_dir(),
+    );
+
     // Initialize file-based logging with daily rotation
-    let log_dir = dirs::data_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("audiotester")
-        .join("logs");
+    let log_dir = data_dir.join("logs");
     std::fs::create_dir_all(&log_dir).ok();
 
     let file_appender = tracing_appender::rolling::daily(&log_dir, "audiotester.log");
     let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
 
-    let file_layer = tracing_subscriber::fmt::layer()
-        .with_writer(non_blocking)
-        .with_ansi(false);
+    // AUDIOTESTER_LOG_JSON=1 writes one JSON object per line for log ingestion
+    let log_json = std::env::var("AUDIOTESTER_LOG_JSON").is_ok_and(|value| {
+        matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "on" | "yes"
+        )
+    });
+
+    use tracing_subscriber::Layer;
+    let file_layer = if log_json {
+        tracing_subscriber::fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_writer(non_blocking)
+            .with_ansi(false)
+            .boxed()
+    } else {
+        tracing_subscriber::fmt::layer()
+            .with_writer(non_blocking)
+            .with_ansi(false)
+            .boxed()
+    };
 
     let env_filter = tracing_subscriber::EnvFilter::from_default_env()
         .add_directive("audiotester=debug".parse().unwrap())
         .add_directive("audiotester_core=debug".parse().unwrap())
         .add_directive("audiotester_server=info".parse().unwrap());
 
+    // Reloadable so the level can be raised via /api/v1/log-level without restarting
+    let (filter_layer, filter_handle) = tracing_subscriber::reload::Layer::new(env_filter);
+
+    // Recent events kept in memory for GET /api/v1/events/log
+    let event_log = Arc::new(audiotester_server::event_log::EventLog::new());
+
     use tracing_subscriber::layer::SubscriberExt;
     use tracing_subscriber::util::SubscriberInitExt;
     tracing_subscriber::registry()
-        .with(env_filter)
+        .with(filter_layer)
         .with(file_layer)
+        .with(audiotester_server::event_log::EventLogLayer::new(
+            Arc::clone(&event_log),
+        ))
         .init();
 
-    tracing::info!(log_dir = %log_dir.display(), "Logging initialized");
+    tracing::info!(log_dir = %log_dir.display(), json = log_json, "Logging initialized");
+    if let Some(error) = data_dir_error {
+        tracing::warn!(%error, "Invalid AUDIOTESTER_DATA_DIR, using default");
+    }
     tracing::info!("Starting Audiotester v{}", audiotester_core::VERSION);
 
-    // Set process priority to HIGH for audio stability (prevents ASIO callback starvation
-    // when other windows overlap the dashboard - issue #23)
+    // Raise process priority for audio stability (prevents ASIO callback starvation
+    // when other windows overlap the dashboard - issue #23). HIGH unless overridden.
     #[cfg(target_os = "windows")]
     {
-        use windows_sys::Win32::System::Threading::{
-            GetCurrentProcess, SetPriorityClass, HIGH_PRIORITY_CLASS,
+        let priority = match std::env::var("AUDIOTESTER_PRIORITY") {
+            Ok(value) => value
+                .parse::<priority::ProcessPriority>()
+                .unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "Invalid AUDIOTESTER_PRIORITY, using high");
+                    priority::ProcessPriority::default()
+                }),
+            Err(_) => priority::ProcessPriority::default(),
         };
-        let result = unsafe { SetPriorityClass(GetCurrentProcess(), HIGH_PRIORITY_CLASS) };
-        if result != 0 {
-            tracing::info!("Process priority set to HIGH for audio stability");
+        if priority::apply(priority) {
+            tracing::info!(%priority, "Process priority set for audio stability");
         } else {
-            tracing::warn!("Failed to set process priority to HIGH");
+            tracing::warn!(%priority, "Failed to set process priority");
         }
     }
 
@@ -72,11 +160,249 @@ pub fn run() {
     let _ = APP_HANDLE_NOTIFY.set(Arc::new(tokio::sync::Notify::new()));
 
     // Create shared state
-    let engine = EngineHandle::spawn();
-    let stats = Arc::new(Mutex::new(StatsStore::new()));
-
-    let config = ServerConfig::default();
-    let state = AppState::new(engine.clone(), Arc::clone(&stats), config, Some(log_dir));
+    let host = match std::env::var("AUDIOTESTER_HOST") {
+        Ok(value) => value.parse::<HostPreference>().unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Invalid AUDIOTESTER_HOST, using auto");
+            HostPreference::Auto
+        }),
+        Err(_) => HostPreference::Auto,
+    };
+    let engine = EngineHandle::spawn_with_host(host);
+    let stats_path = data_dir.join("stats.json");
+    let mut store = if stats_path.exists() {
+        match StatsStore::load_from_path(&stats_path) {
+            Ok(store) => {
+                tracing::info!(path = %stats_path.display(), "Restored saved statistics");
+                store
+            }
+            Err(e) => {
+                // Keep the bad file for inspection instead of overwriting it on the next save
+                let corrupt_path = stats_path.with_extension("json.corrupt");
+                tracing::warn!(
+                    error = %e,
+                    moved_to = %corrupt_path.display(),
+                    "Statistics file unreadable, starting with empty history"
+                );
+                std::fs::rename(&stats_path, &corrupt_path).ok();
+                StatsStore::new()
+            }
+        }
+    } else {
+        StatsStore::new()
+    };
+    if let Ok(value) = std::env::var("AUDIOTESTER_SPIKE_FACTOR") {
+        match value.trim().parse::<f64>() {
+            Ok(factor) if factor > 0.0 => store.set_spike_factor(factor),
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_SPIKE_FACTOR"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_LOSS_COALESCE_MS") {
+        match value.trim().parse::<u64>() {
+            Ok(ms) => store.set_loss_coalesce_ms(ms),
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_LOSS_COALESCE_MS"),
+        }
+    }
+    let stats = Arc::new(Mutex::new(store));
+
+    let mut config = ServerConfig {
+        stats_path: Some(stats_path.clone()),
+        baseline_path: Some(data_dir.join("baseline.json")),
+        preferences_path: Some(data_dir.join("preferences.json")),
+        incident_dir: Some(data_dir.join("incidents")),
+        ..Default::default()
+    };
+    if let Ok(value) = std::env::var("AUDIOTESTER_INCIDENT_MIN_LOSS") {
+        match value.trim().parse::<u64>() {
+            Ok(samples) if samples > 0 => config.incident_min_loss = samples,
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_INCIDENT_MIN_LOSS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_PORT") {
+        match value.trim().parse::<u16>() {
+            Ok(port) if port > 0 => config.port = port,
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_PORT"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_BIND") {
+        let addr = value.trim();
+        if addr.parse::<std::net::IpAddr>().is_ok() {
+            config.bind_addr = addr.to_string();
+        } else {
+            tracing::warn!(value = %value, "Invalid AUDIOTESTER_BIND");
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_RECONNECT_STRATEGY") {
+        match value.parse::<ReconnectStrategy>() {
+            Ok(strategy) => {
+                tracing::info!(strategy = ?strategy, "Reconnect strategy configured");
+                config.reconnect_strategy = strategy;
+            }
+            Err(e) => tracing::warn!(error = %e, "Invalid AUDIOTESTER_RECONNECT_STRATEGY"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_MAX_RECONNECT_ATTEMPTS") {
+        match value.trim().parse::<u32>() {
+            Ok(attempts) => config.max_reconnect_attempts = attempts,
+            Err(_) => {
+                tracing::warn!(value = %value, "Invalid AUDIOTESTER_MAX_RECONNECT_ATTEMPTS")
+            }
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_MIN_CONFIDENCE") {
+        match value.trim().parse::<f32>() {
+            Ok(confidence) if (0.0..=1.0).contains(&confidence) => {
+                config.min_confidence = confidence
+            }
+            _ => {
+                tracing::warn!(value = %value, "Invalid AUDIOTESTER_MIN_CONFIDENCE (expected 0.0-1.0)")
+            }
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_MIN_LATENCY_MS") {
+        match value.trim().parse::<f64>() {
+            Ok(ms) if ms >= 0.0 => config.min_latency_ms = ms,
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_MIN_LATENCY_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_MAX_LATENCY_MS") {
+        match value.trim().parse::<f64>() {
+            Ok(ms) if ms > 0.0 => config.max_latency_ms = ms,
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_MAX_LATENCY_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_EXPECTED_LATENCY_MS") {
+        match value.trim().parse::<f64>() {
+            Ok(ms) if ms > 0.0 => config.expected_latency_ms = Some(ms),
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_EXPECTED_LATENCY_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_LATENCY_TOLERANCE_MS") {
+        match value.trim().parse::<f64>() {
+            Ok(ms) if ms >= 0.0 => config.latency_tolerance_ms = ms,
+            _ => tracing::warn!(value = %value, "Invalid AUDIOTESTER_LATENCY_TOLERANCE_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_STARTUP_GRACE_MS") {
+        match value.trim().parse::<u64>() {
+            Ok(ms) => config.startup_grace_ms = ms,
+            Err(_) => tracing::warn!(value = %value, "Invalid AUDIOTESTER_STARTUP_GRACE_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_NOTIFICATIONS") {
+        config.notifications_enabled = !matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "0" | "false" | "off" | "no"
+        );
+    }
+    if let Ok(token) = std::env::var("AUDIOTESTER_TOKEN") {
+        let token = token.trim();
+        if !token.is_empty() {
+            config.auth_token = Some(token.to_string());
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_TOKEN_ALL") {
+        config.auth_all = matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "on" | "yes"
+        );
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_ALLOWED_ORIGINS") {
+        config.allowed_origins = value
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    if let Ok(url) = std::env::var("AUDIOTESTER_ALERT_WEBHOOK") {
+        let url = url.trim();
+        if !url.is_empty() {
+            config.alert_webhook = Some(url.to_string());
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_OSC_TARGET") {
+        match value.trim().parse::<std::net::SocketAddr>() {
+            Ok(target) => config.osc_target = Some(target),
+            Err(_) => {
+                tracing::warn!(value = %value, "Invalid AUDIOTESTER_OSC_TARGET (expected ip:port)")
+            }
+        }
+    }
+    if let Ok(port) = std::env::var("AUDIOTESTER_MIDI_PORT") {
+        let port = port.trim();
+        if !port.is_empty() {
+            config.midi_port = Some(port.to_string());
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_STATS_SAVE_INTERVAL_SECS") {
+        match value.trim().parse::<u64>() {
+            Ok(secs) => config.stats_save_interval_secs = secs,
+            Err(_) => {
+                tracing::warn!(value = %value, "Invalid AUDIOTESTER_STATS_SAVE_INTERVAL_SECS")
+            }
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_SIGNAL_TIMEOUT_MS") {
+        match value.trim().parse::<u64>() {
+            Ok(ms) => config.signal_timeout_ms = ms,
+            Err(_) => tracing::warn!(value = %value, "Invalid AUDIOTESTER_SIGNAL_TIMEOUT_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_RECONNECT_AFTER_LOSS_MS") {
+        match value.trim().parse::<u64>() {
+            Ok(ms) => config.reconnect_after_loss_ms = ms,
+            Err(_) => {
+                tracing::warn!(value = %value, "Invalid AUDIOTESTER_RECONNECT_AFTER_LOSS_MS")
+            }
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_LATENCY_WARNING_MS") {
+        match value.trim().parse::<f64>() {
+            Ok(ms) => config.latency_warning_ms = ms,
+            Err(_) => tracing::warn!(value = %value, "Invalid AUDIOTESTER_LATENCY_WARNING_MS"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_LATENCY_ERROR_MS") {
+        match value.trim().parse::<f64>() {
+            Ok(ms) => config.latency_error_ms = ms,
+            Err(_) => tracing::warn!(value = %value, "Invalid AUDIOTESTER_LATENCY_ERROR_MS"),
+        }
+    }
+    if let Err(e) = LatencyThresholds::validate(config.latency_warning_ms, config.latency_error_ms)
+    {
+        let defaults = ServerConfig::default();
+        tracing::warn!(error = %e, "Invalid latency thresholds, using defaults");
+        config.latency_warning_ms = defaults.latency_warning_ms;
+        config.latency_error_ms = defaults.latency_error_ms;
+    }
+    if let Err(e) =
+        ServerConfig::validate_latency_window(config.min_latency_ms, config.max_latency_ms)
+    {
+        let defaults = ServerConfig::default();
+        tracing::warn!(error = %e, "Invalid valid-latency window, using defaults");
+        config.min_latency_ms = defaults.min_latency_ms;
+        config.max_latency_ms = defaults.max_latency_ms;
+    }
+    if let Err(e) =
+        SignalLossTimeouts::validate(config.signal_timeout_ms, config.reconnect_after_loss_ms)
+    {
+        let defaults = ServerConfig::default();
+        tracing::warn!(error = %e, "Invalid signal-loss timeouts, using defaults");
+        config.signal_timeout_ms = defaults.signal_timeout_ms;
+        config.reconnect_after_loss_ms = defaults.reconnect_after_loss_ms;
+    }
+    // Auto-configure runs when a device or auto-start is requested
+    let autoconfig_schedule = (std::env::var("AUDIOTESTER_DEVICE").is_ok()
+        || std::env::var("AUDIOTESTER_AUTO_START").is_ok())
+    .then(autoconfig::AutoConfigSchedule::from_env);
+    config.autoconfig = autoconfig_schedule.map(|schedule| AutoConfigInfo {
+        delay_secs: schedule.delay.as_secs(),
+        attempts: schedule.attempts,
+        interval_secs: schedule.interval.as_secs(),
+    });
+    let state = AppState::new(engine.clone(), Arc::clone(&stats), config, Some(log_dir))
+        .with_log_filter(filter_handle)
+        .with_event_log(event_log);
 
     // Single Tokio runtime for all async tasks
     let rt = tokio::runtime::Runtime::new().expect("Failed to create Tokio runtime");
@@ -90,20 +416,43 @@ pub fn run() {
         }
     });
 
+    if let Ok(value) = std::env::var("AUDIOTESTER_MLS_FALLBACK_CYCLES") {
+        match value.trim().parse::<u32>() {
+            Ok(cycles) => {
+                let fallback_engine = engine.clone();
+                rt_handle.spawn(async move {
+                    fallback_engine.set_mls_fallback_cycles(cycles).await;
+                });
+            }
+            Err(_) => tracing::warn!(value = %value, "Invalid AUDIOTESTER_MLS_FALLBACK_CYCLES"),
+        }
+    }
+    if let Ok(value) = std::env::var("AUDIOTESTER_ECHO_GUARD_MS") {
+        match value.trim().parse::<u32>() {
+            Ok(ms) => {
+                let guard_engine = engine.clone();
+                rt_handle.spawn(async move {
+                    guard_engine.set_echo_guard_ms(ms).await;
+                });
+            }
+            Err(_) => tracing::warn!(value = %value, "Invalid AUDIOTESTER_ECHO_GUARD_MS"),
+        }
+    }
+
     // Spawn auto-configure if env vars are set
-    if std::env::var("AUDIOTESTER_DEVICE").is_ok()
-        || std::env::var("AUDIOTESTER_AUTO_START").is_ok()
-    {
+    if let Some(schedule) = autoconfig_schedule {
         let auto_engine = engine.clone();
         rt_handle.spawn(async move {
-            auto_configure(auto_engine).await;
+            auto_configure(auto_engine, schedule).await;
         });
     }
 
     // Spawn the monitoring loop
     let monitor_state = state.clone();
+    let exit_engine = engine.clone();
+    let exit_rt = rt_handle.clone();
     let monitor_engine = engine;
-    let monitor_stats = stats;
+    let monitor_stats = Arc::clone(&stats);
     rt_handle.spawn(async move {
         monitoring_loop(monitor_engine, monitor_stats, monitor_state).await;
     });
@@ -123,13 +472,14 @@ pub fn run() {
             }
         }))
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
         .on_window_event(|window, event| {
             if let WindowEvent::CloseRequested { api, .. } = event {
                 api.prevent_close();
                 let _ = window.hide();
             }
         })
-        .setup(|app| {
+        .setup(move |app| {
             let handle = app.handle().clone();
 
             // Store AppHandle globally for monitoring loop access
@@ -140,7 +490,12 @@ pub fn run() {
             }
 
             // Setup tray
-            if let Err(e) = tray::setup_tray(&handle) {
+            let on_exit = move |app: &AppHandle| {
+                let app = app.clone();
+                let engine = exit_engine.clone();
+                exit_rt.spawn(shutdown_and_exit(app, engine));
+            };
+            if let Err(e) = tray::setup_tray(&handle, on_exit) {
                 tracing::error!("Failed to setup tray: {}", e);
             }
 
@@ -157,8 +512,29 @@ pub fn run() {
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running Audiotester");
+        .build(tauri::generate_context!())
+        .expect("error while building Audiotester")
+        .run(move |_app, event| {
+            // Persist statistics on clean shutdown (tray Exit)
+            if let tauri::RunEvent::Exit = event {
+                save_stats(&mut lock_stats(&stats), &stats_path);
+            }
+        });
+}
+
+/// Stop the engine, wait for its thread to release the driver, then exit
+///
+/// Exiting while ASIO streams are still open can leave the driver in a
+/// state that needs a VBMatrix restart.
+async fn shutdown_and_exit(app: AppHandle, engine: EngineHandle) {
+    if let Err(e) = engine.stop().await {
+        tracing::warn!("Failed to stop engine before exit: {}", e);
+    }
+    match engine.shutdown(ENGINE_SHUTDOWN_TIMEOUT).await {
+        Ok(()) => tracing::info!("Engine thread stopped"),
+        Err(e) => tracing::warn!("Engine shutdown incomplete: {}", e),
+    }
+    app.exit(0);
 }
 
 /// Auto-configure the engine from environment variables.
@@ -166,10 +542,17 @@ pub fn run() {
 /// Reads `AUDIOTESTER_DEVICE`, `AUDIOTESTER_SAMPLE_RATE`, and
 /// `AUDIOTESTER_AUTO_START` to set up the audio engine without
 /// manual web UI interaction.
-async fn auto_configure(engine: EngineHandle) {
+async fn auto_configure(engine: EngineHandle, schedule: autoconfig::AutoConfigSchedule) {
+    tracing::info!(
+        delay_secs = schedule.delay.as_secs(),
+        attempts = schedule.attempts,
+        interval_secs = schedule.interval.as_secs(),
+        "Auto-configure schedule"
+    );
+
     // Wait for ASIO subsystem to initialize after boot/reboot.
     // VBMatrix may take 30-60s to fully start after Windows login.
-    tokio::time::sleep(Duration::from_secs(10)).await;
+    tokio::time::sleep(schedule.delay).await;
 
     // Set sample rate if specified (trim to handle batch file whitespace)
     if let Ok(rate_str) = std::env::var("AUDIOTESTER_SAMPLE_RATE") {
@@ -193,7 +576,7 @@ async fn auto_configure(engine: EngineHandle) {
         // Select device and start monitoring with retries
         // After reboot, ASIO drivers may need time to fully initialize,
         // so we retry the full select+start cycle
-        for attempt in 1..=20 {
+        for attempt in 1..=schedule.attempts {
             // Re-select device each attempt (fresh ASIO host handle)
             match engine.select_device(device_name.clone()).await {
                 Ok(()) => {
@@ -220,11 +603,15 @@ async fn auto_configure(engine: EngineHandle) {
                 }
             }
 
-            // 5s between each attempt, total retry window ~110s
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            // 5s between attempts by default, total retry window ~110s
+            tokio::time::sleep(schedule.interval).await;
         }
 
-        tracing::error!(device = %device_name, "Failed to auto-configure after 20 attempts");
+        tracing::error!(
+            device = %device_name,
+            attempts = schedule.attempts,
+            "Failed to auto-configure after all attempts"
+        );
     } else if auto_start {
         tracing::info!("Auto-starting monitoring (no device specified)");
         match engine.start().await {
@@ -244,15 +631,51 @@ fn calculate_backoff_ms(attempt: u32) -> u64 {
     delay.min(max_ms)
 }
 
-/// Maximum number of reconnection attempts before requiring manual intervention
-const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+/// Reconnect the engine according to the configured strategy
+///
+/// A full attempt stops the engine, re-selects the device (fresh ASIO host
+/// handle) and starts it again. A streams-only attempt rebuilds the streams
+/// on the existing device handle, which avoids re-enumerating the ASIO host.
+async fn reconnect_engine(
+    engine: &EngineHandle,
+    device: Option<&String>,
+    strategy: ReconnectStrategy,
+    attempt: u32,
+) -> anyhow::Result<()> {
+    if !strategy.is_full_attempt(attempt) {
+        tracing::info!(attempt, "Reconnecting (streams only)");
+        let result = engine.restart_streams().await;
+        if let Err(ref e) = result {
+            tracing::warn!(attempt, error = %e, "Streams-only restart failed");
+        }
+        return result;
+    }
+
+    if let Err(e) = engine.stop().await {
+        tracing::debug!(error = %e, "Stop during reconnect (may be expected)");
+    }
+
+    // Re-select the same device to reinitialize ASIO
+    if let Some(device) = device {
+        if let Err(e) = engine.select_device(device.clone()).await {
+            tracing::warn!(
+                device = %device,
+                error = %e,
+                "Failed to re-select device during reconnect"
+            );
+        }
+    }
+
+    engine.start().await
+}
 
 /// Main monitoring loop - analyzes audio and broadcasts stats
 ///
 /// Includes auto-reconnection with exponential backoff. When the audio engine
-/// encounters an error, it will attempt to reconnect up to MAX_RECONNECT_ATTEMPTS
-/// times with exponential backoff. Stats and graph history are preserved during
-/// reconnection (no clear() is called).
+/// encounters an error, it will attempt to reconnect up to
+/// `ServerConfig::max_reconnect_attempts` times (0 = forever) with exponential
+/// backoff. Stats and graph history are preserved during reconnection (no
+/// clear() is called).
 async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, state: AppState) {
     let mut interval = tokio::time::interval(std::time::Duration::from_millis(100));
     let mut last_status = tray::TrayStatus::Disconnected;
@@ -266,9 +689,46 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
     let mut signal_lost = false;
     let mut signal_lost_since: Option<std::time::Instant> = None;
     let mut reconnect_start: Option<std::time::Instant> = None;
+    // Signal-loss reconnects made since the signal was last valid (drives strategy escalation)
+    let mut signal_loss_reconnect_attempts: u32 = 0;
     // Counter silence tracking: ch1 muted loopback estimated loss.
     let mut counter_silent_since: Option<std::time::Instant> = None;
     let mut cached_sample_rate: u32 = audiotester_core::DEFAULT_SAMPLE_RATE;
+    let mut last_stats_save = std::time::Instant::now();
+    let mut last_availability_tick = std::time::Instant::now();
+    let mut last_incident: Option<std::time::Instant> = None;
+    let mut last_rate_fallback = false;
+    let mut last_engine_state = audiotester_core::audio::engine::EngineState::Stopped;
+    let mut notifier = notify::SignalNotifier::new(state.config.notifications_enabled);
+    let mut alerter = state
+        .config
+        .alert_webhook
+        .as_deref()
+        .map(WebhookAlerter::new);
+    let osc = state
+        .config
+        .osc_target
+        .and_then(|target| match OscSender::new(target) {
+            Ok(sender) => {
+                tracing::info!(%target, "Sending OSC metrics");
+                Some(sender)
+            }
+            Err(e) => {
+                tracing::warn!(%target, error = %e, "Failed to open OSC socket");
+                None
+            }
+        });
+    if let Some(port) = state.config.midi_port.as_deref() {
+        match MidiStatusSender::open(port) {
+            Ok(sender) => {
+                tracing::info!(port, "Sending tray status to MIDI port");
+                let _ = MIDI_STATUS.set(sender);
+            }
+            Err(e) => {
+                tracing::warn!(port, error = %e, "Failed to open MIDI port, MIDI output disabled")
+            }
+        }
+    }
 
     // Wait for Tauri APP_HANDLE to be available (event-driven, no polling)
     if APP_HANDLE.get().is_none() {
@@ -289,15 +749,57 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
         if device_info_update_counter >= 10 {
             device_info_update_counter = 0;
 
+            // Periodic auto-save of statistics (0 = disabled)
+            let save_interval = state.config.stats_save_interval_secs;
+            if let Some(path) = &state.config.stats_path {
+                if save_interval > 0 && last_stats_save.elapsed().as_secs() >= save_interval {
+                    last_stats_save = std::time::Instant::now();
+                    persist_stats(&stats, path).await;
+                }
+            }
+
             // Get engine status and cache in stats store
             if let Ok(engine_status) = engine.get_status().await {
-                if let Ok(mut store) = stats.lock() {
+                {
+                    let mut store = lock_stats(&stats);
                     store.set_uptime(start_time.elapsed().as_secs());
+                    // Measured time since the last pass; stopped time counts as downtime
+                    let now = std::time::Instant::now();
+                    store.tick_availability(
+                        now - last_availability_tick,
+                        engine_status.state
+                            == audiotester_core::audio::engine::EngineState::Running,
+                    );
+                    last_availability_tick = now;
                     store.set_device_info(
                         engine_status.device_name.clone(),
                         engine_status.sample_rate,
-                        0, // Buffer size not exposed by cpal yet
+                        engine_status.actual_buffer_size,
+                    );
+                    store.set_rate_fallback(engine_status.rate_fallback);
+                    store.set_hardware_latency(
+                        engine_status
+                            .hardware_latency_ms
+                            .map(|(input, output)| input + output)
+                            .unwrap_or(0.0),
                     );
+                    store.set_clock_drift(engine_status.clock_drift_ppm);
+                }
+
+                // Log the session summary once monitoring stops
+                if last_engine_state == audiotester_core::audio::engine::EngineState::Running
+                    && engine_status.state == audiotester_core::audio::engine::EngineState::Stopped
+                {
+                    let summary = lock_stats(&stats).session_summary();
+                    tracing::info!(summary = %summary, "Monitoring session ended");
+                }
+                last_engine_state = engine_status.state;
+
+                // Refresh the tray tooltip when a sample-rate fallback starts or ends
+                if engine_status.rate_fallback != last_rate_fallback {
+                    last_rate_fallback = engine_status.rate_fallback;
+                    tray::set_rate_fallback(last_rate_fallback);
+                    emit_tray_status(last_status, 0.0, 0);
                 }
 
                 // Cache sample rate for counter silence estimation
@@ -320,10 +822,14 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
 
             // Update sample counters from engine (cumulative values)
             if let Ok((sent, received)) = engine.get_sample_counts().await {
-                if let Ok(mut store) = stats.lock() {
-                    store.set_samples_sent(sent as u64);
-                    store.set_samples_received(received as u64);
-                }
+                let mut store = lock_stats(&stats);
+                store.set_samples_sent(sent as u64);
+                store.set_samples_received(received as u64);
+            }
+
+            // Per-channel peak levels over the last second (wiring diagnostics)
+            if let Ok(levels) = engine.get_input_levels().await {
+                lock_stats(&stats).set_input_levels(levels);
             }
         }
 
@@ -331,9 +837,7 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
         loss_archive_tick_counter += 1;
         if loss_archive_tick_counter >= 100 {
             loss_archive_tick_counter = 0;
-            if let Ok(mut store) = stats.lock() {
-                store.loss_archive_tick();
-            }
+            lock_stats(&stats).loss_archive_tick();
         }
 
         // Check for ASIO stream invalidation (issue #26):
@@ -342,6 +846,7 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
         // When detected, do a full engine restart for clean measurement state.
         if let Ok(true) = engine.is_stream_invalidated().await {
             tracing::warn!("ASIO stream invalidated (driver reset detected), restarting engine");
+            lock_stats(&stats).record_asio_event(AsioEventKind::Reset);
 
             // Full engine restart: stop → re-select device → start
             if let Err(e) = engine.stop().await {
@@ -364,14 +869,8 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                     signal_lost = false;
                     signal_lost_since = None;
                     counter_silent_since = None;
-                    if let Ok(mut store) = stats.lock() {
-                        store.set_signal_lost(false);
-                        let estimated = store.stats().estimated_loss;
-                        if estimated > 0 {
-                            store.record_loss(estimated);
-                        }
-                        store.reset_estimated_loss();
-                    }
+                    lock_stats(&stats).set_signal_lost(false);
+                    state.commit_estimated_loss();
                 }
                 Err(e) => {
                     tracing::error!(error = %e, "Failed to restart engine after stream invalidation");
@@ -383,17 +882,17 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
         // Try to analyze
         match engine.analyze().await {
             Ok(Some(result)) => {
-                // Check if signal is valid:
-                // 1. Latency must be in valid range (1-100ms for loopback)
-                //    - >100ms indicates MLS period aliasing (no real correlation peak)
-                // 2. Confidence must be above threshold
-                let latency_valid = result.latency_ms > 0.0 && result.latency_ms < 100.0;
-                let confidence_valid = result.confidence >= 0.3;
-                let has_valid_signal = latency_valid && confidence_valid;
+                // Check if signal is valid: latency between min_latency_ms (shorter
+                // means output bleeding into input) and max_latency_ms (longer
+                // means no real match), and confidence at least min_confidence
+                let has_valid_signal = state
+                    .config
+                    .has_valid_signal(result.latency_ms, result.confidence);
 
                 if has_valid_signal {
                     // Update last successful analysis time only for valid signals
                     last_successful_analysis = Some(std::time::Instant::now());
+                    signal_loss_reconnect_attempts = 0;
 
                     // Reset signal_lost if it was set
                     if signal_lost {
@@ -402,23 +901,24 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                             .unwrap_or(0);
                         signal_lost = false;
                         signal_lost_since = None;
-                        if let Ok(mut store) = stats.lock() {
-                            store.set_signal_lost(false);
-                        }
+                        lock_stats(&stats).set_signal_lost(false);
                         tracing::info!(
                             latency_ms = %format!("{:.6}", result.latency_ms),
                             confidence = %format!("{:.3}", result.confidence),
                             lost_duration_ms = lost_duration,
                             "signal_recovered"
                         );
+                        notifier.signal_recovered(
+                            APP_HANDLE.get(),
+                            last_device_name.as_deref().unwrap_or("device"),
+                            result.latency_ms,
+                        );
                     }
-                } else if !signal_lost {
+                } else if !signal_lost && !in_startup_grace(&engine, &state).await {
                     // Invalid signal - set signal_lost immediately
                     signal_lost = true;
                     signal_lost_since = Some(std::time::Instant::now());
-                    if let Ok(mut store) = stats.lock() {
-                        store.set_signal_lost(true);
-                    }
+                    lock_stats(&stats).set_signal_lost(true);
                     tracing::warn!(
                         latency_ms = %format!("{:.6}", result.latency_ms),
                         confidence = %format!("{:.3}", result.confidence),
@@ -438,8 +938,16 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                         let duration = reconnect_start
                             .map(|s| s.elapsed().as_millis() as u64)
                             .unwrap_or(0);
-                        if let Ok(mut store) = stats.lock() {
-                            store.record_disconnection(duration, true);
+                        lock_stats(&stats).record_disconnection(duration, true);
+                        if let Some(path) = &state.config.stats_path {
+                            persist_stats(&stats, path).await;
+                        }
+                        if let Some(alerter) = alerter.as_mut() {
+                            alerter.notify(AlertPayload::from_disconnection(
+                                last_device_name.as_deref(),
+                                duration,
+                                true,
+                            ));
                         }
                         reconnect_in_progress = false;
                         reconnect_start = None;
@@ -447,22 +955,89 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                 }
                 consecutive_failures = 0;
 
-                // Record to stats store (preserve existing data - no clear!)
-                if let Ok(mut store) = stats.lock() {
-                    store.record_latency(result.latency_ms);
-                    store.set_confidence(result.confidence);
+                // Record to stats store (preserve existing data - no clear!),
+                // unless recording is paused via POST /api/v1/recording
+                if state.record_analysis(&result) {
+                    if lock_stats(&stats).stats().anomaly {
+                        tracing::warn!(
+                            latency_ms = %format!("{:.3}", result.latency_ms),
+                            "latency_anomaly"
+                        );
+                    }
                     tracing::debug!(
                         latency_ms = %format!("{:.6}", result.latency_ms),
                         confidence = %format!("{:.3}", result.confidence),
                         lost = result.lost_samples,
                         "stats_recorded"
                     );
-                    if result.lost_samples > 0 {
-                        store.record_loss(result.lost_samples as u64);
+
+                    // Freeze the context around a loss spike (once per cooldown)
+                    let lost = result.lost_samples as u64;
+                    let cooled_down = last_incident.is_none_or(|t| {
+                        t.elapsed().as_secs()
+                            >= audiotester_server::incident::INCIDENT_COOLDOWN_SECS
+                    });
+                    if let Some(dir) = state.config.incident_dir.as_deref() {
+                        if lost >= state.config.incident_min_loss && cooled_down {
+                            last_incident = Some(std::time::Instant::now());
+                            let incident = state.incident_snapshot(lost).await;
+                            let dir = dir.to_path_buf();
+                            // File I/O off the monitoring loop
+                            tokio::task::spawn_blocking(move || match incident.save_in(&dir) {
+                                Ok(path) => tracing::warn!(
+                                    lost,
+                                    path = %path.display(),
+                                    "Loss incident captured"
+                                ),
+                                Err(e) => tracing::warn!(error = %e, "Failed to save incident"),
+                            });
+                        }
+                    }
+                }
+
+                // A frozen buffer repeats one counter value: flag it distinctly
+                // from a muted route so the dashboard can show the real cause
+                {
+                    let mut store = lock_stats(&stats);
+                    if result.counter_stuck && !store.stats().counter_stuck {
+                        tracing::warn!("Counter signal stuck on a constant value (frozen buffer)");
                     }
-                    if result.corrupted_samples > 0 {
-                        store.record_corruption(result.corrupted_samples as u64);
+                    store.set_counter_stuck(result.counter_stuck);
+                }
+
+                // A routing change that adds buffering shifts the latency away
+                // from the commissioned value: flag it and alert once per change.
+                // Cycles without a valid signal keep the previous flag so a
+                // dropout does not look like the latency came back in range.
+                let (latency_out_of_range, was_out_of_range) = {
+                    let mut store = lock_stats(&stats);
+                    let was = store.stats().latency_out_of_range;
+                    let now = state.config.update_latency_out_of_range(
+                        was,
+                        has_valid_signal,
+                        result.latency_ms,
+                    );
+                    store.set_latency_out_of_range(now);
+                    (now, was)
+                };
+                if latency_out_of_range && !was_out_of_range {
+                    tracing::warn!(
+                        latency_ms = %format!("{:.3}", result.latency_ms),
+                        expected_ms = ?state.config.expected_latency_ms,
+                        tolerance_ms = state.config.latency_tolerance_ms,
+                        "latency_out_of_range"
+                    );
+                    if let Some(alerter) = alerter.as_mut() {
+                        alerter.notify(AlertPayload::latency_out_of_range(
+                            last_device_name.as_deref(),
+                            result.latency_ms,
+                        ));
                     }
+                } else if was_out_of_range && !latency_out_of_range {
+                    tracing::info!(
+                        latency_ms = %format!("{:.3}", result.latency_ms),
+                        "Latency back within the expected range"
+                    );
                 }
 
                 // Track counter silence state for estimated loss calculation
@@ -473,40 +1048,38 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                     }
                     // Compute estimated missing samples from elapsed time
                     if let Some(since) = counter_silent_since {
-                        let elapsed_secs = since.elapsed().as_secs_f64();
-                        let estimated = (elapsed_secs * cached_sample_rate as f64) as u64;
-                        if let Ok(mut store) = stats.lock() {
-                            store.set_counter_silent(true);
-                            store.set_estimated_loss(estimated);
-                        }
+                        state.estimate_silent_loss(since.elapsed(), cached_sample_rate);
                     }
                 } else if counter_silent_since.is_some() {
                     // Recovery from silence: commit estimated loss to total_lost
                     // before resetting, so the mute-period samples are not discarded.
+                    // Nothing is committed while recording is paused.
                     counter_silent_since = None;
-                    if let Ok(mut store) = stats.lock() {
-                        let estimated = store.stats().estimated_loss;
-                        if estimated > 0 {
-                            tracing::info!(
-                                estimated_loss = estimated,
-                                "Counter signal recovered — committing estimated loss to total"
-                            );
-                            store.record_loss(estimated);
-                        } else {
-                            tracing::info!("Counter signal recovered (ch1 unmuted)");
-                        }
-                        store.reset_estimated_loss();
+                    let estimated = state.commit_estimated_loss();
+                    if estimated > 0 {
+                        tracing::info!(
+                            estimated_loss = estimated,
+                            "Counter signal recovered — committed estimated loss to total"
+                        );
+                    } else {
+                        tracing::info!("Counter signal recovered (ch1 unmuted)");
                     }
                 }
 
                 // Broadcast to WebSocket clients
                 audiotester_server::ws::broadcast_stats(&state);
+                send_osc_metrics(osc.as_ref(), &stats);
 
                 // Update tray icon status (only if changed to reduce overhead)
+                let loss_rate_per_min = lock_stats(&stats).loss_rate_per_min();
                 let new_status = tray::status_from_analysis(
                     result.latency_ms,
                     result.lost_samples as u64,
                     result.corrupted_samples as u64,
+                    loss_rate_per_min,
+                    result.counter_silent,
+                    latency_out_of_range,
+                    &state.latency_thresholds,
                 );
 
                 if new_status != last_status {
@@ -517,17 +1090,22 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
             }
             Ok(None) => {
                 // No result yet (engine might be stopped or warming up)
-                // Check for signal timeout (1 second without analysis result while engine running)
+                // Check for signal timeout (no analysis result within signal_timeout_ms while engine running)
                 // Skip timeout check during probe grace period (engine just restarted)
                 if let Ok(status) = engine.get_status().await {
-                    if status.state == audiotester_core::audio::engine::EngineState::Running {
+                    if status.test_tone_active {
+                        // No bursts while a test tone plays: restart the timeout after it
+                        last_successful_analysis = Some(std::time::Instant::now());
+                    } else if status.state == audiotester_core::audio::engine::EngineState::Running
+                        && !state.config.in_startup_grace(status.started_at)
+                    {
                         if let Some(last) = last_successful_analysis {
-                            if last.elapsed() > Duration::from_secs(1) && !signal_lost {
+                            let timeout =
+                                Duration::from_millis(state.loss_timeouts.signal_timeout_ms());
+                            if last.elapsed() > timeout && !signal_lost {
                                 signal_lost = true;
                                 signal_lost_since = Some(std::time::Instant::now());
-                                if let Ok(mut store) = stats.lock() {
-                                    store.set_signal_lost(true);
-                                }
+                                lock_stats(&stats).set_signal_lost(true);
                                 tracing::warn!("No signal detected (analysis timeout)");
                             }
                         }
@@ -535,6 +1113,7 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                 }
                 // Still broadcast stats so dashboard updates
                 audiotester_server::ws::broadcast_stats(&state);
+                send_osc_metrics(osc.as_ref(), &stats);
             }
             Err(e) => {
                 // Engine error - attempt reconnection
@@ -544,11 +1123,12 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                 }
                 reconnect_in_progress = true;
 
-                if consecutive_failures <= MAX_RECONNECT_ATTEMPTS {
+                let action = state.config.reconnect_action(consecutive_failures);
+                if action == ReconnectAction::Retry {
                     let backoff = calculate_backoff_ms(consecutive_failures);
                     tracing::warn!(
                         attempt = consecutive_failures,
-                        max = MAX_RECONNECT_ATTEMPTS,
+                        max = state.config.max_reconnect_attempts,
                         backoff_ms = backoff,
                         error = %e,
                         "Audio engine error, attempting reconnection"
@@ -563,25 +1143,17 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                     // Wait with exponential backoff before next attempt
                     tokio::time::sleep(Duration::from_millis(backoff)).await;
 
-                    // FULL reconnection: stop, re-select device, start
-                    // This handles buffer size changes in ASIO driver
-                    if let Err(stop_err) = engine.stop().await {
-                        tracing::debug!(error = %stop_err, "Stop during reconnect (may be expected)");
-                    }
-
-                    // Re-select the same device to reinitialize ASIO
-                    if let Some(ref device) = last_device_name {
-                        if let Err(select_err) = engine.select_device(device.clone()).await {
-                            tracing::warn!(
-                                device = %device,
-                                error = %select_err,
-                                "Failed to re-select device during reconnect"
-                            );
-                        }
-                    }
-
-                    // Try to restart the engine
-                    match engine.start().await {
+                    // Full reconnection (stop, re-select, start) handles buffer
+                    // size changes in the ASIO driver; streams-only is faster
+                    // for transient errors and escalates to full on repeat failure
+                    match reconnect_engine(
+                        &engine,
+                        last_device_name.as_ref(),
+                        state.config.reconnect_strategy,
+                        consecutive_failures,
+                    )
+                    .await
+                    {
                         Ok(()) => {
                             tracing::info!(
                                 attempt = consecutive_failures,
@@ -598,64 +1170,80 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
                             );
                         }
                     }
-                } else if consecutive_failures == MAX_RECONNECT_ATTEMPTS + 1 {
+                } else if action == ReconnectAction::GiveUp {
                     // Only log once when max attempts exceeded
                     tracing::error!(
                         "Max reconnection attempts ({}) exceeded. Manual intervention required.",
-                        MAX_RECONNECT_ATTEMPTS
+                        state.config.max_reconnect_attempts
                     );
 
                     // Record failed reconnection with actual duration
                     let duration = reconnect_start
                         .map(|s| s.elapsed().as_millis() as u64)
                         .unwrap_or(0);
-                    if let Ok(mut store) = stats.lock() {
-                        store.record_disconnection(duration, false);
+                    lock_stats(&stats).record_disconnection(duration, false);
+                    if let Some(path) = &state.config.stats_path {
+                        persist_stats(&stats, path).await;
+                    }
+                    if let Some(alerter) = alerter.as_mut() {
+                        alerter.notify(AlertPayload::from_disconnection(
+                            last_device_name.as_deref(),
+                            duration,
+                            false,
+                        ));
                     }
                     reconnect_start = None;
                 }
             }
         }
 
-        // Signal-loss reconnection: when signal has been lost for >10s,
-        // attempt to reconnect by stopping and restarting the engine.
+        // Signal-loss reconnection: when signal has been lost for longer than
+        // reconnect_after_loss_ms, attempt to reconnect by stopping and
+        // restarting the engine.
         // This handles ASIO driver restarts (e.g. VBMatrix buffer changes)
         // where streams stay alive but receive silence.
         // Suppressed during ASIO restart recovery (which has its own settle/reconnect).
+        if signal_lost {
+            if let Some(lost_since) = signal_lost_since {
+                notifier.signal_lost(
+                    APP_HANDLE.get(),
+                    lost_since,
+                    last_device_name.as_deref().unwrap_or("device"),
+                );
+            }
+        }
+
         if signal_lost && !reconnect_in_progress {
             if let Some(lost_since) = signal_lost_since {
-                if lost_since.elapsed() > Duration::from_secs(10) {
-                    tracing::warn!("Signal lost for >10s, attempting ASIO reconnection");
+                let reconnect_after =
+                    Duration::from_millis(state.loss_timeouts.reconnect_after_loss_ms());
+                if lost_since.elapsed() > reconnect_after {
+                    tracing::warn!(
+                        lost_ms = lost_since.elapsed().as_millis() as u64,
+                        "Signal lost, attempting ASIO reconnection"
+                    );
 
                     if last_status != tray::TrayStatus::Disconnected {
                         last_status = tray::TrayStatus::Disconnected;
                         emit_tray_status(tray::TrayStatus::Disconnected, 0.0, 0);
                     }
 
-                    // Full reconnection: stop, re-select device, start
-                    if let Err(e) = engine.stop().await {
-                        tracing::debug!(error = %e, "Stop during signal-loss reconnect");
-                    }
-
-                    if let Some(ref device) = last_device_name {
-                        if let Err(e) = engine.select_device(device.clone()).await {
-                            tracing::warn!(error = %e, "Failed to re-select device");
-                        }
-                    }
-
-                    match engine.start().await {
+                    signal_loss_reconnect_attempts += 1;
+                    match reconnect_engine(
+                        &engine,
+                        last_device_name.as_ref(),
+                        state.config.reconnect_strategy,
+                        signal_loss_reconnect_attempts,
+                    )
+                    .await
+                    {
                         Ok(()) => {
                             tracing::info!("Engine restarted after signal loss");
+                            lock_stats(&stats).record_asio_event(AsioEventKind::VirtualRestart);
                             last_successful_analysis = None;
                             signal_lost_since = Some(std::time::Instant::now());
                             counter_silent_since = None;
-                            if let Ok(mut store) = stats.lock() {
-                                let estimated = store.stats().estimated_loss;
-                                if estimated > 0 {
-                                    store.record_loss(estimated);
-                                }
-                                store.reset_estimated_loss();
-                            }
+                            state.commit_estimated_loss();
                         }
                         Err(e) => {
                             tracing::error!(error = %e, "Failed to restart after signal loss");
@@ -669,8 +1257,34 @@ async fn monitoring_loop(engine: EngineHandle, stats: Arc<Mutex<StatsStore>>, st
     }
 }
 
+/// Send the live metrics to the OSC receiver, if configured
+fn send_osc_metrics(osc: Option<&OscSender>, stats: &Mutex<StatsStore>) {
+    if let Some(osc) = osc {
+        let (latency, lost, signal_lost) = {
+            let store = lock_stats(stats);
+            let s = store.stats();
+            (s.current_latency, s.total_lost, s.signal_lost)
+        };
+        osc.send_metrics(latency, lost, !signal_lost);
+    }
+}
+
+/// Whether the engine is still inside the configured startup grace period
+///
+/// Drivers can take a while after `start()` before detections stabilize;
+/// signal loss is never reported during this window.
+async fn in_startup_grace(engine: &EngineHandle, state: &AppState) -> bool {
+    match engine.get_status().await {
+        Ok(status) => state.config.in_startup_grace(status.started_at),
+        Err(_) => false,
+    }
+}
+
 /// Emit a tray status event to update the system tray icon
 fn emit_tray_status(status: tray::TrayStatus, latency_ms: f64, lost_samples: u64) {
+    if let Some(midi) = MIDI_STATUS.get() {
+        midi.show(status.light());
+    }
     if let Some(app) = APP_HANDLE.get() {
         let event = tray::TrayStatusEvent {
             status,
@@ -0,0 +1,175 @@
+//! Native desktop notifications for signal loss and recovery
+//!
+//! Lets operators who keep the window hidden notice a dropped path from the
+//! tray alone. Notifications are debounced per state so a flapping signal
+//! does not flood the notification center.
+
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+
+/// How long the signal must stay lost before notifying
+pub const SIGNAL_LOST_NOTIFY_AFTER: Duration = Duration::from_secs(10);
+
+/// Minimum time between two notifications of the same kind
+const NOTIFY_DEBOUNCE: Duration = Duration::from_secs(30);
+
+/// Debounced signal lost/recovered notifier
+pub struct SignalNotifier {
+    enabled: bool,
+    /// Whether a "lost" notification is outstanding (recovery is only
+    /// reported for losses the user was told about)
+    lost_notified: bool,
+    last_lost: Option<Instant>,
+    last_recovered: Option<Instant>,
+}
+
+impl SignalNotifier {
+    /// Create a notifier; when `enabled` is false nothing is ever shown
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            lost_notified: false,
+            last_lost: None,
+            last_recovered: None,
+        }
+    }
+
+    /// Report that the signal has been lost since `lost_since`
+    ///
+    /// Called every tick while the signal is lost; notifies once the loss
+    /// exceeds [`SIGNAL_LOST_NOTIFY_AFTER`]. Returns true when this call
+    /// notified (or would have, with notifications disabled).
+    pub fn signal_lost(
+        &mut self,
+        app: Option<&AppHandle>,
+        lost_since: Instant,
+        device: &str,
+    ) -> bool {
+        if self.lost_notified || lost_since.elapsed() < SIGNAL_LOST_NOTIFY_AFTER {
+            return false;
+        }
+        self.lost_notified = true;
+        if !Self::debounced(&mut self.last_lost) {
+            return false;
+        }
+        self.show(
+            app,
+            "Audiotester: signal lost",
+            &format!(
+                "No test signal on {} for {}s",
+                device,
+                lost_since.elapsed().as_secs()
+            ),
+        );
+        true
+    }
+
+    /// Report that a valid signal is being received again
+    ///
+    /// Returns true when this call notified (or would have, with
+    /// notifications disabled).
+    pub fn signal_recovered(
+        &mut self,
+        app: Option<&AppHandle>,
+        device: &str,
+        latency_ms: f64,
+    ) -> bool {
+        if !std::mem::take(&mut self.lost_notified) {
+            return false;
+        }
+        if !Self::debounced(&mut self.last_recovered) {
+            return false;
+        }
+        self.show(
+            app,
+            "Audiotester: signal recovered",
+            &format!("{} is back, latency {:.2} ms", device, latency_ms),
+        );
+        true
+    }
+
+    /// True (and records now) if no notification of this kind was sent recently
+    fn debounced(last: &mut Option<Instant>) -> bool {
+        if last.is_some_and(|t| t.elapsed() < NOTIFY_DEBOUNCE) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+
+    fn show(&self, app: Option<&AppHandle>, title: &str, body: &str) {
+        if !self.enabled {
+            return;
+        }
+        let Some(app) = app else {
+            return;
+        };
+        tracing::debug!(title, body, "Showing desktop notification");
+        if let Err(e) = app.notification().builder().title(title).body(body).show() {
+            tracing::warn!("Failed to show notification: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A loss that started `secs` seconds ago
+    fn lost_for(secs: u64) -> Instant {
+        Instant::now() - Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn test_lost_notifies_once_after_threshold() {
+        let mut notifier = SignalNotifier::new(true);
+        // Short dropouts are not reported
+        assert!(!notifier.signal_lost(None, lost_for(0), "dev"));
+        assert!(!notifier.signal_lost(None, lost_for(9), "dev"));
+        // Past the threshold: one notification, not one per tick
+        assert!(notifier.signal_lost(None, lost_for(11), "dev"));
+        assert!(!notifier.signal_lost(None, lost_for(12), "dev"));
+    }
+
+    #[test]
+    fn test_recovered_only_after_notified_loss() {
+        let mut notifier = SignalNotifier::new(true);
+        // A loss the user was never told about is not followed by a recovery
+        assert!(!notifier.signal_lost(None, lost_for(2), "dev"));
+        assert!(!notifier.signal_recovered(None, "dev", 5.0));
+
+        assert!(notifier.signal_lost(None, lost_for(11), "dev"));
+        assert!(notifier.signal_recovered(None, "dev", 5.0));
+        // Recovery is reported once per loss
+        assert!(!notifier.signal_recovered(None, "dev", 5.0));
+    }
+
+    #[test]
+    fn test_flapping_signal_debounced() {
+        let mut notifier = SignalNotifier::new(true);
+        assert!(notifier.signal_lost(None, lost_for(11), "dev"));
+        assert!(notifier.signal_recovered(None, "dev", 5.0));
+
+        // A second long loss within the debounce window stays silent,
+        // and so does its recovery
+        assert!(!notifier.signal_lost(None, lost_for(11), "dev"));
+        assert!(!notifier.signal_recovered(None, "dev", 5.0));
+
+        // Once the window has passed both kinds notify again
+        let expired = Instant::now() - NOTIFY_DEBOUNCE - Duration::from_secs(1);
+        notifier.last_lost = Some(expired);
+        notifier.last_recovered = Some(expired);
+        assert!(notifier.signal_lost(None, lost_for(11), "dev"));
+        assert!(notifier.signal_recovered(None, "dev", 5.0));
+    }
+
+    #[test]
+    fn test_disabled_still_tracks_transitions() {
+        // Nothing is shown, but the state machine behaves the same so
+        // enabling notifications later does not replay stale events
+        let mut notifier = SignalNotifier::new(false);
+        assert!(notifier.signal_lost(None, lost_for(11), "dev"));
+        assert!(notifier.signal_recovered(None, "dev", 5.0));
+    }
+}
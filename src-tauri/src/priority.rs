@@ -0,0 +1,91 @@
+//! Process priority class selection
+//!
+//! The app raises its own priority so ASIO callbacks are not starved when
+//! other windows overlap the dashboard (issue #23). HIGH is the default;
+//! `AUDIOTESTER_PRIORITY` picks NORMAL for shared machines or REALTIME for
+//! dedicated ones.
+
+/// Windows process priority class requested for the app
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProcessPriority {
+    /// `NORMAL_PRIORITY_CLASS`
+    Normal,
+    /// `HIGH_PRIORITY_CLASS`
+    #[default]
+    High,
+    /// `REALTIME_PRIORITY_CLASS` (falls back to HIGH without admin rights)
+    Realtime,
+}
+
+impl ProcessPriority {
+    /// Win32 priority class constant for `SetPriorityClass`
+    #[cfg(target_os = "windows")]
+    pub fn class(self) -> windows_sys::Win32::System::Threading::PROCESS_CREATION_FLAGS {
+        use windows_sys::Win32::System::Threading::{
+            HIGH_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, REALTIME_PRIORITY_CLASS,
+        };
+        match self {
+            ProcessPriority::Normal => NORMAL_PRIORITY_CLASS,
+            ProcessPriority::High => HIGH_PRIORITY_CLASS,
+            ProcessPriority::Realtime => REALTIME_PRIORITY_CLASS,
+        }
+    }
+}
+
+impl std::fmt::Display for ProcessPriority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ProcessPriority::Normal => "NORMAL",
+            ProcessPriority::High => "HIGH",
+            ProcessPriority::Realtime => "REALTIME",
+        })
+    }
+}
+
+impl std::str::FromStr for ProcessPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "normal" => Ok(ProcessPriority::Normal),
+            "high" => Ok(ProcessPriority::High),
+            "realtime" => Ok(ProcessPriority::Realtime),
+            other => Err(format!(
+                "Unknown process priority: {} (expected normal, high or realtime)",
+                other
+            )),
+        }
+    }
+}
+
+/// Apply `priority` to the current process, returning whether it succeeded
+#[cfg(target_os = "windows")]
+pub fn apply(priority: ProcessPriority) -> bool {
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, SetPriorityClass};
+    unsafe { SetPriorityClass(GetCurrentProcess(), priority.class()) != 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_priority() {
+        assert_eq!("normal".parse(), Ok(ProcessPriority::Normal));
+        assert_eq!(" HIGH ".parse(), Ok(ProcessPriority::High));
+        assert_eq!("Realtime".parse(), Ok(ProcessPriority::Realtime));
+        assert!("idle".parse::<ProcessPriority>().is_err());
+        assert_eq!(ProcessPriority::default(), ProcessPriority::High);
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn test_priority_class() {
+        use windows_sys::Win32::System::Threading::{
+            HIGH_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, REALTIME_PRIORITY_CLASS,
+        };
+        assert_eq!(ProcessPriority::Normal.class(), NORMAL_PRIORITY_CLASS);
+        assert_eq!(ProcessPriority::High.class(), HIGH_PRIORITY_CLASS);
+        assert_eq!(ProcessPriority::Realtime.class(), REALTIME_PRIORITY_CLASS);
+    }
+}
@@ -3,7 +3,10 @@
 //! Provides system tray icon with status indication and context menu.
 //! Status updates are handled via Tauri global events.
 
+use audiotester_server::midi::StatusLight;
+use audiotester_server::{LatencyLevel, LatencyThresholds};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::image::Image;
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
@@ -15,9 +18,23 @@ pub enum TrayStatus {
     Ok,
     Warning,
     Error,
+    /// Burst path alive but the counter channel is silent (loopback muted)
+    Muted,
     Disconnected,
 }
 
+impl TrayStatus {
+    /// MIDI indicator light for this status (muted counts as a warning)
+    pub fn light(self) -> StatusLight {
+        match self {
+            TrayStatus::Ok => StatusLight::Green,
+            TrayStatus::Warning | TrayStatus::Muted => StatusLight::Yellow,
+            TrayStatus::Error => StatusLight::Red,
+            TrayStatus::Disconnected => StatusLight::Off,
+        }
+    }
+}
+
 /// Status event payload for tray icon updates
 ///
 /// Emitted from the monitoring loop to update tray icon color
@@ -32,11 +49,45 @@ pub struct TrayStatusEvent {
     pub lost_samples: u64,
 }
 
+/// Actions available from the tray menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayAction {
+    OpenDashboard,
+    ShowRemoteUrl,
+    Exit,
+}
+
+impl TrayAction {
+    /// Map a menu item id to its action
+    fn from_menu_id(id: &str) -> Option<Self> {
+        match id {
+            "dashboard" => Some(Self::OpenDashboard),
+            "remote" => Some(Self::ShowRemoteUrl),
+            "quit" => Some(Self::Exit),
+            _ => None,
+        }
+    }
+}
+
+/// Set when the engine runs at a different sample rate than configured
+static RATE_FALLBACK: AtomicBool = AtomicBool::new(false);
+
+/// Record whether a sample-rate fallback occurred (shown in the tooltip)
+pub fn set_rate_fallback(fallback: bool) {
+    RATE_FALLBACK.store(fallback, Ordering::Relaxed);
+}
+
 /// Icon size in pixels
 const ICON_SIZE: u32 = 16;
 
 /// Set up the tray icon with menu
-pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+///
+/// `on_exit` handles [`TrayAction::Exit`] and is responsible for shutting
+/// down and exiting the application.
+pub fn setup_tray<F>(app: &AppHandle, on_exit: F) -> Result<(), Box<dyn std::error::Error>>
+where
+    F: Fn(&AppHandle) + Send + Sync + 'static,
+{
     let version_label = format!(
         "v{} ({})",
         audiotester_core::VERSION,
@@ -76,22 +127,21 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .icon(icon)
         .tooltip("Audiotester - Audio Monitor")
         .menu(&menu)
-        .on_menu_event(move |app, event| {
-            let id = event.id.as_ref();
-            match id {
-                "dashboard" => {
+        .on_menu_event(
+            move |app, event| match TrayAction::from_menu_id(event.id.as_ref()) {
+                Some(TrayAction::OpenDashboard) => {
                     open_dashboard(app);
                 }
-                "remote" => {
+                Some(TrayAction::ShowRemoteUrl) => {
                     show_remote_url(app);
                 }
-                "quit" => {
+                Some(TrayAction::Exit) => {
                     tracing::info!("Exit requested from tray");
-                    app.exit(0);
+                    on_exit(app);
                 }
-                _ => {}
-            }
-        })
+                None => {}
+            },
+        )
         .on_tray_icon_event(|tray, event| {
             // Left-click opens dashboard directly (no menu)
             if let TrayIconEvent::Click {
@@ -158,6 +208,7 @@ pub fn make_status_icon(status: TrayStatus) -> Image<'static> {
         TrayStatus::Ok => (0x00u8, 0xC8u8, 0x00u8),
         TrayStatus::Warning => (0xFF, 0xA5, 0x00),
         TrayStatus::Error => (0xFF, 0x00, 0x00),
+        TrayStatus::Muted => (0x40, 0x80, 0xFF),
         TrayStatus::Disconnected => (0x80, 0x80, 0x80),
     };
 
@@ -220,11 +271,16 @@ pub fn update_tray_status(
         // Update tooltip with status info
         let tooltip = match status {
             TrayStatus::Ok => "Audiotester - Monitoring OK",
-            TrayStatus::Warning => "Audiotester - Warning (sample loss detected)",
+            TrayStatus::Warning => "Audiotester - Warning (sample loss or elevated latency)",
             TrayStatus::Error => "Audiotester - Error (high latency)",
+            TrayStatus::Muted => "Audiotester - Loopback muted (counter silent)",
             TrayStatus::Disconnected => "Audiotester - Disconnected",
         };
-        tray.set_tooltip(Some(tooltip))?;
+        if RATE_FALLBACK.load(Ordering::Relaxed) && status != TrayStatus::Disconnected {
+            tray.set_tooltip(Some(format!("{tooltip} [sample rate fallback]")))?;
+        } else {
+            tray.set_tooltip(Some(tooltip))?;
+        }
 
         tracing::trace!("Tray icon updated to {:?}", status);
     }
@@ -234,20 +290,83 @@ pub fn update_tray_status(
 /// Determine tray status from analysis results
 ///
 /// # Status mapping:
-/// - Ok (green): Latency < 50ms, no sample loss
-/// - Warning (orange): Sample loss detected
-/// - Error (red): Latency >= 50ms
+/// - Ok (green): Latency below the warning threshold, no sample loss
+/// - Warning (orange): Sample loss detected now or within the last minute,
+///   or latency at or above the warning threshold or outside the expected range
+/// - Error (red): Latency at or above the error threshold, no sample loss
+/// - Muted (blue): Counter channel silent, so loss cannot be measured
 /// - Disconnected (gray): Not monitoring
+///
+/// Loss takes priority over high latency. With the default thresholds
+/// (both 50 ms) there is no separate warning band.
 pub fn status_from_analysis(
     latency_ms: f64,
     lost_samples: u64,
     corrupted_samples: u64,
+    loss_rate_per_min: f64,
+    counter_silent: bool,
+    latency_out_of_range: bool,
+    thresholds: &LatencyThresholds,
 ) -> TrayStatus {
-    if lost_samples > 0 || corrupted_samples > 0 {
+    let level = thresholds.classify(latency_ms);
+    if counter_silent {
+        TrayStatus::Muted
+    } else if lost_samples > 0 || corrupted_samples > 0 || loss_rate_per_min > 0.0 {
         TrayStatus::Warning
-    } else if latency_ms >= 50.0 {
+    } else if level == LatencyLevel::Error {
         TrayStatus::Error
+    } else if level == LatencyLevel::Warning || latency_out_of_range {
+        TrayStatus::Warning
     } else {
         TrayStatus::Ok
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(latency_ms: f64, lost_samples: u64, thresholds: &LatencyThresholds) -> TrayStatus {
+        status_from_analysis(latency_ms, lost_samples, 0, 0.0, false, false, thresholds)
+    }
+
+    #[test]
+    fn test_default_thresholds_have_no_warning_band() {
+        let config = audiotester_server::ServerConfig::default();
+        let thresholds = LatencyThresholds::new(config.latency_warning_ms, config.latency_error_ms);
+        assert_eq!(status(49.9, 0, &thresholds), TrayStatus::Ok);
+        assert_eq!(status(50.0, 0, &thresholds), TrayStatus::Error);
+        assert_eq!(status(100.0, 5, &thresholds), TrayStatus::Warning);
+    }
+
+    #[test]
+    fn test_custom_threshold_boundaries() {
+        let thresholds = LatencyThresholds::new(10.0, 20.0);
+        assert_eq!(status(9.99, 0, &thresholds), TrayStatus::Ok);
+        assert_eq!(status(10.0, 0, &thresholds), TrayStatus::Warning);
+        assert_eq!(status(19.99, 0, &thresholds), TrayStatus::Warning);
+        assert_eq!(status(20.0, 0, &thresholds), TrayStatus::Error);
+        // Loss still takes priority over high latency
+        assert_eq!(status(30.0, 1, &thresholds), TrayStatus::Warning);
+    }
+
+    #[test]
+    fn test_status_precedence() {
+        let thresholds = LatencyThresholds::new(10.0, 20.0);
+        // A silent counter hides everything else
+        assert_eq!(
+            status_from_analysis(100.0, 5, 1, 2.0, true, true, &thresholds),
+            TrayStatus::Muted
+        );
+        // Out of the expected range is a warning even at low latency
+        assert_eq!(
+            status_from_analysis(5.0, 0, 0, 0.0, false, true, &thresholds),
+            TrayStatus::Warning
+        );
+        // Recent loss keeps the warning after the counter recovers
+        assert_eq!(
+            status_from_analysis(5.0, 0, 0, 0.5, false, false, &thresholds),
+            TrayStatus::Warning
+        );
+    }
+}
@@ -118,6 +118,8 @@ fn test_extended_plot_data() {
 #[test]
 fn test_loss_events_with_timestamps() {
     let mut store = StatsStore::new();
+    // Keep back-to-back losses as separate events
+    store.set_loss_coalesce_ms(0);
 
     store.record_loss(5);
     store.record_loss(10);
@@ -138,6 +138,8 @@ fn test_reset_counters_preserves_history() {
 #[test]
 fn test_loss_event_recording() {
     let mut store = StatsStore::new();
+    // Keep back-to-back losses as separate events
+    store.set_loss_coalesce_ms(0);
 
     store.record_loss(5);
     store.record_loss(10);